@@ -10,6 +10,8 @@ CONTRACT="output/elrond-nftmanager.wasm"
 PAYMENT_TOKEN_ID="EGLD"
 NFT_TOKEN_PRICE=1000000000000000
 ROYALTIES=300
+MAX_ROYALTIES=1000
+MAX_SUPPLY=0 # 0 means unlimited
 IMAGE_BASE_URI="https://ipfs.io/ipfs/QmXSFnUfdot3SgLsuZFdpefXii31YuyvtAD23NKdz9toar"
 METADATA_BASE_URI="https://ipfs.io/ipfs/QmS1Zn9ytigCjkNtQPAduFuzptadNw2kC9asptoYq9ZBwS"
 
@@ -25,7 +27,7 @@ deploy() {
     --gas-limit=100000000 \
     --send --outfile="deploy-devnet.interaction.json" \
     --proxy="${PROXY}" \
-    --arguments ${PAYMENT_TOKEN_ID_HEX} ${NFT_TOKEN_PRICE} ${TOKEN_PRICE} ${ROYALTIES} ${IMAGE_BASE_URI_HEX} ${METADATA_BASE_URI_HEX} \
+    --arguments ${PAYMENT_TOKEN_ID_HEX} ${NFT_TOKEN_PRICE} ${TOKEN_PRICE} ${ROYALTIES} ${IMAGE_BASE_URI_HEX} ${METADATA_BASE_URI_HEX} ${MAX_ROYALTIES} ${MAX_SUPPLY} \
     --metadata-payable  \
     --metadata-payable-by-sc \
     --chain=${CHAIN_ID} || return
@@ -0,0 +1,112 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum Role {
+    // the contract owner, implicit and never granted
+    Owner,
+    Admin,
+    Operator,
+    Treasurer,
+    MetadataManager,
+}
+
+impl Role {
+    // roles an admin holds implicitly and may hand out
+    fn is_delegated_by_admin(&self) -> bool {
+        matches!(self, Role::Operator | Role::MetadataManager)
+    }
+}
+
+// The owner holds every role. Admins additionally act as operators and metadata
+// managers; treasurers can only be appointed by the owner.
+#[elrond_wasm::module]
+pub trait AccessControlModule: crate::audit::AuditModule {
+    // endpoints - owner-only
+
+    #[only_owner]
+    #[endpoint(addAdmin)]
+    fn add_admin(&self, address: ManagedAddress) -> SCResult<()> {
+        self.grant_role(Role::Admin, address)
+    }
+
+    #[only_owner]
+    #[endpoint(removeAdmin)]
+    fn remove_admin(&self, address: ManagedAddress) -> SCResult<()> {
+        self.revoke_role(Role::Admin, address)
+    }
+
+    // endpoints
+
+    #[endpoint(grantRole)]
+    fn grant_role(&self, role: Role, address: ManagedAddress) -> SCResult<()> {
+        self.require_can_manage_role(role);
+
+        if self.role_members(role).insert(address.clone()) {
+            self.log_entry_added(self.audit_key_with(b"role_members", &role), &address);
+        }
+
+        Ok(())
+    }
+
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, role: Role, address: ManagedAddress) -> SCResult<()> {
+        self.require_can_manage_role(role);
+
+        if self.role_members(role).swap_remove(&address) {
+            self.log_entry_removed(self.audit_key_with(b"role_members", &role), &address);
+        }
+
+        Ok(())
+    }
+
+    // private
+
+    fn require_role(&self, role: Role) {
+        let caller = self.blockchain().get_caller();
+        require!(self.has_role(role, caller), "permission denied");
+    }
+
+    fn require_can_manage_role(&self, role: Role) {
+        require!(role != Role::Owner, "owner role cannot be granted");
+
+        let caller = self.blockchain().get_caller();
+        if caller == self.blockchain().get_owner_address() {
+            return;
+        }
+
+        require!(
+            role.is_delegated_by_admin() && self.role_members(Role::Admin).contains(&caller),
+            "permission denied"
+        );
+    }
+
+    // views
+
+    #[view(hasRole)]
+    fn has_role(&self, role: Role, address: ManagedAddress) -> bool {
+        if address == self.blockchain().get_owner_address() {
+            return true;
+        }
+        if self.role_members(role).contains(&address) {
+            return true;
+        }
+
+        role.is_delegated_by_admin() && self.role_members(Role::Admin).contains(&address)
+    }
+
+    #[view(getAdmins)]
+    fn get_admins(&self) -> MultiResultVec<ManagedAddress> {
+        self.get_role_members(Role::Admin)
+    }
+
+    #[view(getRoleMembers)]
+    fn get_role_members(&self, role: Role) -> MultiResultVec<ManagedAddress> {
+        self.role_members(role).iter().collect::<Vec<ManagedAddress>>().into()
+    }
+
+    // storage
+
+    #[storage_mapper("role_members")]
+    fn role_members(&self, role: Role) -> UnorderedSetMapper<ManagedAddress>;
+}
@@ -0,0 +1,157 @@
+elrond_wasm::imports!();
+
+use crate::access_control::Role;
+
+const UPDATE_ATTRIBUTES_FUNC_NAME: &[u8] = b"ESDTNFTUpdateAttributes";
+const ADD_URI_FUNC_NAME: &[u8] = b"ESDTNFTAddURI";
+const BUILT_IN_CALL_GAS: u64 = 1_000_000;
+const UPDATED_NFT_AMOUNT: u32 = 1;
+
+// Attribute and URI updates after mint for evolving collections. The protocol only
+// lets the holder of a token with the NftUpdateAttributes / NftAddUri role change
+// it, so tokens held by the contract (staked or in inventory) are updated right
+// away; for any other token the change waits until the holder sends it in through
+// `applyAttributeUpdate` and gets it back updated.
+#[elrond_wasm::module]
+pub trait AttributeUpdatesModule:
+    crate::access_control::AccessControlModule + crate::audit::AuditModule + crate::storage::StorageModule
+{
+    // endpoints - owner-only
+
+    // true when added right away, false when left pending for the holder
+    #[only_owner]
+    #[endpoint(addUri)]
+    fn add_uri(&self, nonce: u64, #[var_args] uris: ManagedVarArgs<ManagedBuffer>) -> bool {
+        require!(!uris.is_empty(), "no uri given");
+
+        let nft_token_id = self.nft_token_id().get();
+        let mut all_uris = self.pending_uris(nonce).get();
+        for uri in uris.into_iter() {
+            all_uris.push(uri);
+        }
+
+        if self.blockchain().get_sc_balance(&nft_token_id, nonce) > 0 {
+            self.apply_uris(&nft_token_id, nonce, &all_uris);
+            if !self.pending_uris(nonce).is_empty() {
+                self.audited_clear(&self.pending_uris(nonce), self.audit_key_with(b"pending_uris", &nonce));
+            }
+            return true;
+        }
+
+        self.audited_set(&self.pending_uris(nonce), self.audit_key_with(b"pending_uris", &nonce), &all_uris);
+
+        false
+    }
+
+    // endpoints - role-gated
+
+    // true when applied right away, false when left pending for the holder
+    #[endpoint(updateAttributes)]
+    fn update_attributes(&self, nonce: u64, new_attributes: ManagedBuffer) -> bool {
+        self.require_role(Role::MetadataManager);
+
+        let nft_token_id = self.nft_token_id().get();
+        if self.blockchain().get_sc_balance(&nft_token_id, nonce) > 0 {
+            self.apply_attributes(&nft_token_id, nonce, &new_attributes);
+            if !self.pending_attributes(nonce).is_empty() {
+                self.audited_clear(&self.pending_attributes(nonce), self.audit_key_with(b"pending_attributes", &nonce));
+            }
+            return true;
+        }
+
+        self.audited_set(
+            &self.pending_attributes(nonce),
+            self.audit_key_with(b"pending_attributes", &nonce),
+            &new_attributes,
+        );
+
+        false
+    }
+
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(applyAttributeUpdate)]
+    fn apply_attribute_update(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_nonce] payment_nonce: u64,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        require!(payment_token == self.nft_token_id().get(), "only collection NFTs can be updated");
+        require!(payment_amount == UPDATED_NFT_AMOUNT, "invalid amount");
+
+        let has_attributes = !self.pending_attributes(payment_nonce).is_empty();
+        let has_uris = !self.pending_uris(payment_nonce).is_empty();
+        require!(has_attributes || has_uris, "no pending update");
+
+        if has_attributes {
+            let new_attributes = self.pending_attributes(payment_nonce).get();
+            self.audited_clear(
+                &self.pending_attributes(payment_nonce),
+                self.audit_key_with(b"pending_attributes", &payment_nonce),
+            );
+            self.apply_attributes(&payment_token, payment_nonce, &new_attributes);
+        }
+        if has_uris {
+            let new_uris = self.pending_uris(payment_nonce).get();
+            self.audited_clear(&self.pending_uris(payment_nonce), self.audit_key_with(b"pending_uris", &payment_nonce));
+            self.apply_uris(&payment_token, payment_nonce, &new_uris);
+        }
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct(&caller, &payment_token, payment_nonce, &payment_amount, &[]);
+    }
+
+    // private
+
+    fn apply_attributes(&self, token_id: &TokenIdentifier, nonce: u64, attributes: &ManagedBuffer) {
+        let mut arg_buffer = ManagedArgBuffer::new_empty();
+        arg_buffer.push_arg(token_id);
+        arg_buffer.push_arg(nonce);
+        arg_buffer.push_arg(attributes);
+
+        self.send().call_local_esdt_built_in_function(
+            BUILT_IN_CALL_GAS,
+            &ManagedBuffer::new_from_bytes(UPDATE_ATTRIBUTES_FUNC_NAME),
+            &arg_buffer,
+        );
+
+        self.attributes_updated_event(nonce, attributes);
+    }
+
+    fn apply_uris(&self, token_id: &TokenIdentifier, nonce: u64, uris: &ManagedVec<ManagedBuffer>) {
+        let mut arg_buffer = ManagedArgBuffer::new_empty();
+        arg_buffer.push_arg(token_id);
+        arg_buffer.push_arg(nonce);
+        for uri in uris.iter() {
+            arg_buffer.push_arg(uri.clone());
+        }
+
+        self.send().call_local_esdt_built_in_function(
+            BUILT_IN_CALL_GAS,
+            &ManagedBuffer::new_from_bytes(ADD_URI_FUNC_NAME),
+            &arg_buffer,
+        );
+
+        self.uris_added_event(nonce, uris);
+    }
+
+    // events
+
+    #[event("attributes_updated")]
+    fn attributes_updated_event(&self, #[indexed] nonce: u64, attributes: &ManagedBuffer);
+
+    #[event("uris_added")]
+    fn uris_added_event(&self, #[indexed] nonce: u64, uris: &ManagedVec<ManagedBuffer>);
+
+    // storage
+
+    #[view(getPendingAttributes)]
+    #[storage_mapper("pending_attributes")]
+    fn pending_attributes(&self, nonce: u64) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getPendingUris)]
+    #[storage_mapper("pending_uris")]
+    fn pending_uris(&self, nonce: u64) -> SingleValueMapper<ManagedVec<ManagedBuffer>>;
+}
@@ -0,0 +1,113 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use elrond_codec::TopEncode;
+
+const AUDIT_KEY_SEPARATOR: &[u8] = b".";
+
+// top-encoded values, empty when the storage was or becomes empty
+#[derive(TopEncode, TypeAbi)]
+pub struct ValueChange<M: ManagedTypeApi> {
+    pub before: ManagedBuffer<M>,
+    pub after: ManagedBuffer<M>,
+}
+
+// Every storage write goes through these helpers so that monitoring can rebuild the
+// full contract state from events alone. Keys are the storage names, followed by
+// the mapper arguments for parametrized storage.
+#[elrond_wasm::module]
+pub trait AuditModule {
+    // private
+
+    fn audit_key(&self, name: &[u8]) -> ManagedBuffer {
+        ManagedBuffer::new_from_bytes(name)
+    }
+
+    fn audit_key_with<K: TopEncode>(&self, name: &[u8], arg: &K) -> ManagedBuffer {
+        let mut key = ManagedBuffer::new_from_bytes(name);
+        key.append_bytes(AUDIT_KEY_SEPARATOR);
+        key.append(&self.serializer().top_encode_to_managed_buffer(arg));
+
+        key
+    }
+
+    fn audited_set<T: TopEncode + TopDecode>(
+        &self,
+        mapper: &SingleValueMapper<Self::Api, T>,
+        key: ManagedBuffer,
+        value: &T,
+    ) {
+        let before = self.encoded_value(mapper);
+        mapper.set(value);
+
+        let after = self.serializer().top_encode_to_managed_buffer(value);
+        self.value_changed_event(&key, &ValueChange { before, after });
+    }
+
+    fn audited_update<T: TopEncode + TopDecode, R, F: FnOnce(&mut T) -> R>(
+        &self,
+        mapper: &SingleValueMapper<Self::Api, T>,
+        key: ManagedBuffer,
+        f: F,
+    ) -> R {
+        let before = self.encoded_value(mapper);
+        let result = mapper.update(f);
+
+        let after = self.encoded_value(mapper);
+        self.value_changed_event(&key, &ValueChange { before, after });
+
+        result
+    }
+
+    fn audited_clear<T: TopEncode + TopDecode>(
+        &self,
+        mapper: &SingleValueMapper<Self::Api, T>,
+        key: ManagedBuffer,
+    ) {
+        let before = self.encoded_value(mapper);
+        mapper.clear();
+
+        self.value_changed_event(&key, &ValueChange { before, after: ManagedBuffer::new() });
+    }
+
+    // collection mappers log the entries they gain or lose
+
+    fn log_entry_added<T: TopEncode>(&self, key: ManagedBuffer, entry: &T) {
+        let entry = self.serializer().top_encode_to_managed_buffer(entry);
+        self.entry_added_event(&key, &entry);
+    }
+
+    fn log_entry_removed<T: TopEncode>(&self, key: ManagedBuffer, entry: &T) {
+        let entry = self.serializer().top_encode_to_managed_buffer(entry);
+        self.entry_removed_event(&key, &entry);
+    }
+
+    fn log_collection_cleared(&self, key: ManagedBuffer) {
+        self.collection_cleared_event(&key);
+    }
+
+    fn encoded_value<T: TopEncode + TopDecode>(
+        &self,
+        mapper: &SingleValueMapper<Self::Api, T>,
+    ) -> ManagedBuffer {
+        if mapper.is_empty() {
+            return ManagedBuffer::new();
+        }
+
+        self.serializer().top_encode_to_managed_buffer(&mapper.get())
+    }
+
+    // events
+
+    #[event("value_changed")]
+    fn value_changed_event(&self, #[indexed] key: &ManagedBuffer, change: &ValueChange<Self::Api>);
+
+    #[event("entry_added")]
+    fn entry_added_event(&self, #[indexed] key: &ManagedBuffer, entry: &ManagedBuffer);
+
+    #[event("entry_removed")]
+    fn entry_removed_event(&self, #[indexed] key: &ManagedBuffer, entry: &ManagedBuffer);
+
+    #[event("collection_cleared")]
+    fn collection_cleared_event(&self, #[indexed] key: &ManagedBuffer);
+}
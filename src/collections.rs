@@ -0,0 +1,328 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::pause::Feature;
+use crate::revenue::RevenueSource;
+
+const COLLECTION_NFT_AMOUNT: u32 = 1;
+const URI_SLASH: &[u8] = b"/";
+const HASH_TAG: &[u8] = b" #";
+const IMAGE_FILE_EXTENSION: &[u8] = b".png";
+const METADATA_FILE_EXTENSION: &[u8] = b".json";
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct CollectionConfig<M: ManagedTypeApi> {
+    pub name: ManagedBuffer<M>,
+    pub payment_token: TokenIdentifier<M>,
+    pub price: BigUint<M>,
+    pub royalties: u32,
+    pub image_base_uri: ManagedBuffer<M>,
+    pub metadata_base_uri: ManagedBuffer<M>,
+    // 0 means unlimited
+    pub max_supply: u32,
+    pub mint_count: u32,
+}
+
+// Additional NFT collections issued and minted by the same contract. The main
+// collection keeps its own storage and endpoints; collections registered here
+// have ids starting at 1, each with its own price, URIs, royalties and counter.
+#[elrond_wasm::module]
+pub trait CollectionsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::deadline::DeadlineModule
+    + crate::mint_history::MintHistoryModule
+    + crate::pause::PauseModule
+    + crate::revenue::RevenueModule
+    + crate::shareholders::ShareholdersModule
+    + crate::storage::StorageModule
+{
+    // endpoints - owner-only
+
+    #[only_owner]
+    #[endpoint(addCollection)]
+    fn add_collection(
+        &self,
+        name: ManagedBuffer,
+        payment_token: TokenIdentifier,
+        price: BigUint,
+        royalties: u32,
+        image_base_uri: ManagedBuffer,
+        metadata_base_uri: ManagedBuffer,
+        max_supply: u32,
+    ) -> u32 {
+        require!(royalties <= self.max_royalties().get(), "royalties cannot exceed the committed maximum");
+        require!(
+            payment_token.is_egld() || payment_token.is_valid_esdt_identifier(),
+            "invalid token identifier provided"
+        );
+
+        let collection_id = self.collection_count().get() + 1;
+        self.audited_set(
+            &self.collections(collection_id),
+            self.audit_key_with(b"collections", &collection_id),
+            &CollectionConfig {
+                name,
+                payment_token,
+                price,
+                royalties,
+                image_base_uri,
+                metadata_base_uri,
+                max_supply,
+                mint_count: 0,
+            },
+        );
+        self.audited_set(&self.collection_count(), self.audit_key(b"collection_count"), &collection_id);
+
+        collection_id
+    }
+
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(issueCollectionToken)]
+    fn issue_collection_token(
+        &self,
+        collection_id: u32,
+        token_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+    ) -> AsyncCall {
+        self.require_collection_exists(collection_id);
+        require!(self.collection_token_id(collection_id).is_empty(), "Token already issued");
+        require!(!self.collection_issue_pending(collection_id).get(), "Token issuance already in progress");
+
+        self.audited_set(
+            &self.collection_issue_pending(collection_id),
+            self.audit_key_with(b"collection_issue_pending", &collection_id),
+            &true,
+        );
+
+        let payment_amount = self.call_value().egld_value();
+        self.send()
+            .esdt_system_sc_proxy()
+            .issue_non_fungible(
+                payment_amount,
+                &token_name,
+                &token_ticker,
+                NonFungibleTokenProperties {
+                    can_freeze: false,
+                    can_wipe: false,
+                    can_pause: false,
+                    can_change_owner: true,
+                    can_upgrade: false,
+                    can_add_special_roles: true,
+                },
+            )
+            .async_call()
+            .with_callback(self.callbacks().collection_issue_callback(collection_id))
+    }
+
+    #[only_owner]
+    #[endpoint(setCollectionLocalRoles)]
+    fn set_collection_local_roles(&self, collection_id: u32) -> AsyncCall {
+        self.require_collection_exists(collection_id);
+        require!(!self.collection_token_id(collection_id).is_empty(), "Token not issued");
+        require!(!self.collection_roles_set(collection_id).get(), "Local roles already set");
+        require!(!self.collection_roles_pending(collection_id).get(), "Local roles request already in progress");
+
+        self.audited_set(
+            &self.collection_roles_pending(collection_id),
+            self.audit_key_with(b"collection_roles_pending", &collection_id),
+            &true,
+        );
+        self.send()
+            .esdt_system_sc_proxy()
+            .set_special_roles(
+                &self.blockchain().get_sc_address(),
+                &self.collection_token_id(collection_id).get(),
+                [EsdtLocalRole::NftCreate][..].iter().cloned(),
+            )
+            .async_call()
+            .with_callback(self.callbacks().collection_roles_callback(collection_id))
+    }
+
+    // endpoints - role-gated
+
+    #[endpoint(setCollectionPrice)]
+    fn set_collection_price(&self, collection_id: u32, payment_token: TokenIdentifier, price: BigUint) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        self.require_collection_exists(collection_id);
+        require!(
+            payment_token.is_egld() || payment_token.is_valid_esdt_identifier(),
+            "invalid token identifier provided"
+        );
+
+        self.audited_update(
+            &self.collections(collection_id),
+            self.audit_key_with(b"collections", &collection_id),
+            |collection| {
+                collection.payment_token = payment_token;
+                collection.price = price;
+            },
+        );
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(mintFromCollection)]
+    fn mint_from_collection(
+        &self,
+        collection_id: u32,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) -> u64 {
+        self.require_feature_active(Feature::Mint);
+        self.require_valid_until(valid_until);
+        self.require_collection_exists(collection_id);
+
+        require!(self.collection_roles_set(collection_id).get(), "Local roles not set");
+        let mut collection = self.collections(collection_id).get();
+        require!(
+            collection.max_supply == 0 || collection.mint_count < collection.max_supply,
+            "sold out"
+        );
+        require!(payment_token == collection.payment_token, "not given token identifier");
+        require!(payment_amount >= collection.price, "not enough tokens");
+
+        collection.mint_count += 1;
+        let token_id = self.collection_token_id(collection_id).get();
+        let nft_nonce = self.create_collection_nft(&token_id, &collection);
+        self.audited_set(
+            &self.collections(collection_id),
+            self.audit_key_with(b"collections", &collection_id),
+            &collection,
+        );
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct(
+            &caller,
+            &token_id,
+            nft_nonce,
+            &BigUint::from(COLLECTION_NFT_AMOUNT),
+            &[],
+        );
+
+        self.record_token_mint(&token_id, &caller, nft_nonce, &payment_amount, &payment_token);
+        self.record_revenue(RevenueSource::PrimaryMint, &payment_token, &payment_amount);
+        self.accrue_shares(&payment_token, &payment_amount);
+
+        nft_nonce
+    }
+
+    // private
+
+    fn require_collection_exists(&self, collection_id: u32) {
+        require!(!self.collections(collection_id).is_empty(), "collection does not exist");
+    }
+
+    // the collection's `mint_count` already includes this mint
+    fn create_collection_nft(&self, token_id: &TokenIdentifier, collection: &CollectionConfig<Self::Api>) -> u64 {
+        use alloc::string::ToString;
+
+        let number = ManagedBuffer::new_from_bytes(collection.mint_count.to_string().as_bytes());
+
+        let mut name = collection.name.clone();
+        name.append(&ManagedBuffer::new_from_bytes(HASH_TAG));
+        name.append(&number);
+
+        let mut image_uri = collection.image_base_uri.clone();
+        image_uri.append(&ManagedBuffer::new_from_bytes(URI_SLASH));
+        image_uri.append(&number);
+        image_uri.append(&ManagedBuffer::new_from_bytes(IMAGE_FILE_EXTENSION));
+
+        let mut metadata_uri = collection.metadata_base_uri.clone();
+        metadata_uri.append(&ManagedBuffer::new_from_bytes(URI_SLASH));
+        metadata_uri.append(&number);
+        metadata_uri.append(&ManagedBuffer::new_from_bytes(METADATA_FILE_EXTENSION));
+
+        let mut uris = ManagedVec::new();
+        uris.push(image_uri);
+        uris.push(metadata_uri);
+
+        self.send().esdt_nft_create(
+            token_id,
+            &BigUint::from(COLLECTION_NFT_AMOUNT),
+            &name,
+            &BigUint::from(collection.royalties),
+            &ManagedBuffer::new(),
+            &ManagedBuffer::new(),
+            &uris,
+        )
+    }
+
+    // callbacks
+
+    #[callback]
+    fn collection_issue_callback(
+        &self,
+        collection_id: u32,
+        #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>,
+    ) {
+        self.audited_clear(
+            &self.collection_issue_pending(collection_id),
+            self.audit_key_with(b"collection_issue_pending", &collection_id),
+        );
+
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.audited_set(
+                    &self.collection_token_id(collection_id),
+                    self.audit_key_with(b"collection_token_id", &collection_id),
+                    &token_id,
+                );
+            },
+            ManagedAsyncCallResult::Err(_) => {
+                let caller = self.blockchain().get_owner_address();
+                let (returned_tokens, token_id) = self.call_value().payment_token_pair();
+                if token_id.is_egld() && returned_tokens > 0 {
+                    self.send()
+                        .direct(&caller, &token_id, 0, &returned_tokens, &[]);
+                }
+            },
+        }
+    }
+
+    #[callback]
+    fn collection_roles_callback(&self, collection_id: u32, #[call_result] result: ManagedAsyncCallResult<()>) {
+        self.audited_clear(
+            &self.collection_roles_pending(collection_id),
+            self.audit_key_with(b"collection_roles_pending", &collection_id),
+        );
+
+        if let ManagedAsyncCallResult::Ok(()) = result {
+            self.audited_set(
+                &self.collection_roles_set(collection_id),
+                self.audit_key_with(b"collection_roles_set", &collection_id),
+                &true,
+            );
+        }
+    }
+
+    // storage
+
+    #[view(getCollectionCount)]
+    #[storage_mapper("collection_count")]
+    fn collection_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getCollection)]
+    #[storage_mapper("collections")]
+    fn collections(&self, collection_id: u32) -> SingleValueMapper<CollectionConfig<Self::Api>>;
+
+    #[view(getCollectionTokenId)]
+    #[storage_mapper("collection_token_id")]
+    fn collection_token_id(&self, collection_id: u32) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(areCollectionRolesSet)]
+    #[storage_mapper("collection_roles_set")]
+    fn collection_roles_set(&self, collection_id: u32) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("collection_issue_pending")]
+    fn collection_issue_pending(&self, collection_id: u32) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("collection_roles_pending")]
+    fn collection_roles_pending(&self, collection_id: u32) -> SingleValueMapper<bool>;
+}
@@ -0,0 +1,94 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+pub const COUPON_HASH_LENGTH: usize = 32;
+const BASIS_POINTS_MAX: u32 = 10_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Coupon {
+    // in basis points
+    pub discount: u32,
+    pub remaining_uses: u32,
+}
+
+// Promo codes for public mints. Only the sha256 of a code is stored; a buyer sends
+// the code itself to `mintWithCoupon`. The code is public once the transaction is
+// broadcast, so a code is only as private as its remaining uses are few.
+#[elrond_wasm::module]
+pub trait CouponsModule: crate::audit::AuditModule {
+    // endpoints - owner-only
+
+    // replaces the coupon registered under the same hash
+    #[only_owner]
+    #[endpoint(addCoupon)]
+    fn add_coupon(
+        &self,
+        code_hash: ManagedByteArray<Self::Api, COUPON_HASH_LENGTH>,
+        discount: u32,
+        uses: u32,
+    ) -> SCResult<()> {
+        require!(discount > 0 && discount <= BASIS_POINTS_MAX, "invalid discount");
+        require!(uses > 0, "uses must be positive");
+
+        self.audited_set(
+            &self.coupons(&code_hash),
+            self.audit_key_with(b"coupons", &code_hash),
+            &Coupon {
+                discount,
+                remaining_uses: uses,
+            },
+        );
+
+        Ok(())
+    }
+
+    #[only_owner]
+    #[endpoint(removeCoupon)]
+    fn remove_coupon(&self, code_hash: ManagedByteArray<Self::Api, COUPON_HASH_LENGTH>) -> SCResult<()> {
+        require!(!self.coupons(&code_hash).is_empty(), "unknown coupon");
+
+        self.audited_clear(&self.coupons(&code_hash), self.audit_key_with(b"coupons", &code_hash));
+
+        Ok(())
+    }
+
+    // private
+
+    // uses up one redemption of the code and returns its discount
+    fn redeem_coupon(&self, code: &ManagedBuffer) -> u32 {
+        let code_hash = self.crypto().sha256(code);
+        require!(!self.coupons(&code_hash).is_empty(), "invalid coupon");
+
+        let mut coupon = self.coupons(&code_hash).get();
+        coupon.remaining_uses -= 1;
+        if coupon.remaining_uses == 0 {
+            self.audited_clear(&self.coupons(&code_hash), self.audit_key_with(b"coupons", &code_hash));
+        } else {
+            self.audited_set(&self.coupons(&code_hash), self.audit_key_with(b"coupons", &code_hash), &coupon);
+        }
+        self.coupon_redeemed_event(&code_hash, &self.blockchain().get_caller(), coupon.remaining_uses);
+
+        coupon.discount
+    }
+
+    fn coupon_discounted_price(&self, price: &BigUint, discount: u32) -> BigUint {
+        price * (BASIS_POINTS_MAX - discount) / BASIS_POINTS_MAX
+    }
+
+    // events
+
+    #[event("coupon_redeemed")]
+    fn coupon_redeemed_event(
+        &self,
+        #[indexed] code_hash: &ManagedByteArray<Self::Api, COUPON_HASH_LENGTH>,
+        #[indexed] buyer: &ManagedAddress,
+        remaining_uses: u32,
+    );
+
+    // storage
+
+    // coupons are removed once used up
+    #[view(getCoupon)]
+    #[storage_mapper("coupons")]
+    fn coupons(&self, code_hash: &ManagedByteArray<Self::Api, COUPON_HASH_LENGTH>) -> SingleValueMapper<Coupon>;
+}
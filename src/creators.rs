@@ -0,0 +1,142 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::pause::Feature;
+
+const BASIS_POINTS_MAX: u32 = 10_000;
+const HEX_DIGITS: &[u8] = b"0123456789abcdef";
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct GuestArtist<M: ManagedTypeApi> {
+    pub first_mint_id: u32,
+    pub last_mint_id: u32,
+    pub creator: ManagedAddress<M>,
+    // share of the royalties earned by pieces in the range, in basis points
+    pub royalty_share: u32,
+}
+
+#[elrond_wasm::module]
+pub trait CreatorsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+    + crate::mint_ids::MintIdsModule
+    + crate::pause::PauseModule
+{
+    // endpoints - role-gated
+
+    // the creator is written into the attributes at mint, so only unminted ids qualify
+    #[endpoint(registerGuestArtist)]
+    fn register_guest_artist(
+        &self,
+        first_mint_id: u32,
+        last_mint_id: u32,
+        creator: ManagedAddress,
+        royalty_share: u32,
+    ) -> usize {
+        self.require_role(Role::Admin);
+
+        require!(first_mint_id > 0 && first_mint_id <= last_mint_id, "invalid mint id range");
+        require!(first_mint_id > self.highest_mint_id().get(), "range includes minted ids");
+        require!(royalty_share <= BASIS_POINTS_MAX, "royalty share cannot exceed 100%");
+        for artist in self.guest_artists().iter() {
+            require!(
+                last_mint_id < artist.first_mint_id || first_mint_id > artist.last_mint_id,
+                "range overlaps an existing guest artist"
+            );
+        }
+
+        let artist = GuestArtist {
+            first_mint_id,
+            last_mint_id,
+            creator,
+            royalty_share,
+        };
+        self.log_entry_added(self.audit_key(b"guest_artists"), &artist);
+
+        self.guest_artists().push(&artist)
+    }
+
+    /// endpoint
+
+    #[endpoint(claimCreatorRoyalties)]
+    fn claim_creator_royalties(&self, token_id: TokenIdentifier) {
+        self.require_feature_active(Feature::Claims);
+
+        let caller = self.blockchain().get_caller();
+        let amount = self.creator_royalties(&caller, &token_id).get();
+        require!(amount > 0, "nothing to claim");
+
+        self.audited_clear(
+            &self.creator_royalties(&caller, &token_id),
+            self.audit_key_with(b"creator_royalties", &(&caller, &token_id)),
+        );
+        self.release_inventory(&token_id, 0, &amount);
+        self.send().direct(&caller, &token_id, 0, &amount, &[]);
+    }
+
+    // private
+
+    fn find_guest_artist(&self, mint_id: u32) -> Option<GuestArtist<Self::Api>> {
+        self.guest_artists()
+            .iter()
+            .find(|artist| mint_id >= artist.first_mint_id && mint_id <= artist.last_mint_id)
+    }
+
+    // credits the guest artist's share of royalties earned by `mint_id`,
+    // returns what is left for the collection
+    fn route_creator_royalties(&self, mint_id: u32, token_id: &TokenIdentifier, amount: &BigUint) -> BigUint {
+        match self.find_guest_artist(mint_id) {
+            Some(artist) => {
+                let share = amount * artist.royalty_share / BASIS_POINTS_MAX;
+                self.audited_update(
+                    &self.creator_royalties(&artist.creator, token_id),
+                    self.audit_key_with(b"creator_royalties", &(&artist.creator, token_id)),
+                    |v| *v += &share,
+                );
+                self.protect_inventory(token_id, 0, &share);
+
+                amount - &share
+            },
+            None => amount.clone(),
+        }
+    }
+
+    // lowercase hex of the creator address, for embedding in attributes
+    fn creator_hex(&self, mint_id: u32) -> Option<ManagedBuffer> {
+        let artist = self.find_guest_artist(mint_id)?;
+        let address_bytes = artist.creator.to_address();
+
+        let mut hex = ManagedBuffer::new();
+        for byte in address_bytes.as_bytes() {
+            let digits = [
+                HEX_DIGITS[(byte >> 4) as usize],
+                HEX_DIGITS[(byte & 0x0f) as usize],
+            ];
+            hex.append_bytes(&digits);
+        }
+
+        Some(hex)
+    }
+
+    // views
+
+    #[view(getCreatorOf)]
+    fn get_creator_of(&self, mint_id: u32) -> OptionalResult<ManagedAddress> {
+        match self.find_guest_artist(mint_id) {
+            Some(artist) => OptionalResult::Some(artist.creator),
+            None => OptionalResult::None,
+        }
+    }
+
+    // storage
+
+    #[view(getGuestArtists)]
+    #[storage_mapper("guest_artists")]
+    fn guest_artists(&self) -> VecMapper<GuestArtist<Self::Api>>;
+
+    #[view(getCreatorRoyalties)]
+    #[storage_mapper("creator_royalties")]
+    fn creator_royalties(&self, creator: &ManagedAddress, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,17 @@
+elrond_wasm::imports!();
+
+// User-facing purchase endpoints take an optional `valid_until` timestamp, so a
+// transaction that sat in the mempool cannot execute after prices or phases moved.
+#[elrond_wasm::module]
+pub trait DeadlineModule {
+    // private
+
+    fn require_valid_until(&self, valid_until: OptionalArg<u64>) {
+        if let OptionalArg::Some(deadline) = valid_until {
+            require!(
+                self.blockchain().get_block_timestamp() <= deadline,
+                "transaction deadline passed"
+            );
+        }
+    }
+}
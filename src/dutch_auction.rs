@@ -0,0 +1,90 @@
+elrond_wasm::imports!();
+
+use crate::access_control::Role;
+
+// Optional descending mint price: from the start timestamp the price drops by
+// `decay_step` every `decay_interval` seconds until it reaches the floor. An
+// interval of one second gives a linear decay. While disabled the fixed NFT price
+// applies.
+#[elrond_wasm::module]
+pub trait DutchAuctionModule:
+    crate::access_control::AccessControlModule + crate::audit::AuditModule + crate::timelock::TimelockModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(setDutchAuction)]
+    fn set_dutch_auction(
+        &self,
+        start_timestamp: u64,
+        start_price: BigUint,
+        floor_price: BigUint,
+        decay_step: BigUint,
+        decay_interval: u64,
+    ) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        require!(start_price >= floor_price, "floor above start price");
+        require!(decay_interval > 0, "decay interval must be positive");
+
+        self.audited_set(&self.dutch_auction_start_timestamp(), self.audit_key(b"dutch_auction_start_timestamp"), &start_timestamp);
+        self.audited_set(&self.dutch_auction_start_price(), self.audit_key(b"dutch_auction_start_price"), &start_price);
+        self.audited_set(&self.dutch_auction_floor_price(), self.audit_key(b"dutch_auction_floor_price"), &floor_price);
+        self.audited_set(&self.dutch_auction_decay_step(), self.audit_key(b"dutch_auction_decay_step"), &decay_step);
+        self.audited_set(&self.dutch_auction_decay_interval(), self.audit_key(b"dutch_auction_decay_interval"), &decay_interval);
+        self.audited_set(&self.dutch_auction_enabled(), self.audit_key(b"dutch_auction_enabled"), &true);
+
+        Ok(())
+    }
+
+    #[endpoint(disableDutchAuction)]
+    fn disable_dutch_auction(&self) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        self.audited_clear(&self.dutch_auction_enabled(), self.audit_key(b"dutch_auction_enabled"));
+
+        Ok(())
+    }
+
+    // private
+
+    fn dutch_auction_price(&self) -> BigUint {
+        let start_price = self.dutch_auction_start_price().get();
+        let now = self.blockchain().get_block_timestamp();
+        let start_timestamp = self.dutch_auction_start_timestamp().get();
+        if now <= start_timestamp {
+            return start_price;
+        }
+
+        let steps = (now - start_timestamp) / self.dutch_auction_decay_interval().get();
+        let decay = self.dutch_auction_decay_step().get() * BigUint::from(steps);
+        let floor_price = self.dutch_auction_floor_price().get();
+        if decay >= &start_price - &floor_price {
+            return floor_price;
+        }
+
+        start_price - decay
+    }
+
+    // storage
+
+    #[view(isDutchAuctionEnabled)]
+    #[storage_mapper("dutch_auction_enabled")]
+    fn dutch_auction_enabled(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("dutch_auction_start_timestamp")]
+    fn dutch_auction_start_timestamp(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("dutch_auction_start_price")]
+    fn dutch_auction_start_price(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("dutch_auction_floor_price")]
+    fn dutch_auction_floor_price(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("dutch_auction_decay_step")]
+    fn dutch_auction_decay_step(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("dutch_auction_decay_interval")]
+    fn dutch_auction_decay_interval(&self) -> SingleValueMapper<u64>;
+}
@@ -0,0 +1,331 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::pause::Feature;
+use crate::revenue::RevenueSource;
+
+const EDITION_COPY_AMOUNT: u32 = 1;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum EditionKind {
+    Limited,
+    Open,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Edition<M: ManagedTypeApi> {
+    pub kind: EditionKind,
+    pub nonce: u64,
+    pub payment_token: TokenIdentifier<M>,
+    pub price: BigUint<M>,
+    // copies delivered per mint, paid for with one `price`
+    pub copies_per_mint: u32,
+    // in copies, 0 means unlimited for open editions
+    pub supply: u32,
+    pub remaining: u32,
+}
+
+// Editions live in their own SFT collection: every artwork is one nonce. Limited
+// editions have their whole supply created up front and held by the contract until
+// bought. Open editions keep a single master copy in the contract and add quantity
+// to the nonce on every mint, optionally up to a cap.
+#[elrond_wasm::module]
+pub trait EditionsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::deadline::DeadlineModule
+    + crate::inventory::InventoryModule
+    + crate::mint_history::MintHistoryModule
+    + crate::pause::PauseModule
+    + crate::revenue::RevenueModule
+    + crate::shareholders::ShareholdersModule
+    + crate::storage::StorageModule
+    + crate::timelock::TimelockModule
+{
+    // endpoints - owner-only
+
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(issueEditionToken)]
+    fn issue_edition_token(&self, token_name: ManagedBuffer, token_ticker: ManagedBuffer) -> AsyncCall {
+        require!(self.edition_token_id().is_empty(), "Token already issued");
+        require!(!self.edition_issue_pending().get(), "Token issuance already in progress");
+
+        self.audited_set(
+            &self.edition_issue_pending(),
+            self.audit_key(b"edition_issue_pending"),
+            &true,
+        );
+
+        let payment_amount = self.call_value().egld_value();
+        self.send()
+            .esdt_system_sc_proxy()
+            .issue_semi_fungible(
+                payment_amount,
+                &token_name,
+                &token_ticker,
+                SemiFungibleTokenProperties {
+                    can_freeze: false,
+                    can_wipe: false,
+                    can_pause: false,
+                    can_change_owner: true,
+                    can_upgrade: false,
+                    can_add_special_roles: true,
+                },
+            )
+            .async_call()
+            .with_callback(self.callbacks().edition_issue_callback())
+    }
+
+    #[only_owner]
+    #[endpoint(setEditionLocalRoles)]
+    fn set_edition_local_roles(&self) -> AsyncCall {
+        self.require_edition_token_issued();
+        require!(!self.edition_roles_set().get(), "Local roles already set");
+        require!(!self.edition_roles_pending().get(), "Local roles request already in progress");
+
+        self.audited_set(
+            &self.edition_roles_pending(),
+            self.audit_key(b"edition_roles_pending"),
+            &true,
+        );
+        self.send()
+            .esdt_system_sc_proxy()
+            .set_special_roles(
+                &self.blockchain().get_sc_address(),
+                &self.edition_token_id().get(),
+                [EsdtLocalRole::NftCreate, EsdtLocalRole::NftAddQuantity][..].iter().cloned(),
+            )
+            .async_call()
+            .with_callback(self.callbacks().edition_roles_callback())
+    }
+
+    #[endpoint(createEdition)]
+    fn create_edition(
+        &self,
+        name: ManagedBuffer,
+        royalties: u32,
+        payment_token: TokenIdentifier,
+        price: BigUint,
+        supply: u32,
+        #[var_args] uris: ManagedVarArgs<ManagedBuffer>,
+    ) -> u32 {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        self.require_edition_token_issued();
+        require!(self.edition_roles_set().get(), "Edition local roles not set");
+        require!(supply > 0, "supply must be positive");
+        require!(royalties <= self.max_royalties().get(), "royalties cannot exceed the committed maximum");
+        require!(
+            payment_token.is_egld() || payment_token.is_valid_esdt_identifier(),
+            "invalid token identifier provided"
+        );
+
+        let uris = uris.into_vec_of_buffers();
+        let edition_token_id = self.edition_token_id().get();
+        let nonce = self.send().esdt_nft_create(
+            &edition_token_id,
+            &BigUint::from(supply),
+            &name,
+            &BigUint::from(royalties),
+            &ManagedBuffer::new(),
+            &ManagedBuffer::new(),
+            &uris,
+        );
+        self.protect_inventory(&edition_token_id, nonce, &BigUint::from(supply));
+
+        let edition_id = self.edition_count().get() + 1;
+        self.audited_set(
+            &self.editions(edition_id),
+            self.audit_key_with(b"editions", &edition_id),
+            &Edition {
+                kind: EditionKind::Limited,
+                nonce,
+                payment_token,
+                price,
+                copies_per_mint: EDITION_COPY_AMOUNT,
+                supply,
+                remaining: supply,
+            },
+        );
+        self.audited_set(&self.edition_count(), self.audit_key(b"edition_count"), &edition_id);
+
+        edition_id
+    }
+
+    // `max_copies` of 0 leaves the edition open until the admin stops it by pausing mints
+    #[endpoint(createOpenEdition)]
+    fn create_open_edition(
+        &self,
+        name: ManagedBuffer,
+        royalties: u32,
+        payment_token: TokenIdentifier,
+        price: BigUint,
+        copies_per_mint: u32,
+        max_copies: u32,
+        #[var_args] uris: ManagedVarArgs<ManagedBuffer>,
+    ) -> u32 {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        self.require_edition_token_issued();
+        require!(self.edition_roles_set().get(), "Edition local roles not set");
+        require!(copies_per_mint > 0, "copies per mint must be positive");
+        require!(royalties <= self.max_royalties().get(), "royalties cannot exceed the committed maximum");
+        require!(
+            max_copies == 0 || max_copies % copies_per_mint == 0,
+            "max copies must be a multiple of copies per mint"
+        );
+        require!(
+            payment_token.is_egld() || payment_token.is_valid_esdt_identifier(),
+            "invalid token identifier provided"
+        );
+
+        let uris = uris.into_vec_of_buffers();
+        let edition_token_id = self.edition_token_id().get();
+        let master_amount = BigUint::from(EDITION_COPY_AMOUNT);
+        let nonce = self.send().esdt_nft_create(
+            &edition_token_id,
+            &master_amount,
+            &name,
+            &BigUint::from(royalties),
+            &ManagedBuffer::new(),
+            &ManagedBuffer::new(),
+            &uris,
+        );
+        self.protect_inventory(&edition_token_id, nonce, &master_amount);
+
+        let edition_id = self.edition_count().get() + 1;
+        self.audited_set(
+            &self.editions(edition_id),
+            self.audit_key_with(b"editions", &edition_id),
+            &Edition {
+                kind: EditionKind::Open,
+                nonce,
+                payment_token,
+                price,
+                copies_per_mint,
+                supply: max_copies,
+                remaining: max_copies,
+            },
+        );
+        self.audited_set(&self.edition_count(), self.audit_key(b"edition_count"), &edition_id);
+
+        edition_id
+    }
+
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(mintEdition)]
+    fn mint_edition(
+        &self,
+        edition_id: u32,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) {
+        self.require_feature_active(Feature::Mint);
+        self.require_valid_until(valid_until);
+        require!(!self.editions(edition_id).is_empty(), "edition does not exist");
+
+        let mut edition = self.editions(edition_id).get();
+        let capped = edition.kind == EditionKind::Limited || edition.supply > 0;
+        require!(!capped || edition.remaining >= edition.copies_per_mint, "edition sold out");
+        require!(payment_token == edition.payment_token, "not given token identifier");
+        require!(payment_amount == edition.price, "payment does not match the price");
+
+        if capped {
+            edition.remaining -= edition.copies_per_mint;
+            self.audited_set(
+                &self.editions(edition_id),
+                self.audit_key_with(b"editions", &edition_id),
+                &edition,
+            );
+        }
+
+        let edition_token_id = self.edition_token_id().get();
+        let copy_amount = BigUint::from(edition.copies_per_mint);
+        match edition.kind {
+            EditionKind::Limited => self.release_inventory(&edition_token_id, edition.nonce, &copy_amount),
+            EditionKind::Open => self.send().esdt_local_mint(&edition_token_id, edition.nonce, &copy_amount),
+        }
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct(&caller, &edition_token_id, edition.nonce, &copy_amount, &[]);
+
+        self.record_token_mint(&edition_token_id, &caller, edition.nonce, &payment_amount, &payment_token);
+        self.record_revenue(RevenueSource::PrimaryMint, &payment_token, &payment_amount);
+        self.accrue_shares(&payment_token, &payment_amount);
+    }
+
+    // private
+
+    fn require_edition_token_issued(&self) {
+        require!(!self.edition_token_id().is_empty(), "Edition token not issued");
+    }
+
+    // callbacks
+
+    #[callback]
+    fn edition_issue_callback(&self, #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>) {
+        self.audited_clear(&self.edition_issue_pending(), self.audit_key(b"edition_issue_pending"));
+
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.audited_set(
+                    &self.edition_token_id(),
+                    self.audit_key(b"edition_token_id"),
+                    &token_id,
+                );
+            },
+            ManagedAsyncCallResult::Err(_) => {
+                let caller = self.blockchain().get_owner_address();
+                let (returned_tokens, token_id) = self.call_value().payment_token_pair();
+                if token_id.is_egld() && returned_tokens > 0 {
+                    self.send()
+                        .direct(&caller, &token_id, 0, &returned_tokens, &[]);
+                }
+            },
+        }
+    }
+
+    #[callback]
+    fn edition_roles_callback(&self, #[call_result] result: ManagedAsyncCallResult<()>) {
+        self.audited_clear(&self.edition_roles_pending(), self.audit_key(b"edition_roles_pending"));
+
+        if let ManagedAsyncCallResult::Ok(()) = result {
+            self.audited_set(
+                &self.edition_roles_set(),
+                self.audit_key(b"edition_roles_set"),
+                &true,
+            );
+        }
+    }
+
+    // storage
+
+    #[storage_mapper("edition_issue_pending")]
+    fn edition_issue_pending(&self) -> SingleValueMapper<bool>;
+
+    #[view(areEditionRolesSet)]
+    #[storage_mapper("edition_roles_set")]
+    fn edition_roles_set(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("edition_roles_pending")]
+    fn edition_roles_pending(&self) -> SingleValueMapper<bool>;
+
+    #[view(getEditionTokenId)]
+    #[storage_mapper("edition_token_id")]
+    fn edition_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getEditionCount)]
+    #[storage_mapper("edition_count")]
+    fn edition_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getEdition)]
+    #[storage_mapper("editions")]
+    fn editions(&self, edition_id: u32) -> SingleValueMapper<Edition<Self::Api>>;
+}
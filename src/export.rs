@@ -0,0 +1,138 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+// size of the encoded data returned by one export view, kept well under the VM query output limit
+pub const EXPORT_CHUNK_MAX_BYTES: usize = 16_384;
+
+const HEX_DIGITS: &[u8] = b"0123456789abcdef";
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_PADDING: u8 = b'=';
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum ExportEncoding {
+    Hex,
+    Base64,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct ExportChunk<M: ManagedTypeApi> {
+    pub data: ManagedBuffer<M>,
+    pub entry_count: usize,
+    // offset to pass as `from` for the next chunk
+    pub next_from: usize,
+    pub complete: bool,
+}
+
+// Bulk state dumps for CLI tools and backups. A chunk is a run of entries, each one
+// top-encoded and prefixed with its length as u32 big-endian, and the whole run is
+// then hex or base64 encoded. Chunks stop before going over EXPORT_CHUNK_MAX_BYTES
+// but always carry at least one entry.
+#[elrond_wasm::module]
+pub trait ExportModule:
+    crate::audit::AuditModule
+    + crate::holders::HoldersModule
+    + crate::mint_history::MintHistoryModule
+{
+    // private
+
+    fn export_entries<T: TopEncode, I: Iterator<Item = T>>(
+        &self,
+        entries: I,
+        from: usize,
+        encoding: ExportEncoding,
+    ) -> ExportChunk<Self::Api> {
+        let raw_limit = match encoding {
+            ExportEncoding::Hex => EXPORT_CHUNK_MAX_BYTES / 2,
+            ExportEncoding::Base64 => EXPORT_CHUNK_MAX_BYTES / 4 * 3,
+        };
+
+        let mut raw = ManagedBuffer::new();
+        let mut entry_count = 0usize;
+        let mut complete = true;
+        for entry in entries {
+            let encoded = self.serializer().top_encode_to_managed_buffer(&entry);
+            if entry_count > 0 && raw.len() + 4 + encoded.len() > raw_limit {
+                complete = false;
+                break;
+            }
+
+            raw.append_u32_be(encoded.len() as u32);
+            raw.append(&encoded);
+            entry_count += 1;
+        }
+
+        ExportChunk {
+            data: self.encode_export(&raw, encoding),
+            entry_count,
+            next_from: from + entry_count,
+            complete,
+        }
+    }
+
+    fn encode_export(&self, raw: &ManagedBuffer, encoding: ExportEncoding) -> ManagedBuffer {
+        let bytes = raw.to_boxed_bytes();
+        let bytes = bytes.as_slice();
+        let mut encoded = ManagedBuffer::new();
+
+        match encoding {
+            ExportEncoding::Hex => {
+                for byte in bytes {
+                    let digits = [
+                        HEX_DIGITS[(byte >> 4) as usize],
+                        HEX_DIGITS[(byte & 0x0f) as usize],
+                    ];
+                    encoded.append_bytes(&digits);
+                }
+            },
+            ExportEncoding::Base64 => {
+                for group in bytes.chunks(3) {
+                    let b0 = group[0] as usize;
+                    let b1 = *group.get(1).unwrap_or(&0) as usize;
+                    let b2 = *group.get(2).unwrap_or(&0) as usize;
+
+                    let mut quad = [
+                        BASE64_ALPHABET[b0 >> 2],
+                        BASE64_ALPHABET[((b0 & 0x03) << 4) | (b1 >> 4)],
+                        BASE64_ALPHABET[((b1 & 0x0f) << 2) | (b2 >> 6)],
+                        BASE64_ALPHABET[b2 & 0x3f],
+                    ];
+                    if group.len() < 3 {
+                        quad[3] = BASE64_PADDING;
+                    }
+                    if group.len() < 2 {
+                        quad[2] = BASE64_PADDING;
+                    }
+                    encoded.append_bytes(&quad);
+                }
+            },
+        }
+
+        encoded
+    }
+
+    // views
+
+    // entries are (address, attributed token count)
+    #[view(exportHolders)]
+    fn export_holders(&self, from: usize, encoding: ExportEncoding) -> ExportChunk<Self::Api> {
+        let holders = self
+            .holders()
+            .iter()
+            .skip(from)
+            .map(|holder| {
+                let token_count = self.attributed_token_count(&holder).get();
+                (holder, token_count)
+            });
+
+        self.export_entries(holders, from, encoding)
+    }
+
+    // entries are MintRecord, `from` is a zero-based offset into the history
+    #[view(exportMintHistory)]
+    fn export_mint_history(&self, from: usize, encoding: ExportEncoding) -> ExportChunk<Self::Api> {
+        let history = self.mint_history();
+        let records = (from..history.len()).map(|index| history.get(index + 1));
+
+        self.export_entries(records, from, encoding)
+    }
+}
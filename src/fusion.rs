@@ -0,0 +1,56 @@
+elrond_wasm::imports!();
+
+use crate::access_control::Role;
+
+// added to the rarity boost of the trait roll for every fusion level
+const FUSION_LEVEL_BOOST: u32 = 2_500;
+
+// Holders fuse a configured number of collection NFTs into one. The inputs are
+// burned and the first input's mint id is minted again on a new nonce, one level
+// above the highest input level; each level improves the odds on rare traits.
+#[elrond_wasm::module]
+pub trait FusionModule: crate::access_control::AccessControlModule + crate::audit::AuditModule {
+    // endpoints - role-gated
+
+    // 0 disables fusion
+    #[endpoint(setFusionInputCount)]
+    fn set_fusion_input_count(&self, input_count: usize) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        require!(input_count != 1, "fusion needs at least two inputs");
+
+        self.audited_set(&self.fusion_input_count(), self.audit_key(b"fusion_input_count"), &input_count);
+
+        Ok(())
+    }
+
+    // private
+
+    fn fusion_rarity_boost(&self, level: u32) -> u32 {
+        level * FUSION_LEVEL_BOOST
+    }
+
+    // events
+
+    #[event("nft_fused")]
+    fn nft_fused_event(
+        &self,
+        #[indexed] holder: &ManagedAddress,
+        #[indexed] fused_nonce: u64,
+        #[indexed] level: u32,
+        burned_nonces: &ManagedVec<Self::Api, u64>,
+    );
+
+    // storage
+
+    #[view(getFusionInputCount)]
+    #[storage_mapper("fusion_input_count")]
+    fn fusion_input_count(&self) -> SingleValueMapper<usize>;
+
+    #[view(getFusionCount)]
+    #[storage_mapper("fusion_count")]
+    fn fusion_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getFusionLevel)]
+    #[storage_mapper("fusion_level")]
+    fn fusion_level(&self, mint_id: u32) -> SingleValueMapper<u32>;
+}
@@ -0,0 +1,114 @@
+elrond_wasm::imports!();
+
+const REFRESHED_NFT_AMOUNT: u32 = 1;
+
+// Best-effort holder tracking: only movements that go through the contract are seen,
+// transfers made directly between wallets are not. Holders can refresh a stale
+// record by sending the NFT in, it is returned in the same call.
+#[elrond_wasm::module]
+pub trait HoldersModule: crate::audit::AuditModule + crate::storage::StorageModule {
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(refreshKnownOwner)]
+    fn refresh_known_owner(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_nonce] payment_nonce: u64,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        require!(payment_token == self.nft_token_id().get(), "only collection NFTs can be refreshed");
+        require!(payment_amount == REFRESHED_NFT_AMOUNT, "invalid amount");
+
+        let caller = self.blockchain().get_caller();
+        self.reattribute_token(&caller, payment_nonce);
+
+        self.send().direct(&caller, &payment_token, payment_nonce, &payment_amount, &[]);
+    }
+
+    // private
+
+    fn attribute_token(&self, holder: &ManagedAddress, nonce: u64) {
+        self.audited_set(&self.known_owner(nonce), self.audit_key_with(b"known_owner", &nonce), holder);
+
+        let previous = self.attributed_token_count(holder).get();
+        if previous == 0 {
+            self.audited_update(&self.holder_count(), self.audit_key(b"holder_count"), |v| *v += 1);
+            self.holders().insert(holder.clone());
+            self.log_entry_added(self.audit_key(b"holders"), holder);
+        }
+
+        self.audited_set(
+            &self.attributed_token_count(holder),
+            self.audit_key_with(b"attributed_token_count", holder),
+            &(previous + 1),
+        );
+    }
+
+    fn release_token(&self, holder: &ManagedAddress, nonce: u64) {
+        if !self.known_owner(nonce).is_empty() && self.known_owner(nonce).get() == *holder {
+            self.audited_clear(&self.known_owner(nonce), self.audit_key_with(b"known_owner", &nonce));
+        }
+
+        let previous = self.attributed_token_count(holder).get();
+        if previous == 0 {
+            return;
+        }
+
+        if previous == 1 {
+            self.audited_update(&self.holder_count(), self.audit_key(b"holder_count"), |v| *v -= 1);
+            self.holders().swap_remove(holder);
+            self.log_entry_removed(self.audit_key(b"holders"), holder);
+            self.audited_clear(
+                &self.attributed_token_count(holder),
+                self.audit_key_with(b"attributed_token_count", holder),
+            );
+        } else {
+            self.audited_set(
+                &self.attributed_token_count(holder),
+                self.audit_key_with(b"attributed_token_count", holder),
+                &(previous - 1),
+            );
+        }
+    }
+
+    // moves the attribution of a token that was seen in `owner`'s hands
+    fn reattribute_token(&self, owner: &ManagedAddress, nonce: u64) {
+        if !self.known_owner(nonce).is_empty() {
+            let previous_owner = self.known_owner(nonce).get();
+            if previous_owner == *owner {
+                return;
+            }
+            self.release_token(&previous_owner, nonce);
+        }
+
+        self.attribute_token(owner, nonce);
+    }
+
+    // views
+
+    #[view(getKnownOwner)]
+    fn get_known_owner(&self, nonce: u64) -> OptionalResult<ManagedAddress> {
+        if self.known_owner(nonce).is_empty() {
+            return OptionalResult::None;
+        }
+
+        OptionalResult::Some(self.known_owner(nonce).get())
+    }
+
+    // storage
+
+    #[storage_mapper("known_owner")]
+    fn known_owner(&self, nonce: u64) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getHolderCount)]
+    #[storage_mapper("holder_count")]
+    fn holder_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("holders")]
+    fn holders(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getAttributedTokenCount)]
+    #[storage_mapper("attributed_token_count")]
+    fn attributed_token_count(&self, holder: &ManagedAddress) -> SingleValueMapper<u32>;
+}
@@ -0,0 +1,109 @@
+elrond_wasm::imports!();
+
+// left untouched by bulk transfers so the call can still finish and return
+const VAULT_TRANSFER_GAS_RESERVE: u64 = 1_000_000;
+
+// Ledger of contract-held tokens that belong to users or to a subsystem reserve
+// (unsold editions, escrowed creator royalties, ...). Whatever is recorded here can
+// never leave through the rescue endpoint; only the owning subsystem releases it.
+#[elrond_wasm::module]
+pub trait InventoryModule: crate::audit::AuditModule {
+    // endpoints - owner-only
+
+    // recovers tokens sent to the contract by mistake
+    #[only_owner]
+    #[endpoint(rescueTokens)]
+    fn rescue_tokens(&self, token_id: TokenIdentifier, nonce: u64, amount: BigUint, to: ManagedAddress) -> SCResult<()> {
+        require!(amount > 0, "amount must be positive");
+        require!(
+            amount <= self.get_rescuable_amount(token_id.clone(), nonce),
+            "amount exceeds unprotected balance"
+        );
+
+        self.send().direct(&to, &token_id, nonce, &amount, &[]);
+
+        Ok(())
+    }
+
+    // Sends many unprotected tokens of one collection, e.g. prizes or partner
+    // allocations. Stops before running out of gas and returns how many leading
+    // items were sent, so the caller can resubmit the rest.
+    #[only_owner]
+    #[endpoint(transferFromVault)]
+    fn transfer_from_vault(
+        &self,
+        token_id: TokenIdentifier,
+        #[var_args] transfers: ManagedVarArgs<MultiArg3<ManagedAddress, u64, BigUint>>,
+    ) -> usize {
+        let mut transferred = 0;
+        for transfer in transfers.into_iter() {
+            if self.blockchain().get_gas_left() < VAULT_TRANSFER_GAS_RESERVE {
+                break;
+            }
+
+            let (to, nonce, amount) = transfer.into_tuple();
+            require!(amount > 0, "amount must be positive");
+            require!(
+                amount <= self.get_rescuable_amount(token_id.clone(), nonce),
+                "amount exceeds unprotected balance"
+            );
+
+            self.send().direct(&to, &token_id, nonce, &amount, &[]);
+            self.vault_transfer_event(&to, &token_id, nonce, &amount);
+            transferred += 1;
+        }
+
+        transferred
+    }
+
+    // private
+
+    fn protect_inventory(&self, token_id: &TokenIdentifier, nonce: u64, amount: &BigUint) {
+        self.audited_update(
+            &self.protected_amount(token_id, nonce),
+            self.audit_key_with(b"protected_amount", &(token_id, nonce)),
+            |v| *v += amount,
+        );
+    }
+
+    fn release_inventory(&self, token_id: &TokenIdentifier, nonce: u64, amount: &BigUint) {
+        self.audited_update(
+            &self.protected_amount(token_id, nonce),
+            self.audit_key_with(b"protected_amount", &(token_id, nonce)),
+            |v| {
+                require!(*v >= *amount, "releasing more than protected");
+                *v -= amount;
+            },
+        );
+    }
+
+    // views
+
+    #[view(getRescuableAmount)]
+    fn get_rescuable_amount(&self, token_id: TokenIdentifier, nonce: u64) -> BigUint {
+        let balance = self.blockchain().get_sc_balance(&token_id, nonce);
+        let protected = self.protected_amount(&token_id, nonce).get();
+        if balance <= protected {
+            return BigUint::zero();
+        }
+
+        balance - protected
+    }
+
+    // events
+
+    #[event("vault_transfer")]
+    fn vault_transfer_event(
+        &self,
+        #[indexed] to: &ManagedAddress,
+        #[indexed] token_id: &TokenIdentifier,
+        #[indexed] nonce: u64,
+        amount: &BigUint,
+    );
+
+    // storage
+
+    #[view(getProtectedAmount)]
+    #[storage_mapper("protected_amount")]
+    fn protected_amount(&self, token_id: &TokenIdentifier, nonce: u64) -> SingleValueMapper<BigUint>;
+}
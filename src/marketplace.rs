@@ -0,0 +1,542 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::pause::Feature;
+use crate::revenue::RevenueSource;
+use crate::states::{AuctionState, ListingState};
+
+const LISTED_NFT_AMOUNT: u32 = 1;
+const BASIS_POINTS_MAX: u32 = 10_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum ListingKind {
+    FixedPrice,
+    Auction,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Listing<M: ManagedTypeApi> {
+    pub kind: ListingKind,
+    pub seller: ManagedAddress<M>,
+    pub payment_token: TokenIdentifier<M>,
+    // asking price, or the minimum bid of an auction
+    pub price: BigUint<M>,
+    pub listed_timestamp: u64,
+    // auctions only, the zero address until the first bid
+    pub deadline: u64,
+    pub highest_bidder: ManagedAddress<M>,
+    pub highest_bid: BigUint<M>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Offer<M: ManagedTypeApi> {
+    pub nonce: u64,
+    pub buyer: ManagedAddress<M>,
+    pub payment_token: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub expiry_timestamp: u64,
+}
+
+// Secondary sales of main collection NFTs. Sellers list by sending the NFT in and
+// set an asking price in the payment token; a buyer pays exactly that price. The
+// token's royalties go to its guest artist's share and the collection, the
+// marketplace fee to the collection, and the rest to the seller. The collection's
+// part stays in the contract for the treasury to withdraw.
+//
+// English auctions share the listing registry. Bids are escrowed and an outbid
+// bidder is refunded right away; after the deadline anyone can end the auction,
+// which settles the highest bid like a sale or returns an unsold NFT. How a
+// token's last listing or auction closed is kept until it is listed again.
+//
+// Offers work on any minted token, listed or not. The amount is escrowed until the
+// holder accepts by sending the NFT in, or the buyer takes it back; offers past
+// their expiry can be returned to the buyer by anyone.
+#[elrond_wasm::module]
+pub trait MarketplaceModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::creators::CreatorsModule
+    + crate::deadline::DeadlineModule
+    + crate::holders::HoldersModule
+    + crate::inventory::InventoryModule
+    + crate::mint_ids::MintIdsModule
+    + crate::pause::PauseModule
+    + crate::revenue::RevenueModule
+    + crate::storage::StorageModule
+{
+    // endpoints - role-gated
+
+    // in basis points of the sale price, on top of the token's royalties; together
+    // with the committed royalties maximum it cannot exceed the price
+    #[endpoint(setMarketplaceFee)]
+    fn set_marketplace_fee(&self, fee: u32) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        require!(
+            fee + self.max_royalties().get() <= BASIS_POINTS_MAX,
+            "fee and royalties cannot exceed 100%"
+        );
+
+        self.audited_set(&self.marketplace_fee(), self.audit_key(b"marketplace_fee"), &fee);
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(listNft)]
+    fn list_nft(
+        &self,
+        price: BigUint,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_nonce] payment_nonce: u64,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(Feature::Marketplace);
+        require!(payment_token == self.nft_token_id().get(), "only collection NFTs can be listed");
+        require!(payment_amount == LISTED_NFT_AMOUNT, "invalid amount");
+        require!(price > 0, "price must be positive");
+
+        self.add_listing(ListingKind::FixedPrice, payment_nonce, price, 0);
+    }
+
+    #[payable("*")]
+    #[endpoint(startAuction)]
+    fn start_auction(
+        &self,
+        min_bid: BigUint,
+        deadline: u64,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_nonce] payment_nonce: u64,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(Feature::Marketplace);
+        require!(payment_token == self.nft_token_id().get(), "only collection NFTs can be auctioned");
+        require!(payment_amount == LISTED_NFT_AMOUNT, "invalid amount");
+        require!(min_bid > 0, "minimum bid must be positive");
+        require!(deadline > self.blockchain().get_block_timestamp(), "deadline must be in the future");
+
+        self.add_listing(ListingKind::Auction, payment_nonce, min_bid, deadline);
+    }
+
+    #[payable("*")]
+    #[endpoint(bid)]
+    fn bid(
+        &self,
+        nonce: u64,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) {
+        self.require_feature_active(Feature::Marketplace);
+        self.require_valid_until(valid_until);
+        self.require_listed(nonce);
+
+        let mut listing = self.listings(nonce).get();
+        require!(listing.kind == ListingKind::Auction, "token not auctioned");
+        require!(self.blockchain().get_block_timestamp() < listing.deadline, "auction ended");
+        require!(payment_token == listing.payment_token, "not given token identifier");
+        require!(payment_amount >= listing.price, "bid below the minimum");
+        require!(payment_amount > listing.highest_bid, "bid must exceed the highest bid");
+
+        let caller = self.blockchain().get_caller();
+        require!(caller != listing.seller, "seller cannot bid on their own auction");
+
+        self.protect_inventory(&payment_token, 0, &payment_amount);
+        if listing.highest_bid > 0 {
+            self.release_inventory(&payment_token, 0, &listing.highest_bid);
+            self.send()
+                .direct(&listing.highest_bidder, &payment_token, 0, &listing.highest_bid, &[]);
+        }
+
+        listing.highest_bidder = caller.clone();
+        listing.highest_bid = payment_amount;
+        self.audited_set(&self.listings(nonce), self.audit_key_with(b"listings", &nonce), &listing);
+
+        self.bid_placed_event(&caller, nonce, &listing.highest_bid);
+    }
+
+    // anyone can end an auction after its deadline
+    #[endpoint(endAuction)]
+    fn end_auction(&self, nonce: u64) {
+        self.require_listed(nonce);
+
+        let listing = self.listings(nonce).get();
+        require!(listing.kind == ListingKind::Auction, "token not auctioned");
+        require!(self.blockchain().get_block_timestamp() >= listing.deadline, "auction still running");
+
+        self.remove_listing(nonce);
+        self.audited_set(
+            &self.auction_outcome(nonce),
+            self.audit_key_with(b"auction_outcome", &nonce),
+            &AuctionState::Settled,
+        );
+        if listing.highest_bid == 0 {
+            self.send_listed_nft(&listing.seller, nonce);
+            return;
+        }
+
+        self.release_inventory(&listing.payment_token, 0, &listing.highest_bid);
+        self.settle_sale(
+            &listing.seller,
+            &listing.highest_bidder,
+            nonce,
+            &listing.payment_token,
+            &listing.highest_bid,
+        );
+    }
+
+    #[endpoint(delistNft)]
+    fn delist_nft(&self, nonce: u64) {
+        self.require_listed(nonce);
+        let listing = self.listings(nonce).get();
+        require!(self.blockchain().get_caller() == listing.seller, "only the seller can delist");
+        require!(listing.highest_bid == 0, "auction has bids");
+
+        self.remove_listing(nonce);
+        if listing.kind == ListingKind::FixedPrice {
+            self.audited_set(
+                &self.listing_outcome(nonce),
+                self.audit_key_with(b"listing_outcome", &nonce),
+                &ListingState::Cancelled,
+            );
+        }
+        self.send_listed_nft(&listing.seller, nonce);
+    }
+
+    #[payable("*")]
+    #[endpoint(buyNft)]
+    fn buy_nft(
+        &self,
+        nonce: u64,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) {
+        self.require_feature_active(Feature::Marketplace);
+        self.require_valid_until(valid_until);
+        self.require_listed(nonce);
+
+        let listing = self.listings(nonce).get();
+        require!(listing.kind == ListingKind::FixedPrice, "token is auctioned");
+        require!(payment_token == listing.payment_token, "not given token identifier");
+        require!(payment_amount == listing.price, "payment does not match the price");
+
+        let caller = self.blockchain().get_caller();
+        require!(caller != listing.seller, "seller cannot buy their own listing");
+
+        self.remove_listing(nonce);
+        self.audited_set(
+            &self.listing_outcome(nonce),
+            self.audit_key_with(b"listing_outcome", &nonce),
+            &ListingState::Sold,
+        );
+        self.settle_sale(&listing.seller, &caller, nonce, &payment_token, &payment_amount);
+    }
+
+    #[payable("*")]
+    #[endpoint(makeOffer)]
+    fn make_offer(
+        &self,
+        nonce: u64,
+        expiry_timestamp: u64,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) -> u32 {
+        self.require_feature_active(Feature::Marketplace);
+        require!(!self.nonce_mint_id(nonce).is_empty(), "unknown nonce");
+        require!(payment_token == self.payment_token_id().get(), "not given token identifier");
+        require!(payment_amount > 0, "offer must be positive");
+        require!(expiry_timestamp > self.blockchain().get_block_timestamp(), "expiry must be in the future");
+
+        let offer_id = self.offer_count().get() + 1;
+        let buyer = self.blockchain().get_caller();
+        self.audited_set(
+            &self.offers(offer_id),
+            self.audit_key_with(b"offers", &offer_id),
+            &Offer {
+                nonce,
+                buyer: buyer.clone(),
+                payment_token: payment_token.clone(),
+                amount: payment_amount.clone(),
+                expiry_timestamp,
+            },
+        );
+        self.audited_set(&self.offer_count(), self.audit_key(b"offer_count"), &offer_id);
+        self.open_offer_ids().insert(offer_id);
+        self.log_entry_added(self.audit_key(b"open_offer_ids"), &offer_id);
+        self.protect_inventory(&payment_token, 0, &payment_amount);
+
+        self.offer_made_event(&buyer, nonce, offer_id, &payment_amount);
+
+        offer_id
+    }
+
+    // the holder sends the NFT the offer is for
+    #[payable("*")]
+    #[endpoint(acceptOffer)]
+    fn accept_offer(
+        &self,
+        offer_id: u32,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_nonce] payment_nonce: u64,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(Feature::Marketplace);
+        self.require_open_offer(offer_id);
+
+        let offer = self.offers(offer_id).get();
+        require!(
+            payment_token == self.nft_token_id().get() && payment_nonce == offer.nonce,
+            "token does not match the offer"
+        );
+        require!(payment_amount == LISTED_NFT_AMOUNT, "invalid amount");
+        require!(self.blockchain().get_block_timestamp() < offer.expiry_timestamp, "offer expired");
+
+        let seller = self.blockchain().get_caller();
+        self.remove_offer(offer_id);
+        self.release_inventory(&offer.payment_token, 0, &offer.amount);
+        // held like a listed token until delivered
+        self.protect_inventory(&payment_token, payment_nonce, &payment_amount);
+        self.settle_sale(&seller, &offer.buyer, offer.nonce, &offer.payment_token, &offer.amount);
+    }
+
+    // the buyer can take an offer back at any time, anyone once it expired
+    #[endpoint(withdrawOffer)]
+    fn withdraw_offer(&self, offer_id: u32) {
+        self.require_open_offer(offer_id);
+
+        let offer = self.offers(offer_id).get();
+        require!(
+            self.blockchain().get_caller() == offer.buyer
+                || self.blockchain().get_block_timestamp() >= offer.expiry_timestamp,
+            "offer still open"
+        );
+
+        self.remove_offer(offer_id);
+        self.release_inventory(&offer.payment_token, 0, &offer.amount);
+        self.send().direct(&offer.buyer, &offer.payment_token, 0, &offer.amount, &[]);
+    }
+
+    // private
+
+    fn require_open_offer(&self, offer_id: u32) {
+        require!(self.open_offer_ids().contains(&offer_id), "no such offer");
+    }
+
+    fn require_listed(&self, nonce: u64) {
+        require!(self.listed_nonces().contains(&nonce), "token not listed");
+    }
+
+    // the caller sent the NFT, which stays attributed to them until sold
+    fn add_listing(&self, kind: ListingKind, nonce: u64, price: BigUint, deadline: u64) {
+        let caller = self.blockchain().get_caller();
+        self.audited_set(
+            &self.listings(nonce),
+            self.audit_key_with(b"listings", &nonce),
+            &Listing {
+                kind,
+                seller: caller.clone(),
+                payment_token: self.payment_token_id().get(),
+                price,
+                listed_timestamp: self.blockchain().get_block_timestamp(),
+                deadline,
+                highest_bidder: ManagedAddress::zero(),
+                highest_bid: BigUint::zero(),
+            },
+        );
+        self.listed_nonces().insert(nonce);
+        self.log_entry_added(self.audit_key(b"listed_nonces"), &nonce);
+        self.audited_clear(&self.listing_outcome(nonce), self.audit_key_with(b"listing_outcome", &nonce));
+        self.audited_clear(&self.auction_outcome(nonce), self.audit_key_with(b"auction_outcome", &nonce));
+        self.protect_inventory(&self.nft_token_id().get(), nonce, &BigUint::from(LISTED_NFT_AMOUNT));
+        self.reattribute_token(&caller, nonce);
+    }
+
+    // pays out a sale of an NFT the contract holds and delivers it, the listing or
+    // offer behind it must already be removed
+    fn settle_sale(
+        &self,
+        seller: &ManagedAddress,
+        buyer: &ManagedAddress,
+        nonce: u64,
+        payment_token: &TokenIdentifier,
+        price: &BigUint,
+    ) {
+        let seller_amount = self.collect_sale_cuts(nonce, payment_token, price);
+        if seller_amount > 0 {
+            self.send().direct(seller, payment_token, 0, &seller_amount, &[]);
+        }
+        self.send_listed_nft(buyer, nonce);
+        self.reattribute_token(buyer, nonce);
+
+        self.nft_sold_event(seller, buyer, nonce, price);
+    }
+
+    fn remove_offer(&self, offer_id: u32) {
+        self.open_offer_ids().swap_remove(&offer_id);
+        self.log_entry_removed(self.audit_key(b"open_offer_ids"), &offer_id);
+        self.audited_clear(&self.offers(offer_id), self.audit_key_with(b"offers", &offer_id));
+    }
+
+    // takes royalties and the marketplace fee off the sale, returns the seller's part
+    fn collect_sale_cuts(&self, nonce: u64, payment_token: &TokenIdentifier, price: &BigUint) -> BigUint {
+        let token_data = self.blockchain().get_esdt_token_data(
+            &self.blockchain().get_sc_address(),
+            &self.nft_token_id().get(),
+            nonce,
+        );
+        let royalties = price * &token_data.royalties / BASIS_POINTS_MAX;
+        // tokens minted before the royalties maximum was committed can carry more,
+        // the fee then takes what is left
+        let fee = core::cmp::min(price * self.marketplace_fee().get() / BASIS_POINTS_MAX, price - &royalties);
+
+        if royalties > 0 {
+            let collection_royalties = if self.nonce_mint_id(nonce).is_empty() {
+                royalties.clone()
+            } else {
+                self.route_creator_royalties(self.nonce_mint_id(nonce).get(), payment_token, &royalties)
+            };
+            self.record_revenue(RevenueSource::Royalties, payment_token, &collection_royalties);
+        }
+        if fee > 0 {
+            self.record_revenue(RevenueSource::MarketplaceFees, payment_token, &fee);
+        }
+
+        price - &royalties - &fee
+    }
+
+    fn remove_listing(&self, nonce: u64) {
+        self.listed_nonces().swap_remove(&nonce);
+        self.log_entry_removed(self.audit_key(b"listed_nonces"), &nonce);
+        self.audited_clear(&self.listings(nonce), self.audit_key_with(b"listings", &nonce));
+    }
+
+    fn send_listed_nft(&self, to: &ManagedAddress, nonce: u64) {
+        let nft_token_id = self.nft_token_id().get();
+        let amount = BigUint::from(LISTED_NFT_AMOUNT);
+        self.release_inventory(&nft_token_id, nonce, &amount);
+        self.send().direct(to, &nft_token_id, nonce, &amount, &[]);
+    }
+
+    // views
+
+    // (nonce, listing) for open listings, `from` is a zero-based offset
+    #[view(getListings)]
+    fn get_listings(&self, from: usize, size: usize) -> MultiResultVec<MultiResult2<u64, Listing<Self::Api>>> {
+        self.listed_nonces()
+            .iter()
+            .skip(from)
+            .take(size)
+            .map(|nonce| (nonce, self.listings(nonce).get()).into())
+            .collect::<Vec<MultiResult2<u64, Listing<Self::Api>>>>()
+            .into()
+    }
+
+    #[view(getListingCount)]
+    fn get_listing_count(&self) -> usize {
+        self.listed_nonces().len()
+    }
+
+    // the open fixed-price listing, or how the last one closed
+    #[view(getListingState)]
+    fn get_listing_state(&self, nonce: u64) -> ListingState {
+        if self.listed_nonces().contains(&nonce) {
+            return match self.listings(nonce).get().kind {
+                ListingKind::FixedPrice => ListingState::Active,
+                ListingKind::Auction => ListingState::None,
+            };
+        }
+        if self.listing_outcome(nonce).is_empty() {
+            return ListingState::None;
+        }
+
+        self.listing_outcome(nonce).get()
+    }
+
+    // the open auction, or `Settled` once the last one was ended
+    #[view(getAuctionState)]
+    fn get_auction_state(&self, nonce: u64) -> AuctionState {
+        if self.listed_nonces().contains(&nonce) {
+            let listing = self.listings(nonce).get();
+            if listing.kind == ListingKind::FixedPrice {
+                return AuctionState::None;
+            }
+            if self.blockchain().get_block_timestamp() < listing.deadline {
+                return AuctionState::Running;
+            }
+            return AuctionState::Ended;
+        }
+        if self.auction_outcome(nonce).is_empty() {
+            return AuctionState::None;
+        }
+
+        self.auction_outcome(nonce).get()
+    }
+
+    // (offer id, offer) for the open offers on a token, expired ones included
+    #[view(getOffersForNonce)]
+    fn get_offers_for_nonce(&self, nonce: u64) -> MultiResultVec<MultiResult2<u32, Offer<Self::Api>>> {
+        self.open_offer_ids()
+            .iter()
+            .map(|offer_id| (offer_id, self.offers(offer_id).get()))
+            .filter(|(_, offer)| offer.nonce == nonce)
+            .map(|offer| offer.into())
+            .collect::<Vec<MultiResult2<u32, Offer<Self::Api>>>>()
+            .into()
+    }
+
+    // events
+
+    #[event("nft_sold")]
+    fn nft_sold_event(
+        &self,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] nonce: u64,
+        price: &BigUint,
+    );
+
+    #[event("offer_made")]
+    fn offer_made_event(
+        &self,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] nonce: u64,
+        #[indexed] offer_id: u32,
+        amount: &BigUint,
+    );
+
+    #[event("bid_placed")]
+    fn bid_placed_event(&self, #[indexed] bidder: &ManagedAddress, #[indexed] nonce: u64, amount: &BigUint);
+
+    // storage
+
+    #[view(getMarketplaceFee)]
+    #[storage_mapper("marketplace_fee")]
+    fn marketplace_fee(&self) -> SingleValueMapper<u32>;
+
+    #[view(getListing)]
+    #[storage_mapper("listings")]
+    fn listings(&self, nonce: u64) -> SingleValueMapper<Listing<Self::Api>>;
+
+    #[storage_mapper("listed_nonces")]
+    fn listed_nonces(&self) -> UnorderedSetMapper<u64>;
+
+    // cleared when the token is listed again
+    #[storage_mapper("listing_outcome")]
+    fn listing_outcome(&self, nonce: u64) -> SingleValueMapper<ListingState>;
+
+    #[storage_mapper("auction_outcome")]
+    fn auction_outcome(&self, nonce: u64) -> SingleValueMapper<AuctionState>;
+
+    #[storage_mapper("offer_count")]
+    fn offer_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getOffer)]
+    #[storage_mapper("offers")]
+    fn offers(&self, offer_id: u32) -> SingleValueMapper<Offer<Self::Api>>;
+
+    #[storage_mapper("open_offer_ids")]
+    fn open_offer_ids(&self) -> UnorderedSetMapper<u32>;
+}
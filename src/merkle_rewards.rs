@@ -0,0 +1,222 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::pause::Feature;
+
+pub const MERKLE_HASH_LENGTH: usize = 32;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct RewardDistribution<M: ManagedTypeApi> {
+    pub merkle_root: ManagedByteArray<M, MERKLE_HASH_LENGTH>,
+    pub token_id: TokenIdentifier<M>,
+    pub total_amount: BigUint<M>,
+    pub claimed_amount: BigUint<M>,
+    pub expiry_timestamp: u64,
+    pub swept: bool,
+}
+
+// Off-chain computed distributions: the admin publishes the root of a Merkle tree
+// whose leaves are sha256(address | amount big-endian) and every holder claims
+// its leaf with a proof. Pairs are hashed in sorted order, so proofs carry no
+// left/right flags. Distributions are funded from the reward pool, and what is
+// left unclaimed after expiry is swept back into it.
+#[elrond_wasm::module]
+pub trait MerkleRewardsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+{
+    // endpoints - role-gated
+
+    #[payable("*")]
+    #[endpoint(depositRewards)]
+    fn deposit_rewards(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_role(Role::Treasurer);
+        require!(payment_amount > 0, "amount must be positive");
+
+        self.add_to_reward_pool(&payment_token, &payment_amount);
+    }
+
+    #[endpoint(createRewardDistribution)]
+    fn create_reward_distribution(
+        &self,
+        merkle_root: ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH>,
+        token_id: TokenIdentifier,
+        total_amount: BigUint,
+        expiry_timestamp: u64,
+    ) -> u32 {
+        self.require_role(Role::Admin);
+        require!(
+            expiry_timestamp > self.blockchain().get_block_timestamp(),
+            "expiry must be in the future"
+        );
+        require!(total_amount > 0, "amount must be positive");
+        require!(
+            total_amount <= self.reward_pool(&token_id).get(),
+            "not enough rewards in pool"
+        );
+
+        // moves from the pool into the distribution, both stay protected
+        self.audited_update(
+            &self.reward_pool(&token_id),
+            self.audit_key_with(b"reward_pool", &token_id),
+            |v| *v -= &total_amount,
+        );
+
+        let distribution_id = self.distribution_count().get() + 1;
+        self.audited_set(
+            &self.distributions(distribution_id),
+            self.audit_key_with(b"distributions", &distribution_id),
+            &RewardDistribution {
+                merkle_root,
+                token_id,
+                total_amount,
+                claimed_amount: BigUint::zero(),
+                expiry_timestamp,
+                swept: false,
+            },
+        );
+        self.audited_set(&self.distribution_count(), self.audit_key(b"distribution_count"), &distribution_id);
+
+        distribution_id
+    }
+
+    /// endpoint
+
+    #[endpoint(claimReward)]
+    fn claim_reward(
+        &self,
+        distribution_id: u32,
+        amount: BigUint,
+        #[var_args] proof: ManagedVarArgs<ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH>>,
+    ) {
+        self.require_feature_active(Feature::Claims);
+        require!(!self.distributions(distribution_id).is_empty(), "distribution does not exist");
+
+        let mut distribution = self.distributions(distribution_id).get();
+        require!(
+            self.blockchain().get_block_timestamp() < distribution.expiry_timestamp,
+            "distribution expired"
+        );
+
+        let caller = self.blockchain().get_caller();
+        require!(!self.reward_claimed(distribution_id, &caller).get(), "reward already claimed");
+
+        let leaf = self.reward_leaf(&caller, &amount);
+        require!(
+            self.compute_merkle_root(leaf, proof) == distribution.merkle_root,
+            "invalid proof"
+        );
+
+        distribution.claimed_amount += &amount;
+        require!(
+            distribution.claimed_amount <= distribution.total_amount,
+            "distribution exhausted"
+        );
+
+        self.audited_set(
+            &self.reward_claimed(distribution_id, &caller),
+            self.audit_key_with(b"reward_claimed", &(distribution_id, &caller)),
+            &true,
+        );
+        self.audited_set(
+            &self.distributions(distribution_id),
+            self.audit_key_with(b"distributions", &distribution_id),
+            &distribution,
+        );
+
+        self.release_inventory(&distribution.token_id, 0, &amount);
+        self.send().direct(&caller, &distribution.token_id, 0, &amount, &[]);
+    }
+
+    // anyone can return an expired distribution's leftovers to the pool
+    #[endpoint(sweepUnclaimedRewards)]
+    fn sweep_unclaimed_rewards(&self, distribution_id: u32) {
+        require!(!self.distributions(distribution_id).is_empty(), "distribution does not exist");
+
+        let mut distribution = self.distributions(distribution_id).get();
+        require!(
+            self.blockchain().get_block_timestamp() >= distribution.expiry_timestamp,
+            "distribution not expired"
+        );
+        require!(!distribution.swept, "distribution already swept");
+
+        let unclaimed = &distribution.total_amount - &distribution.claimed_amount;
+        distribution.swept = true;
+        self.audited_set(
+            &self.distributions(distribution_id),
+            self.audit_key_with(b"distributions", &distribution_id),
+            &distribution,
+        );
+
+        // already protected as part of the distribution
+        self.audited_update(
+            &self.reward_pool(&distribution.token_id),
+            self.audit_key_with(b"reward_pool", &distribution.token_id),
+            |v| *v += &unclaimed,
+        );
+    }
+
+    // private
+
+    fn add_to_reward_pool(&self, token_id: &TokenIdentifier, amount: &BigUint) {
+        self.audited_update(
+            &self.reward_pool(token_id),
+            self.audit_key_with(b"reward_pool", token_id),
+            |v| *v += amount,
+        );
+        self.protect_inventory(token_id, 0, amount);
+    }
+
+    fn reward_leaf(&self, address: &ManagedAddress, amount: &BigUint) -> ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH> {
+        let mut leaf_data = address.as_managed_buffer().clone();
+        leaf_data.append(&amount.to_bytes_be_buffer());
+
+        self.crypto().sha256(&leaf_data)
+    }
+
+    fn compute_merkle_root(
+        &self,
+        leaf: ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH>,
+        proof: ManagedVarArgs<ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH>>,
+    ) -> ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH> {
+        let mut node = leaf;
+        for sibling in proof.into_iter() {
+            let (first, second) = if node.to_byte_array() <= sibling.to_byte_array() {
+                (node, sibling)
+            } else {
+                (sibling, node)
+            };
+
+            let mut pair = first.as_managed_buffer().clone();
+            pair.append(second.as_managed_buffer());
+            node = self.crypto().sha256(&pair);
+        }
+
+        node
+    }
+
+    // storage
+
+    #[view(getRewardPool)]
+    #[storage_mapper("reward_pool")]
+    fn reward_pool(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getDistributionCount)]
+    #[storage_mapper("distribution_count")]
+    fn distribution_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getRewardDistribution)]
+    #[storage_mapper("distributions")]
+    fn distributions(&self, distribution_id: u32) -> SingleValueMapper<RewardDistribution<Self::Api>>;
+
+    #[view(isRewardClaimed)]
+    #[storage_mapper("reward_claimed")]
+    fn reward_claimed(&self, distribution_id: u32, address: &ManagedAddress) -> SingleValueMapper<bool>;
+}
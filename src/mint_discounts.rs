@@ -0,0 +1,187 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::pause::Feature;
+
+const BASIS_POINTS_MAX: u32 = 10_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct DiscountTier<M: ManagedTypeApi> {
+    pub min_amount: BigUint<M>,
+    pub min_lock_seconds: u64,
+    // in basis points
+    pub discount: u32,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct TokenLock<M: ManagedTypeApi> {
+    pub amount: BigUint<M>,
+    pub lock_seconds: u64,
+    pub unlock_timestamp: u64,
+}
+
+// Users lock the payment token for a period and get a discount on mints while the
+// lock runs, e.g. 1000 tokens for 30 days -> 15% off. Locked tokens are escrowed
+// in the inventory ledger so withdraw and rescue cannot touch them. When the payment
+// token is migrated, locks keep using the token the program started with.
+#[elrond_wasm::module]
+pub trait MintDiscountsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+    + crate::storage::StorageModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(addDiscountTier)]
+    fn add_discount_tier(&self, min_amount: BigUint, min_lock_seconds: u64, discount: u32) -> usize {
+        self.require_role(Role::Admin);
+
+        require!(discount <= BASIS_POINTS_MAX, "discount cannot exceed 100%");
+
+        let tier = DiscountTier {
+            min_amount,
+            min_lock_seconds,
+            discount,
+        };
+        self.log_entry_added(self.audit_key(b"discount_tiers"), &tier);
+
+        self.discount_tiers().push(&tier)
+    }
+
+    #[endpoint(clearDiscountTiers)]
+    fn clear_discount_tiers(&self) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.discount_tiers().clear();
+        self.log_collection_cleared(self.audit_key(b"discount_tiers"));
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    // topping up an existing lock keeps the longer of the two periods
+    #[payable("*")]
+    #[endpoint(lockTokens)]
+    fn lock_tokens(
+        &self,
+        lock_seconds: u64,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(Feature::Staking);
+        require!(
+            payment_token == self.get_lock_token_id(),
+            "not given token identifier"
+        );
+        require!(payment_amount > 0, "amount must be positive");
+        require!(lock_seconds > 0, "lock period must be positive");
+
+        let caller = self.blockchain().get_caller();
+        let unlock_timestamp = self.blockchain().get_block_timestamp() + lock_seconds;
+        let lock = if self.token_locks(&caller).is_empty() {
+            TokenLock {
+                amount: payment_amount.clone(),
+                lock_seconds,
+                unlock_timestamp,
+            }
+        } else {
+            let mut lock = self.token_locks(&caller).get();
+            lock.amount += &payment_amount;
+            if unlock_timestamp > lock.unlock_timestamp {
+                lock.lock_seconds = lock_seconds;
+                lock.unlock_timestamp = unlock_timestamp;
+            }
+            lock
+        };
+
+        self.audited_set(&self.token_locks(&caller), self.audit_key_with(b"token_locks", &caller), &lock);
+        self.protect_inventory(&payment_token, 0, &payment_amount);
+    }
+
+    #[endpoint(unlockTokens)]
+    fn unlock_tokens(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(!self.token_locks(&caller).is_empty(), "no locked tokens");
+
+        let lock = self.token_locks(&caller).get();
+        require!(
+            self.blockchain().get_block_timestamp() >= lock.unlock_timestamp,
+            "tokens still locked"
+        );
+
+        let payment_token = self.get_lock_token_id();
+        self.audited_clear(&self.token_locks(&caller), self.audit_key_with(b"token_locks", &caller));
+        self.release_inventory(&payment_token, 0, &lock.amount);
+
+        self.send().direct(&caller, &payment_token, 0, &lock.amount, &[]);
+    }
+
+    // private
+
+    // pins the current lock token before the payment token changes
+    fn pin_lock_token(&self) {
+        if self.lock_token_id().is_empty() {
+            self.audited_set(&self.lock_token_id(), self.audit_key(b"lock_token_id"), &self.payment_token_id().get());
+        }
+    }
+
+    fn discounted_price(&self, buyer: &ManagedAddress, price: &BigUint) -> BigUint {
+        let discount = self.get_mint_discount(buyer.clone());
+
+        price * (BASIS_POINTS_MAX - discount) / BASIS_POINTS_MAX
+    }
+
+    // views
+
+    // best discount the address currently qualifies for, in basis points
+    #[view(getMintDiscount)]
+    fn get_mint_discount(&self, address: ManagedAddress) -> u32 {
+        if self.token_locks(&address).is_empty() {
+            return 0;
+        }
+
+        let lock = self.token_locks(&address).get();
+        if self.blockchain().get_block_timestamp() >= lock.unlock_timestamp {
+            return 0;
+        }
+
+        self.discount_tiers()
+            .iter()
+            .filter(|tier| lock.amount >= tier.min_amount && lock.lock_seconds >= tier.min_lock_seconds)
+            .map(|tier| tier.discount)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[view(getLockTokenId)]
+    fn get_lock_token_id(&self) -> TokenIdentifier {
+        if self.lock_token_id().is_empty() {
+            return self.payment_token_id().get();
+        }
+
+        self.lock_token_id().get()
+    }
+
+    #[view(getMintPriceFor)]
+    fn get_mint_price_for(&self, address: ManagedAddress) -> BigUint {
+        self.discounted_price(&address, &self.nft_token_price().get())
+    }
+
+    // storage
+
+    #[view(getDiscountTiers)]
+    #[storage_mapper("discount_tiers")]
+    fn discount_tiers(&self) -> VecMapper<DiscountTier<Self::Api>>;
+
+    // empty until the payment token is first migrated
+    #[storage_mapper("lock_token_id")]
+    fn lock_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getTokenLock)]
+    #[storage_mapper("token_locks")]
+    fn token_locks(&self, address: &ManagedAddress) -> SingleValueMapper<TokenLock<Self::Api>>;
+}
@@ -0,0 +1,197 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct MintRecord<M: ManagedTypeApi> {
+    pub timestamp: u64,
+    pub buyer: ManagedAddress<M>,
+    pub nonce: u64,
+    pub price: BigUint<M>,
+    pub payment_token: TokenIdentifier<M>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct CollectionMint<M: ManagedTypeApi> {
+    pub nonce: u64,
+    pub mint_id: u32,
+    pub minter: ManagedAddress<M>,
+    pub timestamp: u64,
+}
+
+#[elrond_wasm::module]
+pub trait MintHistoryModule: crate::audit::AuditModule {
+    // main collection only, see `record_token_mint`
+    fn record_mint(&self, buyer: &ManagedAddress, nonce: u64, price: &BigUint, payment_token: &TokenIdentifier) {
+        let record = self.new_mint_record(buyer, nonce, price, payment_token);
+
+        self.mint_history().push(&record);
+        self.log_entry_added(self.audit_key(b"mint_history"), &record);
+    }
+
+    // editions and side collections keep one history per token, their nonces
+    // overlap the main collection's
+    fn record_token_mint(
+        &self,
+        token_id: &TokenIdentifier,
+        buyer: &ManagedAddress,
+        nonce: u64,
+        price: &BigUint,
+        payment_token: &TokenIdentifier,
+    ) {
+        let record = self.new_mint_record(buyer, nonce, price, payment_token);
+
+        self.token_mint_history(token_id).push(&record);
+        self.log_entry_added(self.audit_key_with(b"token_mint_history", token_id), &record);
+    }
+
+    fn new_mint_record(
+        &self,
+        buyer: &ManagedAddress,
+        nonce: u64,
+        price: &BigUint,
+        payment_token: &TokenIdentifier,
+    ) -> MintRecord<Self::Api> {
+        MintRecord {
+            timestamp: self.blockchain().get_block_timestamp(),
+            buyer: buyer.clone(),
+            nonce,
+            price: price.clone(),
+            payment_token: payment_token.clone(),
+        }
+    }
+
+    fn mint_record_page(
+        &self,
+        history: VecMapper<MintRecord<Self::Api>>,
+        from: usize,
+        size: usize,
+    ) -> MultiResultVec<MintRecord<Self::Api>> {
+        let end = core::cmp::min(from.saturating_add(size), history.len());
+
+        let mut records = Vec::new();
+        for index in from..end {
+            // VecMapper is 1-indexed
+            records.push(history.get(index + 1));
+        }
+
+        records.into()
+    }
+
+    // main collection only, nonces of editions and other collections overlap
+    fn record_minter(&self, minter: &ManagedAddress, nonce: u64, mint_id: u32) {
+        self.audited_set(&self.minter_of(nonce), self.audit_key_with(b"minter_of", &nonce), minter);
+        self.nonces_minted_by(minter).push(&nonce);
+        self.log_entry_added(self.audit_key_with(b"nonces_minted_by", minter), &nonce);
+
+        let collection_mint = CollectionMint {
+            nonce,
+            mint_id,
+            minter: minter.clone(),
+            timestamp: self.blockchain().get_block_timestamp(),
+        };
+        self.audited_set(
+            &self.creation_time(nonce),
+            self.audit_key_with(b"creation_time", &nonce),
+            &collection_mint.timestamp,
+        );
+        self.collection_mints().push(&collection_mint);
+        self.log_entry_added(self.audit_key(b"collection_mints"), &collection_mint);
+    }
+
+    // views
+
+    // `from` is a zero-based offset into the history
+    #[view(getMintHistory)]
+    fn get_mint_history(&self, from: usize, size: usize) -> MultiResultVec<MintRecord<Self::Api>> {
+        self.mint_record_page(self.mint_history(), from, size)
+    }
+
+    #[view(getMintHistoryLength)]
+    fn get_mint_history_length(&self) -> usize {
+        self.mint_history().len()
+    }
+
+    // `from` is a zero-based offset into the token's history
+    #[view(getTokenMintHistory)]
+    fn get_token_mint_history(
+        &self,
+        token_id: TokenIdentifier,
+        from: usize,
+        size: usize,
+    ) -> MultiResultVec<MintRecord<Self::Api>> {
+        self.mint_record_page(self.token_mint_history(&token_id), from, size)
+    }
+
+    #[view(getTokenMintHistoryLength)]
+    fn get_token_mint_history_length(&self, token_id: TokenIdentifier) -> usize {
+        self.token_mint_history(&token_id).len()
+    }
+
+    // `from` is a zero-based offset into the main collection's mints
+    #[view(getMintedNonces)]
+    fn get_minted_nonces(&self, from: usize, size: usize) -> MultiResultVec<u64> {
+        let collection_mints = self.collection_mints();
+        let end = core::cmp::min(from.saturating_add(size), collection_mints.len());
+
+        let mut nonces = Vec::new();
+        for index in from..end {
+            nonces.push(collection_mints.get(index + 1).nonce);
+        }
+
+        nonces.into()
+    }
+
+    #[view(getMintRecords)]
+    fn get_mint_records(&self, from: usize, size: usize) -> MultiResultVec<CollectionMint<Self::Api>> {
+        let collection_mints = self.collection_mints();
+        let end = core::cmp::min(from.saturating_add(size), collection_mints.len());
+
+        let mut records = Vec::new();
+        for index in from..end {
+            records.push(collection_mints.get(index + 1));
+        }
+
+        records.into()
+    }
+
+    #[view(getCollectionMintCount)]
+    fn get_collection_mint_count(&self) -> usize {
+        self.collection_mints().len()
+    }
+
+    // `from` is a zero-based offset into the address' mints
+    #[view(getNoncesMintedBy)]
+    fn get_nonces_minted_by(&self, address: ManagedAddress, from: usize, size: usize) -> MultiResultVec<u64> {
+        let nonces = self.nonces_minted_by(&address);
+        let end = core::cmp::min(from.saturating_add(size), nonces.len());
+
+        let mut page = Vec::new();
+        for index in from..end {
+            page.push(nonces.get(index + 1));
+        }
+
+        page.into()
+    }
+
+    // storage
+
+    #[storage_mapper("collection_mints")]
+    fn collection_mints(&self) -> VecMapper<CollectionMint<Self::Api>>;
+
+    #[view(getMinterOf)]
+    #[storage_mapper("minter_of")]
+    fn minter_of(&self, nonce: u64) -> SingleValueMapper<ManagedAddress>;
+
+    // the `creatime` attribute of the main collection token
+    #[storage_mapper("creation_time")]
+    fn creation_time(&self, nonce: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("nonces_minted_by")]
+    fn nonces_minted_by(&self, minter: &ManagedAddress) -> VecMapper<u64>;
+
+    #[storage_mapper("mint_history")]
+    fn mint_history(&self) -> VecMapper<MintRecord<Self::Api>>;
+
+    #[storage_mapper("token_mint_history")]
+    fn token_mint_history(&self, token_id: &TokenIdentifier) -> VecMapper<MintRecord<Self::Api>>;
+}
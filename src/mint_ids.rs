@@ -0,0 +1,252 @@
+elrond_wasm::imports!();
+
+use crate::access_control::Role;
+
+// Allocation of collection mint ids. Regular mints take the lowest free id after
+// the last one handed out in sequence; in pick mode buyers can also claim a
+// specific free id for a premium, and sequential minting skips it later.
+// In recycle mode burned tokens give their id back, and recycled ids are handed
+// out before the sequence moves on. In random mode the next id is drawn from
+// the remaining ones with a swap-and-pop shuffle seeded by the block random
+// seed, so buyers can't tell which id their mint will get.
+#[elrond_wasm::module]
+pub trait MintIdsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::storage::StorageModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(setPickMode)]
+    fn set_pick_mode(&self, enabled: bool, premium: BigUint) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.audited_set(&self.pick_mode_enabled(), self.audit_key(b"pick_mode_enabled"), &enabled);
+        self.audited_set(&self.pick_premium(), self.audit_key(b"pick_premium"), &premium);
+
+        Ok(())
+    }
+
+    #[endpoint(setRecycleMode)]
+    fn set_recycle_mode(&self, enabled: bool) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.audited_set(&self.recycle_mode_enabled(), self.audit_key(b"recycle_mode_enabled"), &enabled);
+
+        Ok(())
+    }
+
+    // only before any id is handed out, the shuffle covers 1..=max supply
+    #[endpoint(setRandomMode)]
+    fn set_random_mode(&self, enabled: bool) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        require!(self.highest_mint_id().get() == 0, "mint ids already assigned");
+        let max_supply = self.max_supply().get();
+        require!(!enabled || max_supply > 0, "random mode requires a max supply");
+
+        self.audited_set(&self.random_mode_enabled(), self.audit_key(b"random_mode_enabled"), &enabled);
+        let pool_size = if enabled { max_supply } else { 0 };
+        self.audited_set(&self.random_pool_size(), self.audit_key(b"random_pool_size"), &pool_size);
+
+        Ok(())
+    }
+
+    // private
+
+    fn take_next_sequential_mint_id(&self) -> u32 {
+        while let Some(node) = self.recycled_mint_ids().pop_front() {
+            let mint_id = node.into_value();
+            self.log_entry_removed(self.audit_key(b"recycled_mint_ids"), &mint_id);
+
+            // picked since it was recycled
+            if !self.mint_id_taken(mint_id).get() {
+                self.mark_mint_id_taken(mint_id);
+                return mint_id;
+            }
+        }
+
+        if self.random_mode_enabled().get() {
+            return self.take_random_mint_id();
+        }
+
+        let mut mint_id = self.last_sequential_mint_id().get() + 1;
+        while self.mint_id_taken(mint_id).get() {
+            mint_id += 1;
+        }
+
+        self.audited_set(
+            &self.last_sequential_mint_id(),
+            self.audit_key(b"last_sequential_mint_id"),
+            &mint_id,
+        );
+        self.mark_mint_id_taken(mint_id);
+
+        mint_id
+    }
+
+    // the pool holds the ids not drawn yet at slots 0..size, an empty slot i
+    // standing for id i + 1; the drawn slot is refilled from the last one.
+    // Ids picked in the meantime stay in the pool and are skipped when drawn.
+    fn take_random_mint_id(&self) -> u32 {
+        let mut rng = RandomnessSource::<Self::Api>::new();
+        loop {
+            let pool_size = self.random_pool_size().get();
+            require!(pool_size > 0, "no mint id left");
+
+            let index = rng.next_u32_in_range(0, pool_size);
+            let last_index = pool_size - 1;
+            let mint_id = self.random_pool_slot_value(index);
+            if index != last_index {
+                let last_id = self.random_pool_slot_value(last_index);
+                self.audited_set(
+                    &self.random_pool_slot(index),
+                    self.audit_key_with(b"random_pool_slot", &index),
+                    &last_id,
+                );
+            }
+            if !self.random_pool_slot(last_index).is_empty() {
+                self.audited_clear(
+                    &self.random_pool_slot(last_index),
+                    self.audit_key_with(b"random_pool_slot", &last_index),
+                );
+            }
+            self.audited_set(&self.random_pool_size(), self.audit_key(b"random_pool_size"), &last_index);
+
+            if !self.mint_id_taken(mint_id).get() {
+                self.mark_mint_id_taken(mint_id);
+                return mint_id;
+            }
+        }
+    }
+
+    fn random_pool_slot_value(&self, index: u32) -> u32 {
+        let slot = self.random_pool_slot(index);
+        if slot.is_empty() {
+            index + 1
+        } else {
+            slot.get()
+        }
+    }
+
+    // first of the preferred ids still free, so a buyer racing others for the
+    // same number can name fallbacks instead of failing outright
+    fn take_preferred_mint_id(&self, preferred_ids: &ManagedVec<Self::Api, u32>) -> u32 {
+        require!(self.pick_mode_enabled().get(), "pick mode disabled");
+        require!(!preferred_ids.is_empty(), "no mint id given");
+
+        let mint_id = preferred_ids.iter().find(|mint_id| self.is_mint_id_available(*mint_id));
+        require!(mint_id.is_some(), "none of the mint ids is available");
+
+        let mint_id = mint_id.unwrap();
+        self.mark_mint_id_taken(mint_id);
+
+        mint_id
+    }
+
+    fn mark_mint_id_taken(&self, mint_id: u32) {
+        self.audited_set(&self.mint_id_taken(mint_id), self.audit_key_with(b"mint_id_taken", &mint_id), &true);
+        if mint_id > self.highest_mint_id().get() {
+            self.audited_set(&self.highest_mint_id(), self.audit_key(b"highest_mint_id"), &mint_id);
+        }
+    }
+
+    fn record_mint_id_nonce(&self, nonce: u64, mint_id: u32) {
+        self.audited_set(&self.nonce_mint_id(nonce), self.audit_key_with(b"nonce_mint_id", &nonce), &mint_id);
+    }
+
+    // frees the id of a burned token for a later mint
+    fn release_mint_id(&self, nonce: u64) -> u32 {
+        require!(!self.nonce_mint_id(nonce).is_empty(), "token has no mint id");
+
+        let mint_id = self.nonce_mint_id(nonce).get();
+        self.audited_clear(&self.nonce_mint_id(nonce), self.audit_key_with(b"nonce_mint_id", &nonce));
+        self.audited_clear(&self.mint_id_taken(mint_id), self.audit_key_with(b"mint_id_taken", &mint_id));
+        self.recycled_mint_ids().push_back(mint_id);
+        self.log_entry_added(self.audit_key(b"recycled_mint_ids"), &mint_id);
+
+        mint_id
+    }
+
+    // views
+
+    #[view(isMintIdAvailable)]
+    fn is_mint_id_available(&self, mint_id: u32) -> bool {
+        let max_supply = self.max_supply().get();
+
+        mint_id > 0 && (max_supply == 0 || mint_id <= max_supply) && !self.mint_id_taken(mint_id).get()
+    }
+
+    #[view(getMintIdForNonce)]
+    fn get_mint_id_for_nonce(&self, nonce: u64) -> u32 {
+        require!(!self.nonce_mint_id(nonce).is_empty(), "token has no mint id");
+
+        self.nonce_mint_id(nonce).get()
+    }
+
+    // up to `size` free ids, starting the search at `from`
+    #[view(getAvailableMintIds)]
+    fn get_available_mint_ids(&self, from: u32, size: usize) -> MultiResultVec<u32> {
+        let max_supply = self.max_supply().get();
+        let last = if max_supply == 0 { u32::MAX } else { max_supply };
+
+        let mut mint_ids = Vec::new();
+        let mut mint_id = core::cmp::max(from, 1);
+        while mint_ids.len() < size && mint_id <= last {
+            if !self.mint_id_taken(mint_id).get() {
+                mint_ids.push(mint_id);
+            }
+            if mint_id == u32::MAX {
+                break;
+            }
+            mint_id += 1;
+        }
+
+        mint_ids.into()
+    }
+
+    // storage
+
+    #[view(isPickModeEnabled)]
+    #[storage_mapper("pick_mode_enabled")]
+    fn pick_mode_enabled(&self) -> SingleValueMapper<bool>;
+
+    // added to the mint price when the buyer picks the id
+    #[view(getPickPremium)]
+    #[storage_mapper("pick_premium")]
+    fn pick_premium(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(isRecycleModeEnabled)]
+    #[storage_mapper("recycle_mode_enabled")]
+    fn recycle_mode_enabled(&self) -> SingleValueMapper<bool>;
+
+    #[view(isRandomModeEnabled)]
+    #[storage_mapper("random_mode_enabled")]
+    fn random_mode_enabled(&self) -> SingleValueMapper<bool>;
+
+    // ids not drawn yet in random mode
+    #[view(getRandomPoolSize)]
+    #[storage_mapper("random_pool_size")]
+    fn random_pool_size(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("random_pool_slot")]
+    fn random_pool_slot(&self, index: u32) -> SingleValueMapper<u32>;
+
+    #[view(getMintIdOfNonce)]
+    #[storage_mapper("nonce_mint_id")]
+    fn nonce_mint_id(&self, nonce: u64) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("recycled_mint_ids")]
+    fn recycled_mint_ids(&self) -> LinkedListMapper<u32>;
+
+    #[storage_mapper("last_sequential_mint_id")]
+    fn last_sequential_mint_id(&self) -> SingleValueMapper<u32>;
+
+    // max supply can never go below it
+    #[view(getHighestMintId)]
+    #[storage_mapper("highest_mint_id")]
+    fn highest_mint_id(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("mint_id_taken")]
+    fn mint_id_taken(&self, mint_id: u32) -> SingleValueMapper<bool>;
+}
@@ -0,0 +1,73 @@
+elrond_wasm::imports!();
+
+const MINT_PASS_AMOUNT: u32 = 1;
+
+// Mint passes from an earlier drop. A holder sends one pass with `mintWithPass`,
+// the contract burns it and mints an NFT for the pass price, which can be 0. The
+// pass token's manager has to give this contract the local burn role (NftBurn for
+// an SFT pass) before passes can be redeemed. Pass mints skip the public sale
+// window, so holders can redeem ahead of the sale.
+#[elrond_wasm::module]
+pub trait MintPassesModule: crate::audit::AuditModule {
+    // endpoints - owner-only
+
+    // nonce 0 for a fungible pass, `price` is in the payment token
+    #[only_owner]
+    #[endpoint(setMintPass)]
+    fn set_mint_pass(&self, token_id: TokenIdentifier, nonce: u64, price: BigUint) -> SCResult<()> {
+        require!(token_id.is_valid_esdt_identifier(), "invalid token identifier provided");
+
+        self.audited_set(&self.mint_pass_token_id(), self.audit_key(b"mint_pass_token_id"), &token_id);
+        self.audited_set(&self.mint_pass_nonce(), self.audit_key(b"mint_pass_nonce"), &nonce);
+        self.audited_set(&self.mint_pass_price(), self.audit_key(b"mint_pass_price"), &price);
+
+        Ok(())
+    }
+
+    #[only_owner]
+    #[endpoint(clearMintPass)]
+    fn clear_mint_pass(&self) -> SCResult<()> {
+        self.audited_clear(&self.mint_pass_token_id(), self.audit_key(b"mint_pass_token_id"));
+        self.audited_clear(&self.mint_pass_nonce(), self.audit_key(b"mint_pass_nonce"));
+        self.audited_clear(&self.mint_pass_price(), self.audit_key(b"mint_pass_price"));
+
+        Ok(())
+    }
+
+    // private
+
+    // burns the pass and returns the price still owed for the mint
+    fn redeem_mint_pass(&self, payment: &EsdtTokenPayment<Self::Api>) -> BigUint {
+        require!(!self.mint_pass_token_id().is_empty(), "mint passes not enabled");
+        require!(
+            payment.token_identifier == self.mint_pass_token_id().get()
+                && payment.token_nonce == self.mint_pass_nonce().get(),
+            "not a mint pass"
+        );
+        require!(payment.amount == MINT_PASS_AMOUNT, "one mint pass per mint");
+
+        self.send()
+            .esdt_local_burn(&payment.token_identifier, payment.token_nonce, &payment.amount);
+        self.audited_update(&self.mint_passes_redeemed(), self.audit_key(b"mint_passes_redeemed"), |v| *v += 1);
+
+        self.mint_pass_price().get()
+    }
+
+    // storage
+
+    #[view(getMintPassTokenId)]
+    #[storage_mapper("mint_pass_token_id")]
+    fn mint_pass_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getMintPassNonce)]
+    #[storage_mapper("mint_pass_nonce")]
+    fn mint_pass_nonce(&self) -> SingleValueMapper<u64>;
+
+    #[view(getMintPassPrice)]
+    #[storage_mapper("mint_pass_price")]
+    fn mint_pass_price(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getMintPassesRedeemed)]
+    #[storage_mapper("mint_passes_redeemed")]
+    fn mint_passes_redeemed(&self) -> SingleValueMapper<u32>;
+}
@@ -0,0 +1,96 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct MintStats<M: ManagedTypeApi> {
+    pub mint_count: u32,
+    pub revenue: BigUint<M>,
+}
+
+// Mint counts cover every mint, revenue is kept per payment token.
+#[elrond_wasm::module]
+pub trait MintStatsModule: crate::audit::AuditModule {
+    fn record_mint_stats(&self, phase_id: u32, mint_count: u32, token_id: &TokenIdentifier, revenue: &BigUint) {
+        let day = self.get_current_day();
+
+        self.audited_update(
+            &self.daily_mint_count(day),
+            self.audit_key_with(b"daily_mint_count", &day),
+            |v| *v += mint_count,
+        );
+        self.audited_update(
+            &self.daily_revenue(day, token_id),
+            self.audit_key_with(b"daily_revenue", &(day, token_id)),
+            |v| *v += revenue,
+        );
+
+        self.audited_update(
+            &self.phase_mint_count(phase_id),
+            self.audit_key_with(b"phase_mint_count", &phase_id),
+            |v| *v += mint_count,
+        );
+        self.audited_update(
+            &self.phase_revenue(phase_id, token_id),
+            self.audit_key_with(b"phase_revenue", &(phase_id, token_id)),
+            |v| *v += revenue,
+        );
+    }
+
+    // views
+
+    // day index since unix epoch, derived from the block timestamp
+    #[view(getCurrentDay)]
+    fn get_current_day(&self) -> u64 {
+        self.blockchain().get_block_timestamp() / SECONDS_PER_DAY
+    }
+
+    // revenue in `token_id` only
+    #[view(getDailyMintStats)]
+    fn get_daily_mint_stats(&self, day: u64, token_id: TokenIdentifier) -> MintStats<Self::Api> {
+        MintStats {
+            mint_count: self.daily_mint_count(day).get(),
+            revenue: self.daily_revenue(day, &token_id).get(),
+        }
+    }
+
+    // one entry per day in [from_day, to_day]
+    #[view(getDailyMintStatsRange)]
+    fn get_daily_mint_stats_range(
+        &self,
+        from_day: u64,
+        to_day: u64,
+        token_id: TokenIdentifier,
+    ) -> MultiResultVec<MintStats<Self::Api>> {
+        let mut stats = Vec::new();
+        for day in from_day..=to_day {
+            stats.push(self.get_daily_mint_stats(day, token_id.clone()));
+        }
+
+        stats.into()
+    }
+
+    // revenue in `token_id` only
+    #[view(getPhaseMintStats)]
+    fn get_phase_mint_stats(&self, phase_id: u32, token_id: TokenIdentifier) -> MintStats<Self::Api> {
+        MintStats {
+            mint_count: self.phase_mint_count(phase_id).get(),
+            revenue: self.phase_revenue(phase_id, &token_id).get(),
+        }
+    }
+
+    // storage
+
+    #[storage_mapper("daily_mint_count")]
+    fn daily_mint_count(&self, day: u64) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("daily_revenue")]
+    fn daily_revenue(&self, day: u64, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("phase_mint_count")]
+    fn phase_mint_count(&self, phase_id: u32) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("phase_revenue")]
+    fn phase_revenue(&self, phase_id: u32, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+}
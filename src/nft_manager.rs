@@ -5,278 +5,2492 @@ extern crate alloc;
 elrond_wasm::imports!();
 elrond_wasm::derive_imports!();
 
+pub mod access_control;
+pub mod attribute_updates;
+pub mod audit;
+pub mod collections;
+pub mod coupons;
+pub mod creators;
+pub mod deadline;
+pub mod dutch_auction;
+pub mod editions;
+pub mod export;
+pub mod fusion;
+pub mod holders;
+pub mod inventory;
+pub mod marketplace;
+pub mod merkle_rewards;
+pub mod mint_discounts;
+pub mod mint_history;
+pub mod mint_ids;
+pub mod mint_passes;
+pub mod mint_stats;
+pub mod partners;
+pub mod pause;
+pub mod phases;
+pub mod payouts;
+pub mod pieces;
+pub mod polls;
+pub mod price_tiers;
+pub mod raffle;
+pub mod rarity_boost;
+pub mod rarity_traits;
+pub mod receipts;
+pub mod redemptions;
+pub mod referrals;
+pub mod relayers;
+pub mod rentals;
+pub mod reservations;
+pub mod revenue;
+pub mod royalty_overrides;
+pub mod shareholders;
+pub mod signatures;
+pub mod staking;
+pub mod states;
+pub mod storage;
+pub mod timelock;
+pub mod trait_stats;
+pub mod treasury;
+pub mod upgrades;
+pub mod uri_resolver;
+pub mod waitlist;
+pub mod whitelist;
+
+// bumped with every release that needs `migrate` to prepare its storage
+pub const CONTRACT_VERSION: u32 = 1;
 const NFT_AMOUNT: u32 = 1;
 const ROYALTIES_MAX: u32 = 10_000;
+const METADATA_HASH_LENGTH: usize = 32;
+const MINT_MESSAGE_MAX_LENGTH: usize = 32;
+const MINT_MESSAGE_PUNCTUATION: &[u8] = b" -_.,!?'&";
+// keeps batch mints well inside the block gas limit
+const DEFAULT_MAX_MINTS_PER_TX: u32 = 10;
+
+const NFT_UPDATE_ATTRIBUTES_ROLE_NAME: &[u8] = b"ESDTRoleNFTUpdateAttributes";
+const NFT_ADD_URI_ROLE_NAME: &[u8] = b"ESDTRoleNFTAddURI";
+const TRANSFER_ROLE_NAME: &[u8] = b"ESDTTransferRole";
+// single-NFT variants the system SC proxy does not wrap
+const FREEZE_NFT_ENDPOINT: &[u8] = b"freezeSingleNFT";
+const UNFREEZE_NFT_ENDPOINT: &[u8] = b"unFreezeSingleNFT";
+const WIPE_NFT_ENDPOINT: &[u8] = b"wipeSingleNFT";
 
 const URI_SLASH: &[u8] = "/".as_bytes();
 const HASH_TAG: &[u8] = "#".as_bytes();
 const CREATION_TIME_KEY_NAME: &[u8] = "creatime:".as_bytes();
+const TAGS_KEY_NAME: &[u8] = "tags:".as_bytes();
+const METADATA_KEY_NAME: &[u8] = "metadata:".as_bytes();
+const CREATOR_KEY_NAME: &[u8] = "creator:".as_bytes();
+const FUSION_KEY_NAME: &[u8] = "fusion:".as_bytes();
+const MESSAGE_KEY_NAME: &[u8] = "message:".as_bytes();
+const TRAITS_KEY_NAME: &[u8] = "traits:".as_bytes();
+const ATTRIBUTES_SEPARATOR: &[u8] = ";".as_bytes();
+const TRAIT_VALUES_SEPARATOR: &[u8] = ",".as_bytes();
+const DECIMAL_POINT: &[u8] = ".".as_bytes();
 const IMAGE_FILE_EXTENSION: &[u8] = ".png".as_bytes();
 const METADATA_FILE_EXTENSION: &[u8] = ".json".as_bytes();
 
+use access_control::Role;
+use export::ExportEncoding;
+use merkle_rewards::MERKLE_HASH_LENGTH;
+use states::{SaleState, SetupStatus};
+use timelock::TimelockAction;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum NftRole {
+    NftCreate,
+    NftBurn,
+    NftUpdateAttributes,
+    NftAddUri,
+}
+
+impl NftRole {
+    fn role_name(&self) -> &'static [u8] {
+        match self {
+            NftRole::NftCreate => EsdtLocalRole::NftCreate.as_role_name(),
+            NftRole::NftBurn => EsdtLocalRole::NftBurn.as_role_name(),
+            // not in `EsdtLocalRole` yet
+            NftRole::NftUpdateAttributes => NFT_UPDATE_ATTRIBUTES_ROLE_NAME,
+            NftRole::NftAddUri => NFT_ADD_URI_ROLE_NAME,
+        }
+    }
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum ComplianceAction {
+    Freeze,
+    Unfreeze,
+    Wipe,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct FullConfig<M: ManagedTypeApi> {
+    pub nft_token_id: TokenIdentifier<M>,
+    pub nft_token_name: ManagedBuffer<M>,
+    pub payment_token_id: TokenIdentifier<M>,
+    pub nft_token_price: BigUint<M>,
+    pub royalties: u32,
+    pub max_royalties: u32,
+    pub image_base_uri: ManagedBuffer<M>,
+    pub metadata_base_uri: ManagedBuffer<M>,
+    pub metadata_cid: ManagedBuffer<M>,
+    pub tags: ManagedBuffer<M>,
+    pub paused: bool,
+    pub max_supply: u32,
+    pub sale_start_timestamp: u64,
+    pub sale_end_timestamp: u64,
+    pub edition_token_id: TokenIdentifier<M>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct SaleStatus<M: ManagedTypeApi> {
+    pub token_id: TokenIdentifier<M>,
+    // undiscounted price of the next public mint
+    pub price: BigUint<M>,
+    pub payment_token: TokenIdentifier<M>,
+    pub minted: u32,
+    pub max_supply: u32,
+    pub paused: bool,
+    pub sale_start: u64,
+    pub sale_end: u64,
+    pub current_phase: states::SalePhase,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct PaymentTokenMigration<M: ManagedTypeApi> {
+    pub old_price: BigUint<M>,
+    pub new_price: BigUint<M>,
+    pub rate_numerator: BigUint<M>,
+    pub rate_denominator: BigUint<M>,
+    // unprotected balance of the old token sent to the treasury
+    pub swept_amount: BigUint<M>,
+}
+
 #[elrond_wasm::contract]
-pub trait NftManager {
+pub trait NftManager:
+    access_control::AccessControlModule
+    + attribute_updates::AttributeUpdatesModule
+    + audit::AuditModule
+    + collections::CollectionsModule
+    + coupons::CouponsModule
+    + creators::CreatorsModule
+    + deadline::DeadlineModule
+    + dutch_auction::DutchAuctionModule
+    + editions::EditionsModule
+    + export::ExportModule
+    + fusion::FusionModule
+    + holders::HoldersModule
+    + inventory::InventoryModule
+    + marketplace::MarketplaceModule
+    + merkle_rewards::MerkleRewardsModule
+    + mint_discounts::MintDiscountsModule
+    + mint_history::MintHistoryModule
+    + mint_ids::MintIdsModule
+    + mint_passes::MintPassesModule
+    + mint_stats::MintStatsModule
+    + partners::PartnersModule
+    + pause::PauseModule
+    + phases::PhasesModule
+    + payouts::PayoutsModule
+    + pieces::PiecesModule
+    + polls::PollsModule
+    + price_tiers::PriceTiersModule
+    + raffle::RaffleModule
+    + rarity_boost::RarityBoostModule
+    + rarity_traits::RarityTraitsModule
+    + receipts::ReceiptsModule
+    + redemptions::RedemptionsModule
+    + referrals::ReferralsModule
+    + relayers::RelayersModule
+    + rentals::RentalsModule
+    + reservations::ReservationsModule
+    + revenue::RevenueModule
+    + royalty_overrides::RoyaltyOverridesModule
+    + shareholders::ShareholdersModule
+    + signatures::SignaturesModule
+    + staking::StakingModule
+    + storage::StorageModule
+    + timelock::TimelockModule
+    + trait_stats::TraitStatsModule
+    + treasury::TreasuryModule
+    + upgrades::UpgradesModule
+    + uri_resolver::UriResolverModule
+    + waitlist::WaitlistModule
+    + whitelist::WhitelistModule
+{
     #[init]
-    fn init(&self, payment_token_id: TokenIdentifier, nft_token_price: BigUint, royalties: u32, image_base_uri: ManagedBuffer, metadata_base_uri: ManagedBuffer) -> SCResult<()> {
-        require!(royalties <= ROYALTIES_MAX, "royalties cannot exceed 100%");
+    fn init(&self, payment_token_id: TokenIdentifier, nft_token_price: BigUint, royalties: u32, image_base_uri: ManagedBuffer, metadata_base_uri: ManagedBuffer, max_royalties: u32, max_supply: u32) -> SCResult<()> {
+        // an upgrade runs init again; existing state is kept and `migrate` fills in the rest
+        if !self.payment_token_id().is_empty() {
+            return Ok(());
+        }
+
+        require!(max_royalties <= ROYALTIES_MAX, "royalties cannot exceed 100%");
+        require!(royalties <= max_royalties, "royalties cannot exceed the committed maximum");
         require!(
             payment_token_id.is_egld() || payment_token_id.is_valid_esdt_identifier(),
             "invalid token identifier provided"
         );
 
-        self.payment_token_id().set(&payment_token_id);
-        self.nft_token_price().set(&nft_token_price);
-        self.royalties().set(royalties);
-        self.image_base_uri().set(&image_base_uri);
-        self.metadata_base_uri().set(&metadata_base_uri);
+        self.audited_set(
+            &self.payment_token_id(),
+            self.audit_key(b"payment_token_id"),
+            &payment_token_id,
+        );
+        self.audited_set(
+            &self.nft_token_price(),
+            self.audit_key(b"nft_token_price"),
+            &nft_token_price,
+        );
+        self.audited_set(&self.royalties(), self.audit_key(b"royalties"), &royalties);
+        self.audited_set(&self.max_royalties(), self.audit_key(b"max_royalties"), &max_royalties);
+        self.audited_set(&self.max_supply(), self.audit_key(b"max_supply"), &max_supply);
+        self.audited_set(
+            &self.image_base_uri(),
+            self.audit_key(b"image_base_uri"),
+            &image_base_uri,
+        );
+        self.audited_set(
+            &self.metadata_base_uri(),
+            self.audit_key(b"metadata_base_uri"),
+            &metadata_base_uri,
+        );
 
         // set mint_count to 0 for indexing
-        self.mint_count().set(0u32);
+        self.audited_set(&self.mint_count(), self.audit_key(b"mint_count"), &0u32);
+
+        // the deployer receives proceeds until a treasury change is accepted
+        self.audited_set(
+            &self.treasury_address(),
+            self.audit_key(b"treasury_address"),
+            &self.blockchain().get_caller(),
+        );
+        self.audited_set(&self.contract_version(), self.audit_key(b"contract_version"), &CONTRACT_VERSION);
 
         Ok(())
     }
 
-    // endpoints - owner-only
+    // endpoints - restricted
+
+    // run once after an upgrade: storage read by newer code is filled with defaults
+    // where a deployment of an older version left it empty, nothing set is overwritten
+    #[only_owner]
+    #[endpoint(migrate)]
+    fn migrate(&self) -> u32 {
+        let old_version = self.contract_version().get();
+        require!(old_version < CONTRACT_VERSION, "contract already migrated");
+
+        if self.mint_count().is_empty() {
+            self.audited_set(&self.mint_count(), self.audit_key(b"mint_count"), &0u32);
+        }
+        // older deployments committed to no maximum, the current royalties become it
+        if self.max_royalties().is_empty() {
+            self.audited_set(&self.max_royalties(), self.audit_key(b"max_royalties"), &self.royalties().get());
+        }
+        if self.treasury_address().is_empty() {
+            self.audited_set(
+                &self.treasury_address(),
+                self.audit_key(b"treasury_address"),
+                &self.blockchain().get_owner_address(),
+            );
+        }
+        // mint ids were handed out in mint order before they were tracked
+        if self.highest_mint_id().is_empty() {
+            self.audited_set(&self.highest_mint_id(), self.audit_key(b"highest_mint_id"), &self.mint_count().get());
+        }
+
+        self.audited_set(&self.contract_version(), self.audit_key(b"contract_version"), &CONTRACT_VERSION);
+        self.contract_migrated_event(old_version, CONTRACT_VERSION);
+
+        CONTRACT_VERSION
+    }
 
+    // A soulbound collection gets the transfer role on this contract with the local
+    // roles. Once any address holds it, tokens only move from or to role holders, so
+    // holders can send NFTs back to the contract but not to each other.
+    // `can_freeze` and `can_wipe` enable the compliance endpoints and cannot be
+    // turned on later.
     #[only_owner]
     #[payable("EGLD")]
     #[endpoint(issueNft)]
-    fn issue_nft(&self, token_name: ManagedBuffer, token_ticker: ManagedBuffer) -> AsyncCall {
+    fn issue_nft(
+        &self,
+        token_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+        #[var_args] soulbound: OptionalArg<bool>,
+        #[var_args] can_freeze: OptionalArg<bool>,
+        #[var_args] can_wipe: OptionalArg<bool>,
+    ) -> AsyncCall {
         require!(self.nft_token_id().is_empty(), "Token already issued");
+        require!(!self.nft_issue_pending().get(), "Token issuance already in progress");
 
         // save token name
-        self.nft_token_name().set(&token_name);
+        self.audited_set(&self.nft_token_name(), self.audit_key(b"nft_token_name"), &token_name);
+        self.audited_set(&self.nft_issue_pending(), self.audit_key(b"nft_issue_pending"), &true);
+        if soulbound.into_option().unwrap_or(false) {
+            self.audited_set(&self.soulbound(), self.audit_key(b"soulbound"), &true);
+        }
+        let can_freeze = can_freeze.into_option().unwrap_or(false);
+        let can_wipe = can_wipe.into_option().unwrap_or(false);
+        self.audited_set(&self.can_freeze(), self.audit_key(b"can_freeze"), &can_freeze);
+        self.audited_set(&self.can_wipe(), self.audit_key(b"can_wipe"), &can_wipe);
 
-        let payment_amount = self.call_value().egld_value();
+        self.issue_nft_call(&token_name, &token_ticker)
+    }
+
+    // reissues with the name and options of the failed attempt
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(retryIssueNft)]
+    fn retry_issue_nft(&self, token_ticker: ManagedBuffer) -> AsyncCall {
+        require!(self.issue_status().get() == SetupStatus::Failed, "no failed issuance to retry");
+
+        self.audited_set(&self.nft_issue_pending(), self.audit_key(b"nft_issue_pending"), &true);
+        self.issue_nft_call(&self.nft_token_name().get(), &token_ticker)
+    }
+
+    // Without arguments this contract gets every role it needs, once. Specific
+    // roles can be granted to this contract or a companion contract at any time,
+    // e.g. burn or update roles for a staking or upgrade contract.
+    #[only_owner]
+    #[endpoint(setLocalRoles)]
+    fn set_local_roles(
+        &self,
+        #[var_args] target: OptionalArg<ManagedAddress>,
+        #[var_args] roles: ManagedVarArgs<NftRole>,
+    ) -> AsyncCall {
+        self.require_token_issued();
+        require!(!self.creation_stopped().get(), "creation stopped");
+
+        let sc_address = self.blockchain().get_sc_address();
+        let target = target.into_option().unwrap_or_else(|| sc_address.clone());
+        if roles.is_empty() {
+            require!(target == sc_address, "no roles given");
+            require!(!self.local_roles_set().get(), "Local roles already set");
+
+            return self.local_roles_call();
+        }
+
+        let mut set_roles_call = self.send().esdt_system_sc_proxy().set_special_roles(
+            &target,
+            &self.nft_token_id().get(),
+            core::iter::empty(),
+        );
+        let mut grants_create = false;
+        for role in roles.into_iter() {
+            grants_create |= role == NftRole::NftCreate;
+            set_roles_call.push_argument_raw_bytes(role.role_name());
+        }
+
+        set_roles_call
+            .async_call()
+            .with_callback(NftManager::callbacks(self).special_roles_callback(target, grants_create))
+    }
+
+    #[only_owner]
+    #[endpoint(retrySetLocalRoles)]
+    fn retry_set_local_roles(&self) -> AsyncCall {
+        self.require_token_issued();
+        require!(!self.creation_stopped().get(), "creation stopped");
+        require!(self.roles_status().get() == SetupStatus::Failed, "no failed roles request to retry");
+
+        self.local_roles_call()
+    }
+
+    // compliance takedowns, only for a collection issued with `can_freeze`
+    #[only_owner]
+    #[endpoint(freezeNft)]
+    fn freeze_nft(&self, address: ManagedAddress, nonce: u64) -> AsyncCall {
+        self.require_token_issued();
+        require!(self.can_freeze().get(), "collection not freezable");
+
+        self.esdt_system_sc_nft_call(FREEZE_NFT_ENDPOINT, &address, nonce)
+            .async_call()
+            .with_callback(NftManager::callbacks(self).compliance_callback(ComplianceAction::Freeze, address, nonce))
+    }
+
+    #[only_owner]
+    #[endpoint(unfreezeNft)]
+    fn unfreeze_nft(&self, address: ManagedAddress, nonce: u64) -> AsyncCall {
+        self.require_token_issued();
+        require!(self.can_freeze().get(), "collection not freezable");
+
+        self.esdt_system_sc_nft_call(UNFREEZE_NFT_ENDPOINT, &address, nonce)
+            .async_call()
+            .with_callback(NftManager::callbacks(self).compliance_callback(ComplianceAction::Unfreeze, address, nonce))
+    }
+
+    // the token has to be frozen first
+    #[only_owner]
+    #[endpoint(wipeNft)]
+    fn wipe_nft(&self, address: ManagedAddress, nonce: u64) -> AsyncCall {
+        self.require_token_issued();
+        require!(self.can_wipe().get(), "collection not wipeable");
+
+        self.esdt_system_sc_nft_call(WIPE_NFT_ENDPOINT, &address, nonce)
+            .async_call()
+            .with_callback(NftManager::callbacks(self).compliance_callback(ComplianceAction::Wipe, address, nonce))
+    }
+
+    // Permanently caps the supply at what is minted. Every call revokes the create
+    // role from one holder: first the addresses it was granted to through
+    // `setLocalRoles`, then this contract. Once this contract gave it up, no mint
+    // path works and the role cannot be granted again.
+    #[only_owner]
+    #[endpoint(stopCreation)]
+    fn stop_creation(&self) -> AsyncCall {
+        self.require_token_issued();
+        require!(!self.creation_stopped().get(), "creation already stopped");
+
+        let address = self
+            .create_role_addresses()
+            .iter()
+            .next()
+            .unwrap_or_else(|| self.blockchain().get_sc_address());
         self.send()
             .esdt_system_sc_proxy()
-            .issue_non_fungible(
-                payment_amount,
-                &token_name,
-                &token_ticker,
-                NonFungibleTokenProperties {
-                    can_freeze: false,
-                    can_wipe: false,
-                    can_pause: false,
-                    can_change_owner: true,
-                    can_upgrade: false,
-                    can_add_special_roles: true,
-                },
+            .unset_special_roles(
+                &address,
+                &self.nft_token_id().get(),
+                [EsdtLocalRole::NftCreate][..].iter().cloned(),
             )
             .async_call()
-            .with_callback(self.callbacks().issue_callback())
+            .with_callback(NftManager::callbacks(self).stop_creation_callback(address))
     }
 
+    // Hands the collection to a v2 manager without reissuing it. The create role
+    // has to move first, while this contract is still the token manager; minting
+    // here is paused once it has moved.
     #[only_owner]
-    #[endpoint(setLocalRoles)]
-    fn set_local_roles(&self) -> AsyncCall {
+    #[endpoint(transferNftCreateRole)]
+    fn transfer_nft_create_role(&self, new_creator: ManagedAddress) -> AsyncCall {
         self.require_token_issued();
+        require!(!self.creation_stopped().get(), "creation stopped");
+        require!(self.collection_new_owner().is_empty(), "collection already transferred");
+        require!(!new_creator.is_zero(), "invalid address");
 
         self.send()
             .esdt_system_sc_proxy()
-            .set_special_roles(
-                &self.blockchain().get_sc_address(),
+            .transfer_nft_create_role(
                 &self.nft_token_id().get(),
-                [EsdtLocalRole::NftCreate][..].iter().cloned(),
+                &self.blockchain().get_sc_address().to_address(),
+                &new_creator.to_address(),
             )
             .async_call()
+            .with_callback(NftManager::callbacks(self).create_role_transfer_callback(new_creator))
     }
 
     #[only_owner]
+    #[endpoint(transferCollectionOwnership)]
+    fn transfer_collection_ownership(&self, new_owner: ManagedAddress) -> AsyncCall {
+        self.require_token_issued();
+        require!(self.collection_new_owner().is_empty(), "collection already transferred");
+        require!(!new_owner.is_zero(), "invalid address");
+
+        self.send()
+            .esdt_system_sc_proxy()
+            .transfer_ownership(&self.nft_token_id().get(), &new_owner.to_address())
+            .async_call()
+            .with_callback(NftManager::callbacks(self).ownership_transfer_callback(new_owner))
+    }
+
+    // lets `address` move soulbound tokens, e.g. a staking or bridge contract
+    #[only_owner]
+    #[endpoint(setTransferRole)]
+    fn set_transfer_role(&self, address: ManagedAddress) -> AsyncCall {
+        self.require_soulbound();
+        require!(!self.transfer_role_addresses().contains(&address), "address already has the transfer role");
+
+        let mut set_role_call = self.send().esdt_system_sc_proxy().set_special_roles(
+            &address,
+            &self.nft_token_id().get(),
+            core::iter::empty(),
+        );
+        set_role_call.push_argument_raw_bytes(TRANSFER_ROLE_NAME);
+
+        set_role_call
+            .async_call()
+            .with_callback(NftManager::callbacks(self).transfer_role_callback(address, true))
+    }
+
+    // the last role holder cannot be removed, that would make the tokens transferable
+    #[only_owner]
+    #[endpoint(unsetTransferRole)]
+    fn unset_transfer_role(&self, address: ManagedAddress) -> AsyncCall {
+        self.require_soulbound();
+        require!(self.transfer_role_addresses().contains(&address), "address does not have the transfer role");
+        require!(self.transfer_role_addresses().len() > 1, "cannot remove the last transfer role");
+
+        let mut unset_role_call = self.send().esdt_system_sc_proxy().unset_special_roles(
+            &address,
+            &self.nft_token_id().get(),
+            core::iter::empty(),
+        );
+        unset_role_call.push_argument_raw_bytes(TRANSFER_ROLE_NAME);
+
+        unset_role_call
+            .async_call()
+            .with_callback(NftManager::callbacks(self).transfer_role_callback(address, false))
+    }
+
     #[endpoint(pauseMinting)]
     fn pause_minting(&self) -> SCResult<()> {
-        self.paused().set(true);
+        self.require_role(Role::Operator);
+
+        self.audited_set(&self.paused(), self.audit_key(b"paused"), &true);
+        self.pause_event(true);
 
         Ok(())
     }
 
-    #[only_owner]
     #[endpoint(startMinting)]
     fn start_minting(&self) -> SCResult<()> {
+        self.require_role(Role::Operator);
+
         require!(!self.nft_token_id().is_empty(), "token not issued");
+        require!(self.local_roles_set().get(), "local roles not set");
 
-        self.paused().clear();
+        self.audited_clear(&self.paused(), self.audit_key(b"paused"));
+        self.pause_event(false);
 
         Ok(())
     }
 
-    // return estd of token_id
-    // return egld if token_id is not given
-    #[only_owner]
-    #[endpoint(withdraw)]
-    fn withdraw(&self, #[var_args] token_id: OptionalArg<TokenIdentifier>) -> SCResult<()> {
-        let payment_token_id = if let OptionalArg::Some(ti) = token_id {
-            ti
+    // 0 means unlimited
+    #[endpoint(setMaxSupply)]
+    fn set_max_supply(&self, max_supply: u32) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        require!(
+            max_supply == 0 || max_supply >= self.highest_mint_id().get(),
+            "max supply below minted ids"
+        );
+        require!(
+            max_supply == 0 || max_supply >= self.mint_count().get() + self.team_reserve().get(),
+            "max supply below the team reserve"
+        );
+        require!(!self.random_mode_enabled().get(), "max supply fixed in random mode");
+        if self.sale_started() {
+            let current = self.max_supply().get();
+            require!(
+                max_supply != 0 && (current == 0 || max_supply <= current),
+                "max supply can only decrease after sale start"
+            );
         }
-        else {
-            TokenIdentifier::egld()
-        };
 
-        let balance = self.blockchain().get_sc_balance(&payment_token_id, 0);
-        require!(balance != BigUint::zero(), "not enough balance");
+        self.audited_set(&self.max_supply(), self.audit_key(b"max_supply"), &max_supply);
 
-        let caller = self.blockchain().get_caller();
-        
-        self.send().direct(&caller, &payment_token_id, 0, &balance, &[]);
+        Ok(())
+    }
+
+    // 0 restores the default cap
+    #[endpoint(setMaxMintsPerTx)]
+    fn set_max_mints_per_tx(&self, max_mints: u32) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.audited_set(&self.max_mints_per_tx(), self.audit_key(b"max_mints_per_tx"), &max_mints);
 
         Ok(())
     }
 
+    // 0 means unlimited; spreads a drop over several blocks
+    #[endpoint(setMaxMintsPerBlock)]
+    fn set_max_mints_per_block(&self, max_mints: u32) -> SCResult<()> {
+        self.require_role(Role::Admin);
 
-    /// endpoint
+        self.audited_set(&self.max_mints_per_block(), self.audit_key(b"max_mints_per_block"), &max_mints);
 
-    #[payable("*")]
-    #[endpoint(mint)]
-    fn mint(&self, #[payment_token] payment_token: TokenIdentifier, #[payment_amount] payment_amount: BigUint) {
-        self.require_token_issued();
+        Ok(())
+    }
+
+    // 0 means unlimited, lowering it does not affect mints already made
+    #[endpoint(setMaxMintsPerAddress)]
+    fn set_max_mints_per_address(&self, max_mints: u32) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.audited_set(
+            &self.max_mints_per_address(),
+            self.audit_key(b"max_mints_per_address"),
+            &max_mints,
+        );
+
+        Ok(())
+    }
+
+    // a timestamp of 0 leaves that side of the window open
+    #[endpoint(setSaleWindow)]
+    fn set_sale_window(&self, start_timestamp: u64, end_timestamp: u64) -> SCResult<()> {
+        self.require_role(Role::Admin);
 
         require!(
-            payment_token == self.payment_token_id().get(),
-            "not given token identifier"
+            end_timestamp == 0 || end_timestamp > start_timestamp,
+            "sale end must be after sale start"
         );
         require!(
-            payment_amount >= self.nft_token_price().get(),
-            "not enough tokens"
+            !self.sale_started() || start_timestamp == self.sale_start_timestamp().get(),
+            "sale start cannot move after sale start"
         );
 
-        let nft_nonce = self._mint();
-        let nft_token_id = self.nft_token_id().get();
-        let caller = self.blockchain().get_caller();
-        self.send().direct(
-            &caller,
-            &nft_token_id,
-            nft_nonce,
-            &BigUint::from(NFT_AMOUNT),
-            &[],
+        self.audited_set(
+            &self.sale_start_timestamp(),
+            self.audit_key(b"sale_start_timestamp"),
+            &start_timestamp,
+        );
+        self.audited_set(
+            &self.sale_end_timestamp(),
+            self.audit_key(b"sale_end_timestamp"),
+            &end_timestamp,
         );
+
+        Ok(())
     }
 
-    // /// private
+    // CID of the metadata folder, without gateway prefix
+    #[endpoint(setMetadataCid)]
+    fn set_metadata_cid(&self, metadata_cid: ManagedBuffer) -> SCResult<()> {
+        self.require_role(Role::MetadataManager);
 
-    fn _mint(&self) -> u64 {
-        use alloc::string::ToString;
+        require!(
+            !metadata_cid.to_boxed_bytes().as_slice().contains(&ATTRIBUTES_SEPARATOR[0]),
+            "metadata CID cannot contain ';'"
+        );
 
-        // self.require_token_issued();
+        self.audited_set(&self.metadata_cid(), self.audit_key(b"metadata_cid"), &metadata_cid);
 
-        let nft_token_id = self.nft_token_id().get();
+        Ok(())
+    }
 
-        let creation_time_key = ManagedBuffer::new_from_bytes(CREATION_TIME_KEY_NAME);
-        let creation_time = ManagedBuffer::from(&self.blockchain().get_block_timestamp().to_ne_bytes());
-        let mut attributes = ManagedBuffer::new();
-        attributes.append(&creation_time_key);
-        attributes.append(&creation_time);
+    // comma separated, e.g. "art,pfp"
+    #[endpoint(setTags)]
+    fn set_tags(&self, tags: ManagedBuffer) -> SCResult<()> {
+        self.require_role(Role::MetadataManager);
 
-        let attributes_hash = self
-            .crypto()
-            .sha256_legacy(&attributes.to_boxed_bytes().as_slice());
-        let hash_buffer = ManagedBuffer::from(attributes_hash.as_bytes());
+        require!(
+            !tags.to_boxed_bytes().as_slice().contains(&ATTRIBUTES_SEPARATOR[0]),
+            "tags cannot contain ';'"
+        );
 
-        let mint_id = self.mint_count().get() + 1;
+        self.audited_set(&self.tags(), self.audit_key(b"tags"), &tags);
 
-        let mut name = ManagedBuffer::new();
-        name.append(&self.nft_token_name().get());
-        name.append(&ManagedBuffer::new_from_bytes(HASH_TAG));
-        name.append(&ManagedBuffer::new_from_bytes(mint_id.to_string().as_bytes()));
+        Ok(())
+    }
 
-        sc_print!("name: {:x}", name,);
+    // sha256 of each token's hosted metadata JSON, committed before that id is minted
+    #[endpoint(setMetadataHashes)]
+    fn set_metadata_hashes(&self, #[var_args] hashes: ManagedVarArgs<MultiArg2<u32, ManagedBuffer>>) -> SCResult<()> {
+        self.require_role(Role::MetadataManager);
 
-        let mut uris = ManagedVec::new();
-        
-        let mut image_uri = ManagedBuffer::new();
-        image_uri.append(&self.image_base_uri().get());
-        image_uri.append(&ManagedBuffer::new_from_bytes(URI_SLASH));
-        image_uri.append(&ManagedBuffer::new_from_bytes(mint_id.to_string().as_bytes()));
-        image_uri.append(&ManagedBuffer::new_from_bytes(IMAGE_FILE_EXTENSION));
+        for entry in hashes.into_iter() {
+            let (mint_id, content_hash) = entry.into_tuple();
+            require!(!self.mint_id_taken(mint_id).get(), "token already minted");
+            require!(content_hash.len() == METADATA_HASH_LENGTH, "invalid metadata hash length");
 
-        sc_print!("name: {:x}", image_uri);
+            self.audited_set(
+                &self.metadata_hash(mint_id),
+                self.audit_key_with(b"metadata_hash", &mint_id),
+                &content_hash,
+            );
+        }
 
-        uris.push(image_uri);
+        Ok(())
+    }
 
-        let mut metadata_uri = ManagedBuffer::new();
-        metadata_uri.append(&self.image_base_uri().get());
-        metadata_uri.append(&ManagedBuffer::new_from_bytes(URI_SLASH));
-        metadata_uri.append(&ManagedBuffer::new_from_bytes(mint_id.to_string().as_bytes()));
-        metadata_uri.append(&ManagedBuffer::new_from_bytes(METADATA_FILE_EXTENSION));
+    // in basis points, never above the maximum committed at deploy
+    #[endpoint(setRoyalties)]
+    fn set_royalties(&self, royalties: u32) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
 
-        sc_print!("name: {:x}", metadata_uri);
+        self.change_royalties(royalties);
 
-        uris.push(metadata_uri);
+        Ok(())
+    }
 
-        let nft_nonce = self.send().esdt_nft_create(
-            &nft_token_id,
-            &BigUint::from(NFT_AMOUNT),
-            &name,
-            &BigUint::from(self.royalties().get()),
-            &hash_buffer,
-            &attributes,
-            &uris,
-        );
+    // price of a public mint outside sale phases, tiers and the Dutch auction
+    #[endpoint(setPrice)]
+    fn set_price(&self, price: BigUint) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
 
-        self.mint_count().update(|v| *v += 1);
+        self.change_price(price);
 
-        nft_nonce
+        Ok(())
     }
 
-    fn require_token_issued(&self) {
-        require!(!self.nft_token_id().is_empty(), "Token not issued");
+    // only affects tokens minted afterwards
+    #[endpoint(setImageBaseUri)]
+    fn set_image_base_uri(&self, image_base_uri: ManagedBuffer) -> SCResult<()> {
+        self.require_role(Role::MetadataManager);
+        self.require_not_timelocked();
+
+        self.change_image_base_uri(image_base_uri);
+
+        Ok(())
     }
 
-    // callbacks
+    // only affects tokens minted afterwards
+    #[endpoint(setMetadataBaseUri)]
+    fn set_metadata_base_uri(&self, metadata_base_uri: ManagedBuffer) -> SCResult<()> {
+        self.require_role(Role::MetadataManager);
+        self.require_not_timelocked();
 
-    #[callback]
-    fn issue_callback(&self, #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>) {
-        match result {
-            ManagedAsyncCallResult::Ok(token_id) => {
-                self.nft_token_id().set(&token_id);
-            },
-            ManagedAsyncCallResult::Err(_) => {
-                let caller = self.blockchain().get_owner_address();
-                let (returned_tokens, token_id) = self.call_value().payment_token_pair();
-                if token_id.is_egld() && returned_tokens > 0 {
-                    self.send()
-                        .direct(&caller, &token_id, 0, &returned_tokens, &[]);
-                }
-            },
-        }
+        self.change_metadata_base_uri(metadata_base_uri);
+
+        Ok(())
+    }
+
+    // hash over the ordered collection art, fixed once and only before the first mint
+    #[only_owner]
+    #[endpoint(setProvenanceHash)]
+    fn set_provenance_hash(&self, hash: ManagedBuffer) -> SCResult<()> {
+        require!(self.provenance_hash().is_empty(), "provenance hash already set");
+        require!(self.highest_mint_id().get() == 0, "minting already started");
+        require!(hash.len() == METADATA_HASH_LENGTH, "invalid provenance hash length");
+
+        self.audited_set(&self.provenance_hash(), self.audit_key(b"provenance_hash"), &hash);
+
+        Ok(())
+    }
+
+    #[only_owner]
+    #[endpoint(setContractCallersAllowed)]
+    fn set_contract_callers_allowed(&self, allowed: bool) -> SCResult<()> {
+        self.audited_set(
+            &self.contract_callers_allowed(),
+            self.audit_key(b"contract_callers_allowed"),
+            &allowed,
+        );
+
+        Ok(())
+    }
+
+    // paid out of the unprotected payment token balance, 0 disables refunds
+    #[only_owner]
+    #[endpoint(setBurnRefund)]
+    fn set_burn_refund(&self, amount: BigUint) -> SCResult<()> {
+        self.audited_set(&self.burn_refund(), self.audit_key(b"burn_refund"), &amount);
+
+        Ok(())
     }
 
-    /// storage
+    // free mints for giveaways, one per receiver; they skip pricing and per-wallet
+    // limits but not the supply cap, reservations or the waitlist
+    #[endpoint(airdrop)]
+    fn airdrop(&self, #[var_args] receivers: ManagedVarArgs<ManagedAddress>) -> usize {
+        self.require_role(Role::Admin);
+        self.require_token_issued();
+        self.require_not_paused();
+
+        let receiver_count = receivers.len();
+        require!(receiver_count > 0, "no receivers");
+        require!(self.waitlist().is_empty(), "supply reserved for the waitlist");
+        require!(
+            self.has_unreserved_supply(receiver_count as u32),
+            "remaining supply is reserved"
+        );
+
+        let nft_token_id = self.nft_token_id().get();
+        let payment_token = self.payment_token_id().get();
+        let phase_id = self.get_active_phase_id();
+        for receiver in receivers.into_iter() {
+            let mint_id = self.take_next_sequential_mint_id();
+            let nft_nonce = self._mint(&receiver, mint_id, None, self.get_rarity_boost(receiver.clone()));
+            self.send().direct(&receiver, &nft_token_id, nft_nonce, &BigUint::from(NFT_AMOUNT), &[]);
+
+            self.record_mint(&receiver, nft_nonce, &BigUint::zero(), &payment_token);
+            self.mint_event(&receiver, nft_nonce, mint_id, &BigUint::zero());
+            self.record_mint_stats(phase_id, NFT_AMOUNT, &payment_token, &BigUint::zero());
+            self.attribute_token(&receiver, nft_nonce);
+        }
 
-    #[view(getNftTokenId)]
-    #[storage_mapper("nft_token_id")]
-    fn nft_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+        receiver_count
+    }
 
-    #[view(getNftTokenPrice)]
-    #[storage_mapper("nft_token_price")]
-    fn nft_token_price(&self) -> SingleValueMapper<BigUint>;
+    // free mints out of the team reserve, which the public sale never reaches
+    #[only_owner]
+    #[endpoint(mintTeamReserve)]
+    fn mint_team_reserve(&self, amount: u32, recipient: ManagedAddress) -> MultiResultVec<u64> {
+        self.require_token_issued();
+        self.require_not_paused();
+        require!(amount > 0, "amount must be positive");
+        require!(!recipient.is_zero(), "invalid recipient");
+        self.take_team_reserve(amount);
 
-    #[view(getPaymentTokenId)]
-    #[storage_mapper("payment_token_id")]
-    fn payment_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+        let nft_token_id = self.nft_token_id().get();
+        let payment_token = self.payment_token_id().get();
+        let phase_id = self.get_active_phase_id();
+        let mut nonces = Vec::new();
+        for _ in 0..amount {
+            let mint_id = self.take_next_sequential_mint_id();
+            let nft_nonce = self._mint(&recipient, mint_id, None, self.get_rarity_boost(recipient.clone()));
+            self.send().direct(&recipient, &nft_token_id, nft_nonce, &BigUint::from(NFT_AMOUNT), &[]);
 
-    #[view(isPaused)]
-    #[storage_mapper("paused")]
-    fn paused(&self) -> SingleValueMapper<bool>;
+            self.record_mint(&recipient, nft_nonce, &BigUint::zero(), &payment_token);
+            self.mint_event(&recipient, nft_nonce, mint_id, &BigUint::zero());
+            self.record_mint_stats(phase_id, NFT_AMOUNT, &payment_token, &BigUint::zero());
+            self.attribute_token(&recipient, nft_nonce);
+            nonces.push(nft_nonce);
+        }
 
-    #[view(getMintCount)]
-    #[storage_mapper("mint_count")]
-    fn mint_count(&self) -> SingleValueMapper<u32>;
+        nonces.into()
+    }
 
-    // base metadatas
+    // mints to up to `count` raffle entrants drawn at random, the others can claim refunds
+    #[only_owner]
+    #[endpoint(drawWinners)]
+    fn draw_winners(&self, count: u32) -> MultiResultVec<u64> {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+        self.require_not_paused();
+        let winner_count = core::cmp::min(count, self.raffle_entrant_count().get());
+        require!(self.has_unreserved_supply(winner_count), "not enough supply for the winners");
 
-    #[view(getNftTokenName)]
-    #[storage_mapper("nft_token_name")]
-    fn nft_token_name(&self) -> SingleValueMapper<ManagedBuffer>;
+        let nft_token_id = self.nft_token_id().get();
+        let mut nonces = Vec::new();
+        for (winner, entry) in self.draw_raffle_winners(count) {
+            let mint_id = self.take_next_sequential_mint_id();
+            let nft_nonce = self._mint(&winner, mint_id, None, self.get_rarity_boost(winner.clone()));
+            self.send().direct(&winner, &nft_token_id, nft_nonce, &BigUint::from(NFT_AMOUNT), &[]);
 
-    #[view(getRoyalties)]
-    #[storage_mapper("royalties")]
-    fn royalties(&self) -> SingleValueMapper<u32>;
+            self.record_paid_mint(&winner, nft_nonce, mint_id, &entry.payment_token, &entry.amount);
+            nonces.push(nft_nonce);
+        }
 
-    #[view(getImageBaseUri)]
-    #[storage_mapper("image_base_uri")]
-    fn image_base_uri(&self) -> SingleValueMapper<ManagedBuffer>;
+        nonces.into()
+    }
 
-    #[view(getMetadataBaseUri)]
-    #[storage_mapper("metadata_base_uri")]
-    fn metadata_base_uri(&self) -> SingleValueMapper<ManagedBuffer>;
+    // Switches the payment token between drops, with mint paused. Every price still
+    // quoted in the old token is converted at numerator / denominator new tokens per
+    // old token (see `convert_payment_prices`), and the old token's free balance
+    // goes to the treasury. Escrowed payments (waitlist, reservations, raffle
+    // entries, discount locks) keep their own token. Closed while a timelock is set.
+    #[only_owner]
+    #[endpoint(migratePaymentToken)]
+    fn migrate_payment_token(
+        &self,
+        new_token_id: TokenIdentifier,
+        rate_numerator: BigUint,
+        rate_denominator: BigUint,
+    ) -> SCResult<()> {
+        require!(
+            new_token_id.is_egld() || new_token_id.is_valid_esdt_identifier(),
+            "invalid token identifier provided"
+        );
+        let old_token_id = self.payment_token_id().get();
+        require!(new_token_id != old_token_id, "token already accepted");
+        require!(rate_numerator > 0 && rate_denominator > 0, "invalid conversion rate");
+        require!(self.is_feature_paused(pause::Feature::Mint), "mint must be paused");
+        self.require_not_timelocked();
+
+        let old_price = self.nft_token_price().get();
+        let new_price = &old_price * &rate_numerator / &rate_denominator;
+        self.audited_set(&self.nft_token_price(), self.audit_key(b"nft_token_price"), &new_price);
+        self.convert_payment_prices(&old_token_id, &new_token_id, &rate_numerator, &rate_denominator);
+
+        self.pin_lock_token();
+        self.audited_set(&self.payment_token_id(), self.audit_key(b"payment_token_id"), &new_token_id);
+
+        let swept_amount = self.get_rescuable_amount(old_token_id.clone(), 0);
+        if swept_amount > 0 {
+            self.send().direct(&self.treasury_address().get(), &old_token_id, 0, &swept_amount, &[]);
+        }
+
+        self.payment_token_migrated_event(
+            &old_token_id,
+            &new_token_id,
+            &PaymentTokenMigration {
+                old_price,
+                new_price,
+                rate_numerator,
+                rate_denominator,
+                swept_amount,
+            },
+        );
+
+        Ok(())
+    }
+
+    // return estd of token_id
+    // return egld if token_id is not given
+    // funds always go to the registered treasury
+    #[endpoint(withdraw)]
+    fn withdraw(&self, #[var_args] token_id: OptionalArg<TokenIdentifier>) -> SCResult<()> {
+        self.require_role(Role::Treasurer);
+
+        let payment_token_id = if let OptionalArg::Some(ti) = token_id {
+            ti
+        }
+        else {
+            TokenIdentifier::egld()
+        };
+
+        // escrowed funds stay in the contract
+        let balance = self.get_rescuable_amount(payment_token_id.clone(), 0);
+        require!(balance != BigUint::zero(), "not enough balance");
+
+        let treasury = self.treasury_address().get();
+
+        self.send().direct(&treasury, &payment_token_id, 0, &balance, &[]);
+        self.withdraw_event(&payment_token_id, &balance);
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(mint)]
+    fn mint(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) {
+        self.require_valid_until(valid_until);
+
+        let caller = self.blockchain().get_caller();
+        self.mint_paid(&caller, payment_token, payment_amount, None, None, None, None);
+    }
+
+    // the referrer earns a commission and gets the buyer the referral discount
+    #[payable("*")]
+    #[endpoint(mintWithReferrer)]
+    fn mint_with_referrer(
+        &self,
+        referrer: ManagedAddress,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) {
+        self.require_valid_until(valid_until);
+
+        let caller = self.blockchain().get_caller();
+        let referrer = self.valid_referrer(&caller, referrer);
+        self.mint_paid(&caller, payment_token, payment_amount, None, None, referrer, None);
+    }
+
+    // gift or custodial mint: the caller pays, `recipient` receives the NFT
+    #[payable("*")]
+    #[endpoint(mintFor)]
+    fn mint_for(
+        &self,
+        recipient: ManagedAddress,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) {
+        self.require_valid_until(valid_until);
+        require!(!recipient.is_zero(), "invalid recipient");
+
+        self.mint_paid(&recipient, payment_token, payment_amount, None, None, None, None);
+    }
+
+    // mints `amount` NFTs delivered in a single multi-transfer, capped per transaction
+    #[payable("*")]
+    #[endpoint(mintMultiple)]
+    fn mint_multiple(
+        &self,
+        amount: u32,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) -> MultiResultVec<u64> {
+        self.require_valid_until(valid_until);
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+        self.require_not_contract_caller();
+
+        require!(amount > 0, "amount must be positive");
+        require!(amount <= self.get_max_mints_per_tx(), "amount exceeds per transaction cap");
+        require!(
+            payment_token == self.payment_token_id().get(),
+            "not given token identifier"
+        );
+        let caller = self.blockchain().get_caller();
+        let price = self.discounted_price(&caller, &self.require_public_sale(&caller, amount));
+        let charged = self.charge_payment(&caller, &payment_token, &payment_amount, &price);
+
+        // rounding dust of the split stays unattributed in the per-mint history
+        let unit_payment = &charged / &BigUint::from(amount);
+        let nft_token_id = self.nft_token_id().get();
+        let mut nonces = Vec::new();
+        let mut transfers = ManagedVec::new();
+        for _ in 0..amount {
+            let nft_nonce = self.mint_for_buyer(&caller, None, None, &payment_token, &unit_payment);
+            transfers.push(EsdtTokenPayment::new(nft_token_id.clone(), nft_nonce, BigUint::from(NFT_AMOUNT)));
+            nonces.push(nft_nonce);
+        }
+
+        let result = Self::Api::send_api_impl().direct_multi_esdt_transfer_execute(
+            &caller,
+            &transfers,
+            0,
+            &ManagedBuffer::new(),
+            &ManagedArgBuffer::new_empty(),
+        );
+        if let Err(message) = result {
+            sc_panic!(message);
+        }
+
+        nonces.into()
+    }
+
+    // presale mint for whitelisted addresses, `allocation` is the amount in their leaf
+    #[payable("*")]
+    #[endpoint(mintWhitelist)]
+    fn mint_whitelist(
+        &self,
+        allocation: u32,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] proof: ManagedVarArgs<ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH>>,
+    ) -> u64 {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+
+        let caller = self.blockchain().get_caller();
+        self.consume_presale_allocation(&caller, allocation, proof);
+        require!(
+            payment_token == self.payment_token_id().get(),
+            "not given token identifier"
+        );
+        let charged = self.charge_payment(&caller, &payment_token, &payment_amount, &self.presale_price().get());
+
+        self.mint_to_buyer(&caller, None, None, &payment_token, &charged)
+    }
+
+    // off-chain allowlist: the signer grants the caller up to `max_allowed` mints,
+    // which can happen before the public sale window opens; the public sale price
+    // applies (see `allowlist_unit_price`)
+    #[payable("*")]
+    #[endpoint(mintWithSignature)]
+    fn mint_with_signature(
+        &self,
+        max_allowed: u32,
+        signature_nonce: u64,
+        expiry: u64,
+        signature: ManagedBuffer,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) -> u64 {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+
+        let caller = self.blockchain().get_caller();
+        let mut payload = ManagedBuffer::new();
+        payload.append_u32_be(max_allowed);
+        self.verify_signature(b"allowlist_mint", &caller, signature_nonce, expiry, &payload, &signature);
+
+        let minted = self.allowlist_mints(&caller).get();
+        require!(minted < max_allowed, "allowlist allocation used up");
+        self.audited_set(
+            &self.allowlist_mints(&caller),
+            self.audit_key_with(b"allowlist_mints", &caller),
+            &(minted + 1),
+        );
+
+        require!(
+            payment_token == self.payment_token_id().get(),
+            "not given token identifier"
+        );
+        let price = self.discounted_price(&caller, &self.allowlist_unit_price());
+        let charged = self.charge_payment(&caller, &payment_token, &payment_amount, &price);
+
+        self.mint_to_buyer(&caller, None, None, &payment_token, &charged)
+    }
+
+    // short name or dedication embedded in the attributes, e.g. for gift mints
+    #[payable("*")]
+    #[endpoint(mintWithMessage)]
+    fn mint_with_message(
+        &self,
+        message: ManagedBuffer,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) {
+        self.require_valid_until(valid_until);
+        self.require_valid_mint_message(&message);
+
+        self.mint_paid(&self.blockchain().get_caller(), payment_token, payment_amount, None, Some(message), None, None);
+    }
+
+    // mints the first free id of `preferred_ids` for the pick premium on top of the price
+    #[payable("*")]
+    #[endpoint(mintPicked)]
+    fn mint_picked(
+        &self,
+        preferred_ids: ManagedVec<Self::Api, u32>,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) {
+        self.require_valid_until(valid_until);
+
+        self.mint_paid(&self.blockchain().get_caller(), payment_token, payment_amount, Some(preferred_ids), None, None, None);
+    }
+
+    // public mint at the discount of a registered promo code, uses up one of its redemptions
+    #[payable("*")]
+    #[endpoint(mintWithCoupon)]
+    fn mint_with_coupon(
+        &self,
+        code: ManagedBuffer,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) {
+        self.require_valid_until(valid_until);
+
+        self.mint_paid(&self.blockchain().get_caller(), payment_token, payment_amount, None, None, None, Some(code));
+    }
+
+    // membership is shown by sending a partner collection token, which is returned;
+    // a claim with a price takes the payment as the second transfer
+    #[payable("*")]
+    #[endpoint(claimPartnerMint)]
+    fn claim_partner_mint(&self, partner_id: u32) {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+
+        let transfers = self.call_value().all_esdt_transfers();
+        require!(!transfers.is_empty(), "partner token required");
+
+        let caller = self.blockchain().get_caller();
+        let membership = transfers.get(0);
+        require!(membership.amount > 0, "invalid amount");
+        self.use_partner_token(partner_id, &membership.token_identifier, membership.token_nonce);
+        let price = self.consume_partner_claim(partner_id, &caller);
+
+        let (payment_token, payment_amount) = match transfers.try_get(1) {
+            Some(payment) => (payment.token_identifier, payment.amount),
+            None => (self.payment_token_id().get(), BigUint::zero()),
+        };
+        require!(transfers.len() <= 2, "too many transfers");
+        require!(
+            payment_token == self.payment_token_id().get(),
+            "not given token identifier"
+        );
+        let charged = self.charge_payment(&caller, &payment_token, &payment_amount, &price);
+
+        self.mint_to_buyer(&caller, None, None, &payment_token, &charged);
+
+        self.send().direct(
+            &caller,
+            &membership.token_identifier,
+            membership.token_nonce,
+            &membership.amount,
+            &[],
+        );
+    }
+
+    #[payable("*")]
+    #[endpoint(buyPiece)]
+    fn buy_piece(
+        &self,
+        piece_id: u32,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+        #[var_args] valid_until: OptionalArg<u64>,
+    ) -> u64 {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_valid_until(valid_until);
+        self.require_token_issued();
+        self.require_not_paused();
+
+        let piece = self.take_piece(piece_id, &payment_token, &payment_amount);
+        require!(self.waitlist().is_empty(), "supply reserved for the waitlist");
+        require!(self.has_unreserved_supply(1), "remaining supply is reserved");
+
+        let caller = self.blockchain().get_caller();
+        let mint_id = self.take_next_sequential_mint_id();
+        let mut uris = ManagedVec::new();
+        uris.push(piece.uri.clone());
+        let nft_nonce = self.create_main_nft(
+            &caller,
+            mint_id,
+            &piece.name,
+            piece.royalties,
+            &ManagedBuffer::new(),
+            &ManagedBuffer::new(),
+            &uris,
+        );
+        self.mark_piece_sold(piece, nft_nonce);
+        self.send().direct(&caller, &self.nft_token_id().get(), nft_nonce, &BigUint::from(NFT_AMOUNT), &[]);
+
+        self.record_paid_mint(&caller, nft_nonce, mint_id, &payment_token, &payment_amount);
+
+        nft_nonce
+    }
+
+    // the pass is the first transfer, a pass price is paid with the second
+    #[payable("*")]
+    #[endpoint(mintWithPass)]
+    fn mint_with_pass(&self) -> u64 {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+        self.require_not_contract_caller();
+
+        let transfers = self.call_value().all_esdt_transfers();
+        require!(!transfers.is_empty(), "mint pass required");
+        require!(transfers.len() <= 2, "too many transfers");
+
+        let caller = self.blockchain().get_caller();
+        let price = self.redeem_mint_pass(&transfers.get(0));
+
+        let (payment_token, payment_amount) = match transfers.try_get(1) {
+            Some(payment) => (payment.token_identifier, payment.amount),
+            None => (self.payment_token_id().get(), BigUint::zero()),
+        };
+        require!(
+            payment_token == self.payment_token_id().get(),
+            "not given token identifier"
+        );
+        let charged = self.charge_payment(&caller, &payment_token, &payment_amount, &price);
+
+        self.mint_to_buyer(&caller, None, None, &payment_token, &charged)
+    }
+
+    #[payable("*")]
+    #[endpoint(claimPartnerMintWithVoucher)]
+    fn claim_partner_mint_with_voucher(
+        &self,
+        partner_id: u32,
+        signature_nonce: u64,
+        expiry: u64,
+        signature: ManagedBuffer,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+
+        let caller = self.blockchain().get_caller();
+        self.verify_partner_voucher(partner_id, &caller, signature_nonce, expiry, &signature);
+        let price = self.consume_partner_claim(partner_id, &caller);
+        if payment_amount > 0 {
+            require!(
+                payment_token == self.payment_token_id().get(),
+                "not given token identifier"
+            );
+        }
+        let payment_token = self.payment_token_id().get();
+        let charged = self.charge_payment(&caller, &payment_token, &payment_amount, &price);
+
+        self.mint_to_buyer(&caller, None, None, &payment_token, &charged);
+    }
+
+    // gasless mint: a relayer submits the buyer's signed voucher and is reimbursed
+    // from the relayer pool, the mint itself is free for the buyer
+    #[endpoint(mintSponsored)]
+    fn mint_sponsored(
+        &self,
+        buyer: ManagedAddress,
+        signature_nonce: u64,
+        expiry: u64,
+        signature: ManagedBuffer,
+    ) -> u64 {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+        self.require_sale_open();
+
+        self.verify_signature(b"sponsored_mint", &buyer, signature_nonce, expiry, &ManagedBuffer::new(), &signature);
+        self.reimburse_relayer(&self.blockchain().get_caller());
+
+        self.mint_to_buyer(&buyer, None, None, &self.payment_token_id().get(), &BigUint::zero())
+    }
+
+    // in recycle mode holders can burn a token to give its id back to the supply
+    #[payable("*")]
+    #[endpoint(burnToRecycle)]
+    fn burn_to_recycle(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_nonce] payment_nonce: u64,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(pause::Feature::Mint);
+        require!(self.recycle_mode_enabled().get(), "recycle mode disabled");
+        require!(payment_token == self.nft_token_id().get(), "only collection NFTs can be burned");
+        require!(payment_amount == NFT_AMOUNT, "invalid amount");
+
+        let mint_id = self.release_mint_id(payment_nonce);
+        self.forget_traits(mint_id);
+        self.audited_clear(&self.mint_message(mint_id), self.audit_key_with(b"mint_message", &mint_id));
+        self.audited_update(&self.mint_count(), self.audit_key(b"mint_count"), |v| *v -= 1);
+        self.send().esdt_local_burn(&payment_token, payment_nonce, &payment_amount);
+
+        self.release_token(&self.blockchain().get_caller(), payment_nonce);
+    }
+
+    // burns a collection NFT for good, its id is not handed out again; pays the
+    // configured refund in the payment token, if any
+    #[payable("*")]
+    #[endpoint(burn)]
+    fn burn(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_nonce] payment_nonce: u64,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        require!(payment_token == self.nft_token_id().get(), "only collection NFTs can be burned");
+        require!(payment_amount == NFT_AMOUNT, "invalid amount");
+
+        let caller = self.blockchain().get_caller();
+        let refund = self.burn_refund().get();
+        let refund_token = self.payment_token_id().get();
+        if refund > 0 {
+            require!(
+                self.get_rescuable_amount(refund_token.clone(), 0) >= refund,
+                "burn refund not funded"
+            );
+        }
+
+        self.send().esdt_local_burn(&payment_token, payment_nonce, &payment_amount);
+        self.audited_update(&self.burned_count(), self.audit_key(b"burned_count"), |v| *v += 1);
+        if !self.nonce_mint_id(payment_nonce).is_empty() {
+            self.forget_traits(self.nonce_mint_id(payment_nonce).get());
+        }
+        self.release_token(&caller, payment_nonce);
+
+        if refund > 0 {
+            self.send().direct(&caller, &refund_token, 0, &refund, &[]);
+        }
+
+        self.nft_burned_event(&caller, payment_nonce, &refund);
+    }
+
+    // burns the configured number of collection NFTs sent in one multi-transfer and
+    // mints the fused token under the first input's mint id
+    #[payable("*")]
+    #[endpoint(fuse)]
+    fn fuse(&self) -> u64 {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+
+        let input_count = self.fusion_input_count().get();
+        require!(input_count > 0, "fusion disabled");
+        let transfers = self.call_value().all_esdt_transfers();
+        require!(transfers.len() == input_count, "wrong number of NFTs");
+
+        let caller = self.blockchain().get_caller();
+        let nft_token_id = self.nft_token_id().get();
+        let mut burned_nonces = ManagedVec::new();
+        let mut level = 0u32;
+        for transfer in transfers.iter() {
+            require!(transfer.token_identifier == nft_token_id, "only collection NFTs can be fused");
+            require!(transfer.amount == NFT_AMOUNT, "invalid amount");
+
+            let mint_id = self.nonce_mint_id(transfer.token_nonce).get();
+            level = core::cmp::max(level, self.fusion_level(mint_id).get());
+            self.forget_traits(mint_id);
+            self.send().esdt_local_burn(&nft_token_id, transfer.token_nonce, &transfer.amount);
+            self.release_token(&caller, transfer.token_nonce);
+            burned_nonces.push(transfer.token_nonce);
+        }
+        level += 1;
+
+        // the first input's mint id moves to the fused token, the other ids stay burned
+        let first_nonce = burned_nonces.get(0);
+        let mint_id = self.nonce_mint_id(first_nonce).get();
+        self.audited_clear(&self.nonce_mint_id(first_nonce), self.audit_key_with(b"nonce_mint_id", &first_nonce));
+        self.audited_update(&self.mint_count(), self.audit_key(b"mint_count"), |v| *v -= 1);
+        self.audited_update(&self.burned_count(), self.audit_key(b"burned_count"), |v| {
+            *v += input_count as u32 - 1
+        });
+        self.audited_set(&self.fusion_level(mint_id), self.audit_key_with(b"fusion_level", &mint_id), &level);
+
+        let rarity_boost = self.get_rarity_boost(caller.clone()) + self.fusion_rarity_boost(level);
+        let fused_nonce = self._mint(&caller, mint_id, None, rarity_boost);
+        self.send().direct(&caller, &nft_token_id, fused_nonce, &BigUint::from(NFT_AMOUNT), &[]);
+
+        self.record_mint(&caller, fused_nonce, &BigUint::zero(), &self.payment_token_id().get());
+        self.attribute_token(&caller, fused_nonce);
+        self.audited_update(&self.fusion_count(), self.audit_key(b"fusion_count"), |v| *v += 1);
+        self.nft_fused_event(&caller, fused_nonce, level, &burned_nonces);
+
+        fused_nonce
+    }
+
+    // anyone can execute a proposal once its delay has passed
+    #[endpoint(executeAction)]
+    fn execute_action(&self, action_id: u32) -> SCResult<()> {
+        let pending_action = self.take_executable_action(action_id);
+        let value = pending_action.value;
+
+        match pending_action.action {
+            TimelockAction::Price => self.change_price(self.serializer().top_decode_from_managed_buffer(&value)),
+            TimelockAction::Royalties => {
+                self.change_royalties(self.serializer().top_decode_from_managed_buffer(&value))
+            },
+            TimelockAction::ImageBaseUri => self.change_image_base_uri(value),
+            TimelockAction::MetadataBaseUri => self.change_metadata_base_uri(value),
+            TimelockAction::TimelockDelay => self.apply_timelock_delay(&value),
+        }
+
+        Ok(())
+    }
+
+    // mints returned supply to the waitlist, oldest entry first
+    #[endpoint(settleWaitlist)]
+    fn settle_waitlist(&self, max_entries: usize) -> usize {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+
+        let nft_token_id = self.nft_token_id().get();
+        let mut settled = 0;
+        while settled < max_entries && self.has_unreserved_supply(1) {
+            let entry = match self.pop_waitlist_entry() {
+                Some(entry) => entry,
+                None => break,
+            };
+
+            let mint_id = self.take_next_sequential_mint_id();
+            let nft_nonce = self._mint(&entry.address, mint_id, None, self.get_rarity_boost(entry.address.clone()));
+            self.send().direct(
+                &entry.address,
+                &nft_token_id,
+                nft_nonce,
+                &BigUint::from(NFT_AMOUNT),
+                &[],
+            );
+
+            self.record_paid_mint(&entry.address, nft_nonce, mint_id, &entry.payment_token, &entry.amount);
+            settled += 1;
+        }
+
+        settled
+    }
+
+    // /// private
+
+    // the caller pays and gets the discount and refund, per-wallet rules apply to the recipient
+    fn mint_paid(
+        &self,
+        recipient: &ManagedAddress,
+        payment_token: TokenIdentifier,
+        payment_amount: BigUint,
+        preferred_ids: Option<ManagedVec<Self::Api, u32>>,
+        message: Option<ManagedBuffer>,
+        referrer: Option<ManagedAddress>,
+        coupon: Option<ManagedBuffer>,
+    ) {
+        self.require_feature_active(pause::Feature::Mint);
+        self.require_token_issued();
+        self.require_not_contract_caller();
+
+        require!(
+            payment_token == self.payment_token_id().get(),
+            "not given token identifier"
+        );
+        let caller = self.blockchain().get_caller();
+        let mut price = self.discounted_price(&caller, &self.require_public_sale(recipient, 1));
+        if referrer.is_some() {
+            price = self.referral_discounted_price(&price);
+        }
+        if let Some(code) = coupon {
+            price = self.coupon_discounted_price(&price, self.redeem_coupon(&code));
+        }
+        if preferred_ids.is_some() {
+            price += self.pick_premium().get();
+        }
+        let charged = self.charge_payment(&caller, &payment_token, &payment_amount, &price);
+        let revenue = match referrer {
+            Some(referrer) => &charged - &self.credit_referrer(&referrer, &payment_token, &charged),
+            None => charged,
+        };
+
+        self.mint_to_buyer(recipient, preferred_ids, message, &payment_token, &revenue);
+    }
+
+    // keeps exactly `price` and sends any surplus back to the buyer
+    fn charge_payment(
+        &self,
+        buyer: &ManagedAddress,
+        payment_token: &TokenIdentifier,
+        payment_amount: &BigUint,
+        price: &BigUint,
+    ) -> BigUint {
+        require!(payment_amount >= price, "not enough tokens");
+
+        let surplus = payment_amount - price;
+        if surplus > 0 {
+            self.send().direct(buyer, payment_token, 0, &surplus, &[]);
+            self.overpayment_refunded_event(buyer, payment_token, &surplus);
+        }
+
+        price.clone()
+    }
+
+    // supply checks, id allocation, delivery and bookkeeping shared by every paid mint path
+    fn mint_to_buyer(
+        &self,
+        buyer: &ManagedAddress,
+        preferred_ids: Option<ManagedVec<Self::Api, u32>>,
+        message: Option<ManagedBuffer>,
+        payment_token: &TokenIdentifier,
+        payment_amount: &BigUint,
+    ) -> u64 {
+        let nft_nonce = self.mint_for_buyer(buyer, preferred_ids, message, payment_token, payment_amount);
+        self.send().direct(
+            buyer,
+            &self.nft_token_id().get(),
+            nft_nonce,
+            &BigUint::from(NFT_AMOUNT),
+            &[],
+        );
+
+        nft_nonce
+    }
+
+    // same as `mint_to_buyer` but leaves the NFT in the contract for the caller to deliver
+    fn mint_for_buyer(
+        &self,
+        buyer: &ManagedAddress,
+        preferred_ids: Option<ManagedVec<Self::Api, u32>>,
+        message: Option<ManagedBuffer>,
+        payment_token: &TokenIdentifier,
+        payment_amount: &BigUint,
+    ) -> u64 {
+        self.require_not_paused();
+
+        let max_mints = self.max_mints_per_address().get();
+        let minted = self.mints_per_address(buyer).get();
+        require!(max_mints == 0 || minted < max_mints, "mint limit per address reached");
+        self.audited_set(
+            &self.mints_per_address(buyer),
+            self.audit_key_with(b"mints_per_address", buyer),
+            &(minted + 1),
+        );
+        self.throttle_block_mints();
+
+        if !self.use_reservation(buyer) {
+            require!(self.waitlist().is_empty(), "supply reserved for the waitlist");
+            require!(self.has_unreserved_supply(1), "remaining supply is reserved");
+        }
+        let mint_id = match &preferred_ids {
+            Some(preferred_ids) => self.take_preferred_mint_id(preferred_ids),
+            None => self.take_next_sequential_mint_id(),
+        };
+        let nft_nonce = self._mint(buyer, mint_id, message, self.get_rarity_boost(buyer.clone()));
+        self.record_paid_mint(buyer, nft_nonce, mint_id, payment_token, payment_amount);
+
+        nft_nonce
+    }
+
+    // history, stats, revenue, shares, holders and the receipt for a main collection
+    // token the buyer paid for, used by every paid mint path
+    fn record_paid_mint(
+        &self,
+        buyer: &ManagedAddress,
+        nft_nonce: u64,
+        mint_id: u32,
+        payment_token: &TokenIdentifier,
+        payment_amount: &BigUint,
+    ) {
+        self.record_mint(buyer, nft_nonce, payment_amount, payment_token);
+        self.mint_event(buyer, nft_nonce, mint_id, payment_amount);
+        let phase_id = self.get_active_phase_id();
+        self.record_mint_stats(phase_id, NFT_AMOUNT, payment_token, payment_amount);
+        self.record_revenue(revenue::RevenueSource::PrimaryMint, payment_token, payment_amount);
+        self.accrue_shares(payment_token, payment_amount);
+        self.attribute_token(buyer, nft_nonce);
+        self.issue_receipt(buyer, self.drop_id_of(mint_id), phase_id);
+    }
+
+    // `mint_id` must already be taken through the mint ids module, `rarity_boost`
+    // applies to the trait roll
+    fn _mint(&self, minter: &ManagedAddress, mint_id: u32, message: Option<ManagedBuffer>, rarity_boost: u32) -> u64 {
+        use alloc::string::ToString;
+
+        if let Some(message) = &message {
+            self.audited_set(&self.mint_message(mint_id), self.audit_key_with(b"mint_message", &mint_id), message);
+        }
+        self.roll_traits(mint_id, rarity_boost);
+        let attributes = self.build_attributes(mint_id);
+
+        // prefer the committed metadata hash, fall back to hashing the attributes
+        let hash_buffer = if !self.metadata_hash(mint_id).is_empty() {
+            self.metadata_hash(mint_id).get()
+        } else {
+            let attributes_hash = self
+                .crypto()
+                .sha256_legacy(attributes.to_boxed_bytes().as_slice());
+            ManagedBuffer::from(attributes_hash.as_bytes())
+        };
+
+        let mut name = ManagedBuffer::new();
+        name.append(&self.nft_token_name().get());
+        name.append(&ManagedBuffer::new_from_bytes(HASH_TAG));
+        name.append(&ManagedBuffer::new_from_bytes(mint_id.to_string().as_bytes()));
+
+        sc_print!("name: {:x}", name,);
+
+        let uris = self.resolve_uri_list(mint_id);
+
+        self.create_main_nft(
+            minter,
+            mint_id,
+            &name,
+            self.royalties_for_mint_id(mint_id),
+            &hash_buffer,
+            &attributes,
+            &uris,
+        )
+    }
+
+    // the only place tokens of the main collection are created, so the pause,
+    // `stopCreation` and the supply cap hold for every mint path
+    #[allow(clippy::too_many_arguments)]
+    fn create_main_nft(
+        &self,
+        minter: &ManagedAddress,
+        mint_id: u32,
+        name: &ManagedBuffer,
+        royalties: u32,
+        hash: &ManagedBuffer,
+        attributes: &ManagedBuffer,
+        uris: &ManagedVec<ManagedBuffer>,
+    ) -> u64 {
+        self.require_not_paused();
+        require!(!self.creation_stopped().get(), "creation stopped");
+        require!(!self.is_sold_out(), "sold out");
+
+        let nft_nonce = self.send().esdt_nft_create(
+            &self.nft_token_id().get(),
+            &BigUint::from(NFT_AMOUNT),
+            name,
+            &BigUint::from(royalties),
+            hash,
+            attributes,
+            uris,
+        );
+
+        self.audited_update(&self.mint_count(), self.audit_key(b"mint_count"), |v| *v += 1);
+        self.record_mint_id_nonce(nft_nonce, mint_id);
+        self.record_minter(minter, nft_nonce, mint_id);
+
+        nft_nonce
+    }
+
+    // marketplace convention `metadata:<cid>/<id>.json;tags:<tags>`, followed by the creation
+    // time as a decimal timestamp, e.g. `creatime:1650000000`; metadata and tags are left
+    // out until they are set
+    fn build_attributes(&self, mint_id: u32) -> ManagedBuffer {
+        use alloc::string::ToString;
+
+        let mut attributes = ManagedBuffer::new();
+
+        if !self.metadata_cid().is_empty() {
+            attributes.append(&ManagedBuffer::new_from_bytes(METADATA_KEY_NAME));
+            attributes.append(&self.metadata_cid().get());
+            attributes.append(&ManagedBuffer::new_from_bytes(URI_SLASH));
+            attributes.append(&ManagedBuffer::new_from_bytes(mint_id.to_string().as_bytes()));
+            attributes.append(&ManagedBuffer::new_from_bytes(METADATA_FILE_EXTENSION));
+            attributes.append(&ManagedBuffer::new_from_bytes(ATTRIBUTES_SEPARATOR));
+        }
+        if !self.tags().is_empty() {
+            attributes.append(&ManagedBuffer::new_from_bytes(TAGS_KEY_NAME));
+            attributes.append(&self.tags().get());
+            attributes.append(&ManagedBuffer::new_from_bytes(ATTRIBUTES_SEPARATOR));
+        }
+
+        let creation_time = self.blockchain().get_block_timestamp().to_string();
+        attributes.append(&ManagedBuffer::new_from_bytes(CREATION_TIME_KEY_NAME));
+        attributes.append(&ManagedBuffer::new_from_bytes(creation_time.as_bytes()));
+
+        if let Some(creator_hex) = self.creator_hex(mint_id) {
+            attributes.append(&ManagedBuffer::new_from_bytes(ATTRIBUTES_SEPARATOR));
+            attributes.append(&ManagedBuffer::new_from_bytes(CREATOR_KEY_NAME));
+            attributes.append(&creator_hex);
+        }
+        self.append_mint_message(&mut attributes, mint_id);
+        self.append_traits(&mut attributes, mint_id);
+        self.append_fusion_level(&mut attributes, mint_id);
+
+        attributes
+    }
+
+    // converts every price quoted in the old payment token, editions and collections
+    // priced in it move to the new token
+    fn convert_payment_prices(
+        &self,
+        old_token_id: &TokenIdentifier,
+        new_token_id: &TokenIdentifier,
+        rate_numerator: &BigUint,
+        rate_denominator: &BigUint,
+    ) {
+        let convert = |amount: &BigUint| amount * rate_numerator / rate_denominator;
+
+        for (mapper, key) in [
+            (self.pick_premium(), &b"pick_premium"[..]),
+            (self.reservation_deposit(), &b"reservation_deposit"[..]),
+            (self.presale_price(), &b"presale_price"[..]),
+            (self.dutch_auction_start_price(), &b"dutch_auction_start_price"[..]),
+            (self.dutch_auction_floor_price(), &b"dutch_auction_floor_price"[..]),
+            (self.dutch_auction_decay_step(), &b"dutch_auction_decay_step"[..]),
+            (self.burn_refund(), &b"burn_refund"[..]),
+            (self.mint_pass_price(), &b"mint_pass_price"[..]),
+            (self.raffle_price(), &b"raffle_price"[..]),
+        ] {
+            if !mapper.is_empty() {
+                self.audited_update(&mapper, self.audit_key(key), |v| *v = convert(v));
+            }
+        }
+
+        for phase_id in 1..=self.phase_count().get() {
+            self.audited_update(&self.sale_phases(phase_id), self.audit_key_with(b"sale_phases", &phase_id), |phase| {
+                phase.price = convert(&phase.price)
+            });
+        }
+        for index in 1..=self.price_tiers().len() {
+            let mut tier = self.price_tiers().get(index);
+            self.log_entry_removed(self.audit_key(b"price_tiers"), &tier);
+            tier.price = convert(&tier.price);
+            self.log_entry_added(self.audit_key(b"price_tiers"), &tier);
+            self.price_tiers().set(index, &tier);
+        }
+        for partner_id in 1..=self.partner_count().get() {
+            self.audited_update(&self.partners(partner_id), self.audit_key_with(b"partners", &partner_id), |partner| {
+                partner.price = convert(&partner.price)
+            });
+        }
+        for piece_id in self.available_pieces().iter() {
+            self.audited_update(&self.pieces(piece_id), self.audit_key_with(b"pieces", &piece_id), |piece| {
+                piece.price = convert(&piece.price)
+            });
+        }
+        for nonce in self.rental_nonces().iter() {
+            self.audited_update(&self.rentals(nonce), self.audit_key_with(b"rentals", &nonce), |rental| {
+                rental.price_per_day = convert(&rental.price_per_day)
+            });
+        }
+        for edition_id in 1..=self.edition_count().get() {
+            if &self.editions(edition_id).get().payment_token == old_token_id {
+                self.audited_update(&self.editions(edition_id), self.audit_key_with(b"editions", &edition_id), |edition| {
+                    edition.payment_token = new_token_id.clone();
+                    edition.price = convert(&edition.price);
+                });
+            }
+        }
+        for collection_id in 1..=self.collection_count().get() {
+            if &self.collections(collection_id).get().payment_token == old_token_id {
+                self.audited_update(
+                    &self.collections(collection_id),
+                    self.audit_key_with(b"collections", &collection_id),
+                    |collection| {
+                        collection.payment_token = new_token_id.clone();
+                        collection.price = convert(&collection.price);
+                    },
+                );
+            }
+        }
+    }
+
+    fn append_fusion_level(&self, attributes: &mut ManagedBuffer, mint_id: u32) {
+        use alloc::string::ToString;
+
+        let level = self.fusion_level(mint_id).get();
+        if level == 0 {
+            return;
+        }
+
+        attributes.append(&ManagedBuffer::new_from_bytes(ATTRIBUTES_SEPARATOR));
+        attributes.append(&ManagedBuffer::new_from_bytes(FUSION_KEY_NAME));
+        attributes.append(&ManagedBuffer::new_from_bytes(level.to_string().as_bytes()));
+    }
+
+    // rolled value ids in trait order, e.g. `traits:2,1,4`
+    fn append_traits(&self, attributes: &mut ManagedBuffer, mint_id: u32) {
+        use alloc::string::ToString;
+
+        let value_ids = self.token_traits(mint_id).get();
+        if value_ids.is_empty() {
+            return;
+        }
+
+        attributes.append(&ManagedBuffer::new_from_bytes(ATTRIBUTES_SEPARATOR));
+        attributes.append(&ManagedBuffer::new_from_bytes(TRAITS_KEY_NAME));
+        for (index, value_id) in value_ids.iter().enumerate() {
+            if index > 0 {
+                attributes.append(&ManagedBuffer::new_from_bytes(TRAIT_VALUES_SEPARATOR));
+            }
+            attributes.append(&ManagedBuffer::new_from_bytes(value_id.to_string().as_bytes()));
+        }
+    }
+
+    fn append_mint_message(&self, attributes: &mut ManagedBuffer, mint_id: u32) {
+        if self.mint_message(mint_id).is_empty() {
+            return;
+        }
+
+        attributes.append(&ManagedBuffer::new_from_bytes(ATTRIBUTES_SEPARATOR));
+        attributes.append(&ManagedBuffer::new_from_bytes(MESSAGE_KEY_NAME));
+        attributes.append(&self.mint_message(mint_id).get());
+    }
+
+    // printable ASCII letters, digits, spaces and a few punctuation marks only
+    fn require_valid_mint_message(&self, message: &ManagedBuffer) {
+        require!(!message.is_empty(), "message cannot be empty");
+        require!(message.len() <= MINT_MESSAGE_MAX_LENGTH, "message too long");
+
+        let bytes = message.to_boxed_bytes();
+        require!(
+            bytes
+                .as_slice()
+                .iter()
+                .all(|c| c.is_ascii_alphanumeric() || MINT_MESSAGE_PUNCTUATION.contains(c)),
+            "message contains invalid characters"
+        );
+    }
+
+    fn throttle_block_mints(&self) {
+        let max_mints = self.max_mints_per_block().get();
+        if max_mints == 0 {
+            return;
+        }
+
+        let block_nonce = self.blockchain().get_block_nonce();
+        let minted = self.mints_in_block(block_nonce).get();
+        require!(minted < max_mints, "block mint limit reached, retry in a later block");
+        self.audited_set(
+            &self.mints_in_block(block_nonce),
+            self.audit_key_with(b"mints_in_block", &block_nonce),
+            &(minted + 1),
+        );
+    }
+
+    // keeps minting bots from batching through proxy contracts
+    fn require_not_contract_caller(&self) {
+        if self.contract_callers_allowed().get() {
+            return;
+        }
+
+        let caller = self.blockchain().get_caller();
+        require!(!self.blockchain().is_smart_contract(&caller), "contracts cannot mint");
+    }
+
+    fn require_token_issued(&self) {
+        require!(!self.nft_token_id().is_empty(), "Token not issued");
+    }
+
+    // `nft_issue_pending` must already be set
+    fn issue_nft_call(&self, token_name: &ManagedBuffer, token_ticker: &ManagedBuffer) -> AsyncCall {
+        self.audited_set(&self.issue_status(), self.audit_key(b"issue_status"), &SetupStatus::Pending);
+
+        let payment_amount = self.call_value().egld_value();
+        self.send()
+            .esdt_system_sc_proxy()
+            .issue_non_fungible(
+                payment_amount,
+                token_name,
+                token_ticker,
+                NonFungibleTokenProperties {
+                    can_freeze: self.can_freeze().get(),
+                    can_wipe: self.can_wipe().get(),
+                    can_pause: false,
+                    can_change_owner: true,
+                    can_upgrade: false,
+                    can_add_special_roles: true,
+                },
+            )
+            .async_call()
+            .with_callback(NftManager::callbacks(self).issue_callback())
+    }
+
+    // every role this contract needs on its own collection
+    fn local_roles_call(&self) -> AsyncCall {
+        require!(!self.local_roles_pending().get(), "Local roles request already in progress");
+
+        self.audited_set(
+            &self.local_roles_pending(),
+            self.audit_key(b"local_roles_pending"),
+            &true,
+        );
+        self.audited_set(&self.roles_status(), self.audit_key(b"roles_status"), &SetupStatus::Pending);
+
+        let mut set_roles_call = self.send().esdt_system_sc_proxy().set_special_roles(
+            &self.blockchain().get_sc_address(),
+            &self.nft_token_id().get(),
+            core::iter::empty(),
+        );
+        for role in [NftRole::NftCreate, NftRole::NftBurn, NftRole::NftUpdateAttributes, NftRole::NftAddUri] {
+            set_roles_call.push_argument_raw_bytes(role.role_name());
+        }
+        if self.soulbound().get() {
+            set_roles_call.push_argument_raw_bytes(TRANSFER_ROLE_NAME);
+        }
+
+        set_roles_call
+            .async_call()
+            .with_callback(NftManager::callbacks(self).set_local_roles_callback())
+    }
+
+    fn esdt_system_sc_nft_call(&self, endpoint: &[u8], address: &ManagedAddress, nonce: u64) -> ContractCall<Self::Api, ()> {
+        let mut contract_call = ContractCall::new(
+            self.send().esdt_system_sc_proxy().esdt_system_sc_address(),
+            ManagedBuffer::new_from_bytes(endpoint),
+        );
+        contract_call.push_endpoint_arg(&self.nft_token_id().get());
+        contract_call.push_endpoint_arg(nonce);
+        contract_call.push_endpoint_arg(address);
+
+        contract_call
+    }
+
+    fn require_soulbound(&self) {
+        self.require_token_issued();
+        require!(self.soulbound().get(), "collection not soulbound");
+        require!(self.local_roles_set().get(), "local roles not set");
+    }
+
+    // set by `pauseMinting`; `_mint` checks it, entry points call it up front to fail
+    // before doing any other work
+    fn require_not_paused(&self) {
+        require!(!self.paused().get(), "minting paused");
+    }
+
+    fn require_sale_open(&self) {
+        self.require_not_paused();
+
+        let now = self.blockchain().get_block_timestamp();
+        let start = self.sale_start_timestamp().get();
+        let end = self.sale_end_timestamp().get();
+
+        require!(now >= start, "sale not started");
+        require!(end == 0 || now < end, "sale ended");
+    }
+
+    // phase rules when phases are configured, the plain sale window otherwise;
+    // returns the undiscounted price of the next `amount` mints
+    fn require_public_sale(&self, buyer: &ManagedAddress, amount: u32) -> BigUint {
+        if self.phases_configured() {
+            return self.apply_phase_rules(buyer, amount) * amount;
+        }
+
+        self.require_sale_open();
+        if self.dutch_auction_enabled().get() {
+            return self.dutch_auction_price() * amount;
+        }
+
+        // a batch can straddle a tier boundary
+        let next_mint = self.mint_count().get() + 1;
+        let mut price = BigUint::zero();
+        for mint_number in next_mint..next_mint + amount {
+            price += self.price_for_mint_number(mint_number);
+        }
+
+        price
+    }
+
+    // the price `require_public_sale` resolves, without the sale window or the phase
+    // rules: the active phase's price, otherwise the Dutch auction or tier price
+    fn allowlist_unit_price(&self) -> BigUint {
+        if self.phases_configured() {
+            let phase_id = self.get_active_phase_id();
+            if phase_id != 0 {
+                return self.sale_phases(phase_id).get().price;
+            }
+        }
+
+        self.get_current_price()
+    }
+
+    fn change_royalties(&self, royalties: u32) {
+        require!(royalties <= ROYALTIES_MAX, "royalties cannot exceed 100%");
+        require!(
+            royalties <= self.max_royalties().get(),
+            "royalties cannot exceed the committed maximum"
+        );
+        require!(
+            !self.sale_started() || royalties <= self.royalties().get(),
+            "royalties can only decrease after sale start"
+        );
+
+        let old_royalties = self.royalties().get();
+        self.audited_set(&self.royalties(), self.audit_key(b"royalties"), &royalties);
+        self.royalties_changed_event(old_royalties, royalties);
+    }
+
+    fn change_price(&self, price: BigUint) {
+        let old_price = self.nft_token_price().get();
+        self.audited_set(&self.nft_token_price(), self.audit_key(b"nft_token_price"), &price);
+        self.price_changed_event(&old_price, &price);
+    }
+
+    fn change_image_base_uri(&self, image_base_uri: ManagedBuffer) {
+        self.audited_set(&self.image_base_uri(), self.audit_key(b"image_base_uri"), &image_base_uri);
+        self.image_base_uri_changed_event(&image_base_uri);
+    }
+
+    fn change_metadata_base_uri(&self, metadata_base_uri: ManagedBuffer) {
+        self.audited_set(&self.metadata_base_uri(), self.audit_key(b"metadata_base_uri"), &metadata_base_uri);
+        self.metadata_base_uri_changed_event(&metadata_base_uri);
+    }
+
+
+    // views
+
+    #[view(getFullConfig)]
+    fn get_full_config(&self) -> FullConfig<Self::Api> {
+        FullConfig {
+            nft_token_id: self.nft_token_id().get(),
+            nft_token_name: self.nft_token_name().get(),
+            payment_token_id: self.payment_token_id().get(),
+            nft_token_price: self.nft_token_price().get(),
+            royalties: self.royalties().get(),
+            max_royalties: self.max_royalties().get(),
+            image_base_uri: self.image_base_uri().get(),
+            metadata_base_uri: self.metadata_base_uri().get(),
+            metadata_cid: self.metadata_cid().get(),
+            tags: self.tags().get(),
+            paused: self.paused().get(),
+            max_supply: self.max_supply().get(),
+            sale_start_timestamp: self.sale_start_timestamp().get(),
+            sale_end_timestamp: self.sale_end_timestamp().get(),
+            edition_token_id: self.edition_token_id().get(),
+        }
+    }
+
+    // deployments older than the status tracking only know issued or not
+    #[view(getIssueStatus)]
+    fn get_issue_status(&self) -> SetupStatus {
+        if self.issue_status().is_empty() && !self.nft_token_id().is_empty() {
+            return SetupStatus::Done;
+        }
+
+        self.issue_status().get()
+    }
+
+    #[view(getRolesStatus)]
+    fn get_roles_status(&self) -> SetupStatus {
+        if self.roles_status().is_empty() && self.local_roles_set().get() {
+            return SetupStatus::Done;
+        }
+
+        self.roles_status().get()
+    }
+
+    #[view(getTransferRoleAddresses)]
+    fn get_transfer_role_addresses(&self) -> MultiResultVec<ManagedAddress> {
+        self.transfer_role_addresses()
+            .iter()
+            .collect::<Vec<ManagedAddress>>()
+            .into()
+    }
+
+    // what `stopCreation` still has to revoke before this contract's own create role
+    #[view(getCreateRoleAddresses)]
+    fn get_create_role_addresses(&self) -> MultiResultVec<ManagedAddress> {
+        self.create_role_addresses()
+            .iter()
+            .collect::<Vec<ManagedAddress>>()
+            .into()
+    }
+
+    // everything the mint page shows, in one query
+    #[view(getSaleStatus)]
+    fn get_sale_status(&self) -> SaleStatus<Self::Api> {
+        let phase_id = self.get_active_phase_id();
+        let price = if phase_id != 0 {
+            self.sale_phases(phase_id).get().price
+        } else {
+            self.get_current_price()
+        };
+
+        SaleStatus {
+            token_id: self.nft_token_id().get(),
+            price,
+            payment_token: self.payment_token_id().get(),
+            minted: self.mint_count().get(),
+            max_supply: self.max_supply().get(),
+            paused: self.paused().get(),
+            sale_start: self.sale_start_timestamp().get(),
+            sale_end: self.sale_end_timestamp().get(),
+            current_phase: self.get_current_phase(),
+        }
+    }
+
+    // the top-encoded FullConfig, see the export module
+    #[view(exportConfig)]
+    fn export_config(&self, encoding: ExportEncoding) -> ManagedBuffer {
+        let config = self.serializer().top_encode_to_managed_buffer(&self.get_full_config());
+
+        self.encode_export(&config, encoding)
+    }
+
+    #[view(verifyMetadata)]
+    fn verify_metadata(&self, mint_id: u32, content_hash: ManagedBuffer) -> bool {
+        let committed = self.metadata_hash(mint_id);
+
+        !committed.is_empty() && committed.get() == content_hash
+    }
+
+    // e.g. "3.00" for 300 basis points
+    #[view(getRoyaltiesPercent)]
+    fn get_royalties_percent(&self) -> ManagedBuffer {
+        use alloc::string::ToString;
+
+        let royalties = self.royalties().get();
+        let cents = royalties % 100;
+
+        let mut percent = ManagedBuffer::new_from_bytes((royalties / 100).to_string().as_bytes());
+        percent.append(&ManagedBuffer::new_from_bytes(DECIMAL_POINT));
+        if cents < 10 {
+            percent.append(&ManagedBuffer::new_from_bytes(b"0"));
+        }
+        percent.append(&ManagedBuffer::new_from_bytes(cents.to_string().as_bytes()));
+
+        percent
+    }
+
+    #[view(getSaleState)]
+    fn get_sale_state(&self) -> SaleState {
+        if self.nft_token_id().is_empty() {
+            return SaleState::NotStarted;
+        }
+        if self.is_sold_out() {
+            return SaleState::SoldOut;
+        }
+
+        let now = self.blockchain().get_block_timestamp();
+        let end = self.sale_end_timestamp().get();
+        if end != 0 && now >= end {
+            return SaleState::Ended;
+        }
+        if self.paused().get() {
+            return SaleState::Paused;
+        }
+        if now < self.sale_start_timestamp().get() {
+            return SaleState::NotStarted;
+        }
+
+        SaleState::Active
+    }
+
+    #[view(isSoldOut)]
+    fn is_sold_out(&self) -> bool {
+        let max_supply = self.max_supply().get();
+
+        max_supply != 0 && self.mint_count().get() >= max_supply
+    }
+
+    #[view(getMaxMintsPerTx)]
+    fn get_max_mints_per_tx(&self) -> u32 {
+        let max_mints = self.max_mints_per_tx().get();
+        if max_mints == 0 {
+            return DEFAULT_MAX_MINTS_PER_TX;
+        }
+
+        max_mints
+    }
+
+    // u32::MAX while the supply is unlimited
+    #[view(getRemainingSupply)]
+    fn get_remaining_supply(&self) -> u32 {
+        let max_supply = self.max_supply().get();
+        if max_supply == 0 {
+            return u32::MAX;
+        }
+
+        max_supply.saturating_sub(self.mint_count().get())
+    }
+
+    // final assets of a token, also for tokens minted with placeholder URIs
+    #[view(getRevealedUri)]
+    fn get_revealed_uri(&self, nonce: u64) -> uri_resolver::TokenUris<Self::Api> {
+        require!(self.revealed().get(), "collection not revealed");
+        let mint_id = self.nonce_mint_id(nonce).get();
+        require!(mint_id != 0, "unknown nonce");
+
+        self.resolve_token_uris(mint_id)
+    }
+
+    // (trait name, value name) pairs rolled for the token
+    #[view(getAttributes)]
+    fn get_attributes(&self, nonce: u64) -> MultiResultVec<MultiResult2<ManagedBuffer, ManagedBuffer>> {
+        let mint_id = self.nonce_mint_id(nonce).get();
+        require!(mint_id != 0, "unknown nonce");
+
+        self.decode_traits(mint_id)
+    }
+
+    // block timestamp of the mint, the same value the `creatime` attribute holds
+    #[view(getCreationTime)]
+    fn get_creation_time(&self, nonce: u64) -> u64 {
+        require!(!self.creation_time(nonce).is_empty(), "unknown nonce");
+
+        self.creation_time(nonce).get()
+    }
+
+    // reads `creatime` from a token's attributes, in the decimal format or the
+    // 8 raw little-endian bytes older tokens were minted with
+    #[view(decodeCreationTime)]
+    fn decode_creation_time(&self, attributes: ManagedBuffer) -> u64 {
+        let attributes = attributes.to_boxed_bytes();
+        let bytes = attributes.as_slice();
+        let key_start = bytes
+            .windows(CREATION_TIME_KEY_NAME.len())
+            .position(|window| window == CREATION_TIME_KEY_NAME);
+        require!(key_start.is_some(), "no creation time in attributes");
+
+        let value = &bytes[key_start.unwrap() + CREATION_TIME_KEY_NAME.len()..];
+        let digit_count = value.iter().take_while(|byte| byte.is_ascii_digit()).count();
+        if digit_count > 0 && (digit_count == value.len() || value[digit_count] == ATTRIBUTES_SEPARATOR[0]) {
+            return value[..digit_count]
+                .iter()
+                .fold(0u64, |timestamp, digit| timestamp * 10 + (digit - b'0') as u64);
+        }
+
+        require!(value.len() >= 8, "invalid creation time");
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(&value[..8]);
+
+        u64::from_le_bytes(raw)
+    }
+
+    // undiscounted price of a public mint outside sale phases
+    #[view(getCurrentPrice)]
+    fn get_current_price(&self) -> BigUint {
+        if self.dutch_auction_enabled().get() {
+            return self.dutch_auction_price();
+        }
+
+        self.price_for_mint_number(self.mint_count().get() + 1)
+    }
+
+    // undiscounted price the next public mint pays, including the active phase's price
+    #[view(getPriceForNextMint)]
+    fn get_price_for_next_mint(&self) -> BigUint {
+        if self.phases_configured() {
+            let phase_id = self.get_active_phase_id();
+            require!(phase_id != 0, "no active sale phase");
+            return self.sale_phases(phase_id).get().price;
+        }
+
+        self.get_current_price()
+    }
+
+    // (start, end), 0 leaves that side open
+    #[view(getSaleWindow)]
+    fn get_sale_window(&self) -> MultiResult2<u64, u64> {
+        (self.sale_start_timestamp().get(), self.sale_end_timestamp().get()).into()
+    }
+
+    #[view(getSecondsUntilSaleStart)]
+    fn get_seconds_until_sale_start(&self) -> u64 {
+        let now = self.blockchain().get_block_timestamp();
+
+        self.sale_start_timestamp().get().saturating_sub(now)
+    }
+
+    // 0 once the sale has ended, or when no end is configured
+    #[view(getSecondsUntilSaleEnd)]
+    fn get_seconds_until_sale_end(&self) -> u64 {
+        let now = self.blockchain().get_block_timestamp();
+
+        self.sale_end_timestamp().get().saturating_sub(now)
+    }
+
+    // events
+
+    #[event("mint")]
+    fn mint_event(
+        &self,
+        #[indexed] minter: &ManagedAddress,
+        #[indexed] nonce: u64,
+        #[indexed] mint_id: u32,
+        price: &BigUint,
+    );
+
+    #[event("issue")]
+    fn issue_event(&self, #[indexed] token_id: &TokenIdentifier);
+
+    #[event("create_role_transferred")]
+    fn create_role_transferred_event(&self, #[indexed] new_creator: &ManagedAddress);
+
+    #[event("collection_ownership_transferred")]
+    fn collection_ownership_transferred_event(&self, #[indexed] new_owner: &ManagedAddress);
+
+    #[event("special_roles_set")]
+    fn special_roles_set_event(&self, #[indexed] target: &ManagedAddress);
+
+    #[event("creation_stopped")]
+    fn creation_stopped_event(&self, #[indexed] final_supply: u32);
+
+    #[event("setup_failed")]
+    fn setup_failed_event(&self, #[indexed] step: &[u8], error: &ManagedBuffer);
+
+    #[event("compliance")]
+    fn compliance_event(&self, #[indexed] action: ComplianceAction, #[indexed] address: &ManagedAddress, #[indexed] nonce: u64);
+
+    #[event("withdraw")]
+    fn withdraw_event(&self, #[indexed] token_id: &TokenIdentifier, amount: &BigUint);
+
+    #[event("pause")]
+    fn pause_event(&self, #[indexed] paused: bool);
+
+    #[event("contract_migrated")]
+    fn contract_migrated_event(&self, #[indexed] old_version: u32, #[indexed] new_version: u32);
+
+    #[event("overpayment_refunded")]
+    fn overpayment_refunded_event(
+        &self,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] token_id: &TokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("price_changed")]
+    fn price_changed_event(&self, #[indexed] old_price: &BigUint, #[indexed] new_price: &BigUint);
+
+    #[event("royalties_changed")]
+    fn royalties_changed_event(&self, #[indexed] old_royalties: u32, #[indexed] new_royalties: u32);
+
+    #[event("image_base_uri_changed")]
+    fn image_base_uri_changed_event(&self, image_base_uri: &ManagedBuffer);
+
+    #[event("metadata_base_uri_changed")]
+    fn metadata_base_uri_changed_event(&self, metadata_base_uri: &ManagedBuffer);
+
+    #[event("nft_burned")]
+    fn nft_burned_event(&self, #[indexed] holder: &ManagedAddress, #[indexed] nonce: u64, refund: &BigUint);
+
+    #[event("payment_token_migrated")]
+    fn payment_token_migrated_event(
+        &self,
+        #[indexed] old_token_id: &TokenIdentifier,
+        #[indexed] new_token_id: &TokenIdentifier,
+        migration: &PaymentTokenMigration<Self::Api>,
+    );
+
+    // callbacks
+
+    #[callback]
+    fn issue_callback(&self, #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>) {
+        self.audited_clear(&self.nft_issue_pending(), self.audit_key(b"nft_issue_pending"));
+
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.audited_set(&self.nft_token_id(), self.audit_key(b"nft_token_id"), &token_id);
+                self.audited_set(&self.issue_status(), self.audit_key(b"issue_status"), &SetupStatus::Done);
+                self.issue_event(&token_id);
+            },
+            ManagedAsyncCallResult::Err(err) => {
+                self.audited_set(&self.issue_status(), self.audit_key(b"issue_status"), &SetupStatus::Failed);
+                self.setup_failed_event(b"issue", &err.err_msg);
+
+                let caller = self.blockchain().get_owner_address();
+                let (returned_tokens, token_id) = self.call_value().payment_token_pair();
+                if token_id.is_egld() && returned_tokens > 0 {
+                    self.send()
+                        .direct(&caller, &token_id, 0, &returned_tokens, &[]);
+                }
+            },
+        }
+    }
+
+    #[callback]
+    fn set_local_roles_callback(&self, #[call_result] result: ManagedAsyncCallResult<()>) {
+        self.audited_clear(&self.local_roles_pending(), self.audit_key(b"local_roles_pending"));
+
+        match result {
+            ManagedAsyncCallResult::Ok(()) => {
+                self.audited_set(&self.local_roles_set(), self.audit_key(b"local_roles_set"), &true);
+                self.audited_set(&self.roles_status(), self.audit_key(b"roles_status"), &SetupStatus::Done);
+                if self.soulbound().get() {
+                    let sc_address = self.blockchain().get_sc_address();
+                    self.transfer_role_addresses().insert(sc_address.clone());
+                    self.log_entry_added(self.audit_key(b"transfer_role_addresses"), &sc_address);
+                }
+            },
+            ManagedAsyncCallResult::Err(err) => {
+                self.audited_set(&self.roles_status(), self.audit_key(b"roles_status"), &SetupStatus::Failed);
+                self.setup_failed_event(b"roles", &err.err_msg);
+            },
+        }
+    }
+
+    #[callback]
+    fn special_roles_callback(
+        &self,
+        target: ManagedAddress,
+        grants_create: bool,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        if let ManagedAsyncCallResult::Ok(()) = result {
+            // this contract's own create role is revoked last by `stopCreation`
+            if grants_create
+                && target != self.blockchain().get_sc_address()
+                && self.create_role_addresses().insert(target.clone())
+            {
+                self.log_entry_added(self.audit_key(b"create_role_addresses"), &target);
+            }
+            self.special_roles_set_event(&target);
+        }
+    }
+
+    #[callback]
+    fn stop_creation_callback(&self, address: ManagedAddress, #[call_result] result: ManagedAsyncCallResult<()>) {
+        if let ManagedAsyncCallResult::Ok(()) = result {
+            if address != self.blockchain().get_sc_address() {
+                self.create_role_addresses().swap_remove(&address);
+                self.log_entry_removed(self.audit_key(b"create_role_addresses"), &address);
+                return;
+            }
+
+            self.audited_set(&self.creation_stopped(), self.audit_key(b"creation_stopped"), &true);
+            self.creation_stopped_event(self.mint_count().get());
+        }
+    }
+
+    #[callback]
+    fn compliance_callback(
+        &self,
+        action: ComplianceAction,
+        address: ManagedAddress,
+        nonce: u64,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        if let ManagedAsyncCallResult::Ok(()) = result {
+            if action == ComplianceAction::Wipe {
+                self.release_token(&address, nonce);
+            }
+            self.compliance_event(action, &address, nonce);
+        }
+    }
+
+    #[callback]
+    fn create_role_transfer_callback(&self, new_creator: ManagedAddress, #[call_result] result: ManagedAsyncCallResult<()>) {
+        if let ManagedAsyncCallResult::Ok(()) = result {
+            self.audited_set(&self.paused(), self.audit_key(b"paused"), &true);
+            self.pause_event(true);
+            self.create_role_transferred_event(&new_creator);
+        }
+    }
+
+    #[callback]
+    fn ownership_transfer_callback(&self, new_owner: ManagedAddress, #[call_result] result: ManagedAsyncCallResult<()>) {
+        if let ManagedAsyncCallResult::Ok(()) = result {
+            self.audited_set(&self.collection_new_owner(), self.audit_key(b"collection_new_owner"), &new_owner);
+            self.collection_ownership_transferred_event(&new_owner);
+        }
+    }
+
+    #[callback]
+    fn transfer_role_callback(
+        &self,
+        address: ManagedAddress,
+        granted: bool,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        if let ManagedAsyncCallResult::Ok(()) = result {
+            if granted {
+                self.transfer_role_addresses().insert(address.clone());
+                self.log_entry_added(self.audit_key(b"transfer_role_addresses"), &address);
+            } else {
+                self.transfer_role_addresses().swap_remove(&address);
+                self.log_entry_removed(self.audit_key(b"transfer_role_addresses"), &address);
+            }
+        }
+    }
 }
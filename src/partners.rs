@@ -0,0 +1,158 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Partner<M: ManagedTypeApi> {
+    pub collection_token_id: TokenIdentifier<M>,
+    pub quota: u32,
+    pub claimed: u32,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    // in the payment token, 0 for a free claim
+    pub price: BigUint<M>,
+}
+
+// Cross-promotion with partner communities. Each partner gets a quota of free or
+// discounted mints for a time window. Members prove membership either by sending one
+// of the partner collection's tokens along with the claim, which is returned in the
+// same transaction, or with a voucher signed by the backend signer. Every address
+// claims once per partner, and each partner token can only back one claim.
+#[elrond_wasm::module]
+pub trait PartnersModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::signatures::SignaturesModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(registerPartner)]
+    fn register_partner(
+        &self,
+        collection_token_id: TokenIdentifier,
+        quota: u32,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        price: BigUint,
+    ) -> u32 {
+        self.require_role(Role::Admin);
+
+        require!(collection_token_id.is_valid_esdt_identifier(), "invalid token identifier provided");
+        require!(quota > 0, "quota must be positive");
+        require!(end_timestamp > start_timestamp, "claim end must be after claim start");
+
+        let partner_id = self.partner_count().get() + 1;
+        self.audited_set(
+            &self.partners(partner_id),
+            self.audit_key_with(b"partners", &partner_id),
+            &Partner {
+                collection_token_id,
+                quota,
+                claimed: 0,
+                start_timestamp,
+                end_timestamp,
+                price,
+            },
+        );
+        self.audited_set(&self.partner_count(), self.audit_key(b"partner_count"), &partner_id);
+
+        partner_id
+    }
+
+    // can not go below what was already claimed
+    #[endpoint(setPartnerQuota)]
+    fn set_partner_quota(&self, partner_id: u32, quota: u32) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        require!(!self.partners(partner_id).is_empty(), "partner does not exist");
+
+        let mut partner = self.partners(partner_id).get();
+        require!(quota >= partner.claimed, "quota below claimed amount");
+
+        partner.quota = quota;
+        self.audited_set(&self.partners(partner_id), self.audit_key_with(b"partners", &partner_id), &partner);
+
+        Ok(())
+    }
+
+    // private
+
+    // books the claim and returns the price the member has to pay
+    fn consume_partner_claim(&self, partner_id: u32, member: &ManagedAddress) -> BigUint {
+        require!(!self.partners(partner_id).is_empty(), "partner does not exist");
+
+        let mut partner = self.partners(partner_id).get();
+        let now = self.blockchain().get_block_timestamp();
+        require!(now >= partner.start_timestamp, "partner claims not started");
+        require!(now < partner.end_timestamp, "partner claims ended");
+        require!(partner.claimed < partner.quota, "partner quota exhausted");
+        require!(!self.partner_claimed(partner_id, member).get(), "already claimed for this partner");
+
+        partner.claimed += 1;
+        self.audited_set(&self.partners(partner_id), self.audit_key_with(b"partners", &partner_id), &partner);
+        self.audited_set(
+            &self.partner_claimed(partner_id, member),
+            self.audit_key_with(b"partner_claimed", &(partner_id, member)),
+            &true,
+        );
+
+        partner.price
+    }
+
+    // the membership token must not have backed a claim for this partner before
+    fn use_partner_token(&self, partner_id: u32, token_id: &TokenIdentifier, nonce: u64) {
+        let partner = self.partners(partner_id).get();
+        require!(*token_id == partner.collection_token_id, "not a partner collection token");
+        require!(!self.partner_token_used(partner_id, nonce).get(), "partner token already used");
+
+        self.audited_set(
+            &self.partner_token_used(partner_id, nonce),
+            self.audit_key_with(b"partner_token_used", &(partner_id, nonce)),
+            &true,
+        );
+    }
+
+    fn verify_partner_voucher(
+        &self,
+        partner_id: u32,
+        member: &ManagedAddress,
+        signature_nonce: u64,
+        expiry: u64,
+        signature: &ManagedBuffer,
+    ) {
+        let mut payload = ManagedBuffer::new();
+        payload.append_u32_be(partner_id);
+
+        self.verify_signature(b"partner_claim", member, signature_nonce, expiry, &payload, signature);
+    }
+
+    // views
+
+    #[view(getPartnerRemainingQuota)]
+    fn get_partner_remaining_quota(&self, partner_id: u32) -> u32 {
+        if self.partners(partner_id).is_empty() {
+            return 0;
+        }
+
+        let partner = self.partners(partner_id).get();
+        partner.quota - partner.claimed
+    }
+
+    // storage
+
+    #[view(getPartnerCount)]
+    #[storage_mapper("partner_count")]
+    fn partner_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getPartner)]
+    #[storage_mapper("partners")]
+    fn partners(&self, partner_id: u32) -> SingleValueMapper<Partner<Self::Api>>;
+
+    #[view(hasClaimedForPartner)]
+    #[storage_mapper("partner_claimed")]
+    fn partner_claimed(&self, partner_id: u32, address: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[view(isPartnerTokenUsed)]
+    #[storage_mapper("partner_token_used")]
+    fn partner_token_used(&self, partner_id: u32, nonce: u64) -> SingleValueMapper<bool>;
+}
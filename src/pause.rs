@@ -0,0 +1,65 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum Feature {
+    Mint,
+    Claims,
+    Marketplace,
+    Staking,
+    Breeding,
+}
+
+// Per-subsystem pause switches, so an incident in one feature does not
+// require halting the whole contract.
+#[elrond_wasm::module]
+pub trait PauseModule: crate::access_control::AccessControlModule + crate::audit::AuditModule {
+    // endpoints - role-gated
+
+    #[endpoint(pauseFeature)]
+    fn pause_feature(&self, feature: Feature) -> SCResult<()> {
+        self.require_role(Role::Operator);
+
+        if self.paused_features().insert(feature) {
+            self.log_entry_added(self.audit_key(b"paused_features"), &feature);
+        }
+
+        Ok(())
+    }
+
+    #[endpoint(unpauseFeature)]
+    fn unpause_feature(&self, feature: Feature) -> SCResult<()> {
+        self.require_role(Role::Operator);
+
+        if self.paused_features().remove(&feature) {
+            self.log_entry_removed(self.audit_key(b"paused_features"), &feature);
+        }
+
+        Ok(())
+    }
+
+    // private
+
+    fn require_feature_active(&self, feature: Feature) {
+        require!(!self.paused_features().contains(&feature), "feature is paused");
+    }
+
+    // views
+
+    #[view(isFeaturePaused)]
+    fn is_feature_paused(&self, feature: Feature) -> bool {
+        self.paused_features().contains(&feature)
+    }
+
+    #[view(getPausedFeatures)]
+    fn get_paused_features(&self) -> MultiResultVec<Feature> {
+        self.paused_features().iter().collect::<Vec<Feature>>().into()
+    }
+
+    // storage
+
+    #[storage_mapper("paused_features")]
+    fn paused_features(&self) -> SetMapper<Feature>;
+}
@@ -0,0 +1,135 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct PayoutSchedule<M: ManagedTypeApi> {
+    pub recipient: ManagedAddress<M>,
+    pub token_id: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub interval_seconds: u64,
+    pub next_payout_timestamp: u64,
+}
+
+// Recurring payouts (contributor salaries, charity commitments, ...) paid out of the
+// unprotected sale proceeds. Anyone can trigger due payouts; a schedule that misses
+// periods catches up in one go, and one the balance cannot cover stays due and is
+// retried on the next trigger.
+#[elrond_wasm::module]
+pub trait PayoutsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(addPayoutSchedule)]
+    fn add_payout_schedule(
+        &self,
+        recipient: ManagedAddress,
+        token_id: TokenIdentifier,
+        amount: BigUint,
+        interval_seconds: u64,
+        first_payout_timestamp: u64,
+    ) -> u32 {
+        self.require_role(Role::Treasurer);
+
+        require!(!recipient.is_zero(), "invalid recipient");
+        require!(
+            token_id.is_egld() || token_id.is_valid_esdt_identifier(),
+            "invalid token identifier provided"
+        );
+        require!(amount > 0, "amount must be positive");
+        require!(interval_seconds > 0, "interval must be positive");
+
+        let schedule_id = self.payout_schedule_count().get() + 1;
+        self.audited_set(
+            &self.payout_schedules(schedule_id),
+            self.audit_key_with(b"payout_schedules", &schedule_id),
+            &PayoutSchedule {
+                recipient,
+                token_id,
+                amount,
+                interval_seconds,
+                next_payout_timestamp: first_payout_timestamp,
+            },
+        );
+        self.audited_set(&self.payout_schedule_count(), self.audit_key(b"payout_schedule_count"), &schedule_id);
+        self.active_payout_schedules().insert(schedule_id);
+        self.log_entry_added(self.audit_key(b"active_payout_schedules"), &schedule_id);
+
+        schedule_id
+    }
+
+    #[endpoint(cancelPayoutSchedule)]
+    fn cancel_payout_schedule(&self, schedule_id: u32) -> SCResult<()> {
+        self.require_role(Role::Treasurer);
+
+        require!(
+            self.active_payout_schedules().swap_remove(&schedule_id),
+            "payout schedule not active"
+        );
+        self.log_entry_removed(self.audit_key(b"active_payout_schedules"), &schedule_id);
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    // pays every due schedule the balance can cover, returns how many were paid
+    #[endpoint(triggerPayouts)]
+    fn trigger_payouts(&self) -> usize {
+        let now = self.blockchain().get_block_timestamp();
+        let schedule_ids: Vec<u32> = self.active_payout_schedules().iter().collect();
+
+        let mut paid = 0;
+        for schedule_id in schedule_ids {
+            let mut schedule = self.payout_schedules(schedule_id).get();
+            if now < schedule.next_payout_timestamp {
+                continue;
+            }
+
+            let periods = (now - schedule.next_payout_timestamp) / schedule.interval_seconds + 1;
+            let amount = &schedule.amount * periods;
+            if amount > self.get_rescuable_amount(schedule.token_id.clone(), 0) {
+                continue;
+            }
+
+            schedule.next_payout_timestamp += periods * schedule.interval_seconds;
+            self.audited_set(
+                &self.payout_schedules(schedule_id),
+                self.audit_key_with(b"payout_schedules", &schedule_id),
+                &schedule,
+            );
+            self.send().direct(&schedule.recipient, &schedule.token_id, 0, &amount, &[]);
+            paid += 1;
+        }
+
+        paid
+    }
+
+    // views
+
+    #[view(getActivePayoutSchedules)]
+    fn get_active_payout_schedules(&self) -> MultiResultVec<MultiResult2<u32, PayoutSchedule<Self::Api>>> {
+        self.active_payout_schedules()
+            .iter()
+            .map(|schedule_id| (schedule_id, self.payout_schedules(schedule_id).get()).into())
+            .collect::<Vec<MultiResult2<u32, PayoutSchedule<Self::Api>>>>()
+            .into()
+    }
+
+    // storage
+
+    #[view(getPayoutScheduleCount)]
+    #[storage_mapper("payout_schedule_count")]
+    fn payout_schedule_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getPayoutSchedule)]
+    #[storage_mapper("payout_schedules")]
+    fn payout_schedules(&self, schedule_id: u32) -> SingleValueMapper<PayoutSchedule<Self::Api>>;
+
+    #[storage_mapper("active_payout_schedules")]
+    fn active_payout_schedules(&self) -> UnorderedSetMapper<u32>;
+}
@@ -0,0 +1,155 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::states::SalePhase;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct PhaseConfig<M: ManagedTypeApi> {
+    pub phase: SalePhase,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub price: BigUint<M>,
+    // 0 means unlimited
+    pub wallet_limit: u32,
+    // only the whitelist and allowlist mints are accepted
+    pub whitelist_required: bool,
+}
+
+// Scheduled sale phases. Once any phase is configured, public mints follow the
+// phase active at the block timestamp (price, per-wallet limit, whitelist) instead
+// of the plain sale window; between phases nothing can be minted. Phase ids start
+// at 1, id 0 stands for "no phase" in stats and receipts.
+#[elrond_wasm::module]
+pub trait PhasesModule:
+    crate::access_control::AccessControlModule + crate::audit::AuditModule + crate::timelock::TimelockModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(addSalePhase)]
+    fn add_sale_phase(
+        &self,
+        phase: SalePhase,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        price: BigUint,
+        wallet_limit: u32,
+        whitelist_required: bool,
+    ) -> u32 {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        require!(phase != SalePhase::None, "invalid sale phase");
+        require!(start_timestamp < end_timestamp, "invalid phase window");
+        for phase_id in 1..=self.phase_count().get() {
+            let existing = self.sale_phases(phase_id).get();
+            require!(
+                end_timestamp <= existing.start_timestamp || start_timestamp >= existing.end_timestamp,
+                "phase overlaps an existing phase"
+            );
+        }
+
+        let phase_id = self.phase_count().get() + 1;
+        self.audited_set(
+            &self.sale_phases(phase_id),
+            self.audit_key_with(b"sale_phases", &phase_id),
+            &PhaseConfig {
+                phase,
+                start_timestamp,
+                end_timestamp,
+                price,
+                wallet_limit,
+                whitelist_required,
+            },
+        );
+        self.audited_set(&self.phase_count(), self.audit_key(b"phase_count"), &phase_id);
+
+        phase_id
+    }
+
+    // phase mint counters are kept, ids are not reused
+    #[endpoint(clearSalePhases)]
+    fn clear_sale_phases(&self) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        for phase_id in 1..=self.phase_count().get() {
+            self.audited_update(&self.sale_phases(phase_id), self.audit_key_with(b"sale_phases", &phase_id), |config| {
+                config.end_timestamp = config.start_timestamp;
+            });
+        }
+
+        Ok(())
+    }
+
+    // private
+
+    fn phases_configured(&self) -> bool {
+        self.phase_count().get() > 0
+    }
+
+    // checks the active phase for a public mint of `amount` tokens and returns its unit price
+    fn apply_phase_rules(&self, buyer: &ManagedAddress, amount: u32) -> BigUint {
+        let phase_id = self.get_active_phase_id();
+        require!(phase_id != 0, "no active sale phase");
+
+        let config = self.sale_phases(phase_id).get();
+        require!(config.phase != SalePhase::Closed, "sale closed");
+        require!(!config.whitelist_required, "whitelist required for this phase");
+
+        let minted = self.phase_mints(phase_id, buyer).get() + amount;
+        require!(
+            config.wallet_limit == 0 || minted <= config.wallet_limit,
+            "phase wallet limit reached"
+        );
+        self.audited_set(
+            &self.phase_mints(phase_id, buyer),
+            self.audit_key_with(b"phase_mints", &(phase_id, buyer)),
+            &minted,
+        );
+
+        config.price
+    }
+
+    // views
+
+    #[view(getActivePhaseId)]
+    fn get_active_phase_id(&self) -> u32 {
+        let now = self.blockchain().get_block_timestamp();
+        for phase_id in 1..=self.phase_count().get() {
+            let config = self.sale_phases(phase_id).get();
+            if now >= config.start_timestamp && now < config.end_timestamp {
+                return phase_id;
+            }
+        }
+
+        0
+    }
+
+    #[view(getCurrentPhase)]
+    fn get_current_phase(&self) -> SalePhase {
+        let phase_id = self.get_active_phase_id();
+        if phase_id != 0 {
+            return self.sale_phases(phase_id).get().phase;
+        }
+        if self.phases_configured() {
+            return SalePhase::Closed;
+        }
+
+        SalePhase::None
+    }
+
+    // storage
+
+    #[view(getPhaseCount)]
+    #[storage_mapper("phase_count")]
+    fn phase_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getPhaseConfig)]
+    #[storage_mapper("sale_phases")]
+    fn sale_phases(&self, phase_id: u32) -> SingleValueMapper<PhaseConfig<Self::Api>>;
+
+    #[view(getPhaseMints)]
+    #[storage_mapper("phase_mints")]
+    fn phase_mints(&self, phase_id: u32, address: &ManagedAddress) -> SingleValueMapper<u32>;
+}
@@ -0,0 +1,118 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Piece<M: ManagedTypeApi> {
+    pub id: u32,
+    pub name: ManagedBuffer<M>,
+    pub uri: ManagedBuffer<M>,
+    pub price: BigUint<M>,
+    pub royalties: u32,
+    pub sold: bool,
+    // 0 until the piece is bought
+    pub nonce: u64,
+}
+
+// 1/1 art drops: every piece is registered individually and minted into the
+// collection only when a buyer picks it through `buyPiece`. A piece takes the
+// next mint id and counts towards the supply like any other mint.
+#[elrond_wasm::module]
+pub trait PiecesModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::storage::StorageModule
+    + crate::timelock::TimelockModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(registerPiece)]
+    fn register_piece(&self, name: ManagedBuffer, uri: ManagedBuffer, price: BigUint, royalties: u32) -> u32 {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        require!(
+            royalties <= self.max_royalties().get(),
+            "royalties cannot exceed the committed maximum"
+        );
+
+        let piece_id = self.piece_count().get() + 1;
+        self.audited_set(
+            &self.pieces(piece_id),
+            self.audit_key_with(b"pieces", &piece_id),
+            &Piece {
+                id: piece_id,
+                name,
+                uri,
+                price,
+                royalties,
+                sold: false,
+                nonce: 0,
+            },
+        );
+        self.audited_set(&self.piece_count(), self.audit_key(b"piece_count"), &piece_id);
+        self.available_pieces().insert(piece_id);
+        self.log_entry_added(self.audit_key(b"available_pieces"), &piece_id);
+
+        piece_id
+    }
+
+    // private
+
+    // checks the payment for an unsold piece; the caller mints it and calls
+    // `mark_piece_sold`
+    fn take_piece(&self, piece_id: u32, payment_token: &TokenIdentifier, payment_amount: &BigUint) -> Piece<Self::Api> {
+        require!(!self.pieces(piece_id).is_empty(), "piece does not exist");
+
+        let piece = self.pieces(piece_id).get();
+        require!(!piece.sold, "piece already sold");
+        require!(
+            *payment_token == self.payment_token_id().get(),
+            "not given token identifier"
+        );
+        require!(*payment_amount == piece.price, "payment does not match the price");
+
+        piece
+    }
+
+    fn mark_piece_sold(&self, mut piece: Piece<Self::Api>, nonce: u64) {
+        let piece_id = piece.id;
+        piece.sold = true;
+        piece.nonce = nonce;
+        self.audited_set(&self.pieces(piece_id), self.audit_key_with(b"pieces", &piece_id), &piece);
+        self.available_pieces().swap_remove(&piece_id);
+        self.log_entry_removed(self.audit_key(b"available_pieces"), &piece_id);
+    }
+
+    // views
+
+    // `from` is a zero-based offset into the unsold pieces
+    #[view(getAvailablePieces)]
+    fn get_available_pieces(&self, from: usize, size: usize) -> MultiResultVec<Piece<Self::Api>> {
+        let mut pieces = Vec::new();
+        for piece_id in self.available_pieces().iter().skip(from).take(size) {
+            pieces.push(self.pieces(piece_id).get());
+        }
+
+        pieces.into()
+    }
+
+    #[view(getAvailablePieceCount)]
+    fn get_available_piece_count(&self) -> usize {
+        self.available_pieces().len()
+    }
+
+    // storage
+
+    #[view(getPieceCount)]
+    #[storage_mapper("piece_count")]
+    fn piece_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getPiece)]
+    #[storage_mapper("pieces")]
+    fn pieces(&self, piece_id: u32) -> SingleValueMapper<Piece<Self::Api>>;
+
+    #[storage_mapper("available_pieces")]
+    fn available_pieces(&self) -> UnorderedSetMapper<u32>;
+}
@@ -0,0 +1,173 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum PollTarget {
+    // advisory only
+    None,
+    NftTokenPrice,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Poll {
+    pub target: PollTarget,
+    pub end_timestamp: u64,
+    pub finalized: bool,
+    // 1-based index into the options, 0 until finalized
+    pub winning_option: usize,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct PollOption<M: ManagedTypeApi> {
+    pub label: ManagedBuffer<M>,
+    // applied to the poll target if the option wins
+    pub value: BigUint<M>,
+}
+
+// Holder polls over preset options. Votes are weighted by the voter's attributed
+// tokens (staked tokens included) at voting time, one vote per address. Once the
+// poll ends anyone can finalize it, and the winning option's value is written to
+// the poll's target setting; the preset options bound what a poll can set.
+#[elrond_wasm::module]
+pub trait PollsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::holders::HoldersModule
+    + crate::storage::StorageModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(openPoll)]
+    fn open_poll(
+        &self,
+        target: PollTarget,
+        end_timestamp: u64,
+        #[var_args] options: ManagedVarArgs<MultiArg2<ManagedBuffer, BigUint>>,
+    ) -> u32 {
+        self.require_role(Role::Admin);
+        require!(
+            end_timestamp > self.blockchain().get_block_timestamp(),
+            "poll end must be in the future"
+        );
+
+        let poll_id = self.poll_count().get() + 1;
+        for option in options.into_iter() {
+            let (label, value) = option.into_tuple();
+            let option = PollOption { label, value };
+            self.log_entry_added(self.audit_key_with(b"poll_options", &poll_id), &option);
+            self.poll_options(poll_id).push(&option);
+        }
+        require!(self.poll_options(poll_id).len() >= 2, "a poll needs at least two options");
+
+        self.audited_set(
+            &self.polls(poll_id),
+            self.audit_key_with(b"polls", &poll_id),
+            &Poll {
+                target,
+                end_timestamp,
+                finalized: false,
+                winning_option: 0,
+            },
+        );
+        self.audited_set(&self.poll_count(), self.audit_key(b"poll_count"), &poll_id);
+
+        poll_id
+    }
+
+    /// endpoint
+
+    // `option` is 1-based
+    #[endpoint(vote)]
+    fn vote(&self, poll_id: u32, option: usize) {
+        require!(!self.polls(poll_id).is_empty(), "poll does not exist");
+        require!(
+            self.blockchain().get_block_timestamp() < self.polls(poll_id).get().end_timestamp,
+            "poll ended"
+        );
+        require!(option > 0 && option <= self.poll_options(poll_id).len(), "invalid option");
+
+        let caller = self.blockchain().get_caller();
+        require!(!self.poll_voted(poll_id, &caller).get(), "already voted");
+
+        let weight = self.attributed_token_count(&caller).get();
+        require!(weight > 0, "only holders can vote");
+
+        self.audited_set(
+            &self.poll_voted(poll_id, &caller),
+            self.audit_key_with(b"poll_voted", &(poll_id, &caller)),
+            &true,
+        );
+        self.audited_update(
+            &self.poll_option_weight(poll_id, option),
+            self.audit_key_with(b"poll_option_weight", &(poll_id, option)),
+            |v| *v += weight as u64,
+        );
+    }
+
+    #[endpoint(finalizePoll)]
+    fn finalize_poll(&self, poll_id: u32) -> usize {
+        require!(!self.polls(poll_id).is_empty(), "poll does not exist");
+
+        let mut poll = self.polls(poll_id).get();
+        require!(self.blockchain().get_block_timestamp() >= poll.end_timestamp, "poll not ended");
+        require!(!poll.finalized, "poll already finalized");
+
+        // ties go to the earlier option
+        let mut winning_option = 1;
+        for option in 2..=self.poll_options(poll_id).len() {
+            if self.poll_option_weight(poll_id, option).get() > self.poll_option_weight(poll_id, winning_option).get() {
+                winning_option = option;
+            }
+        }
+
+        poll.finalized = true;
+        poll.winning_option = winning_option;
+        self.audited_set(&self.polls(poll_id), self.audit_key_with(b"polls", &poll_id), &poll);
+
+        let value = self.poll_options(poll_id).get(winning_option).value;
+        match poll.target {
+            PollTarget::None => {},
+            PollTarget::NftTokenPrice => {
+                self.audited_set(&self.nft_token_price(), self.audit_key(b"nft_token_price"), &value);
+            },
+        }
+
+        winning_option
+    }
+
+    // views
+
+    // (option, total weight) for every option of the poll
+    #[view(getPollResults)]
+    fn get_poll_results(&self, poll_id: u32) -> MultiResultVec<MultiResult2<PollOption<Self::Api>, u64>> {
+        let mut results = Vec::new();
+        for option in 1..=self.poll_options(poll_id).len() {
+            let weight = self.poll_option_weight(poll_id, option).get();
+            results.push((self.poll_options(poll_id).get(option), weight).into());
+        }
+
+        results.into()
+    }
+
+    // storage
+
+    #[view(getPollCount)]
+    #[storage_mapper("poll_count")]
+    fn poll_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getPoll)]
+    #[storage_mapper("polls")]
+    fn polls(&self, poll_id: u32) -> SingleValueMapper<Poll>;
+
+    #[storage_mapper("poll_options")]
+    fn poll_options(&self, poll_id: u32) -> VecMapper<PollOption<Self::Api>>;
+
+    #[storage_mapper("poll_option_weight")]
+    fn poll_option_weight(&self, poll_id: u32, option: usize) -> SingleValueMapper<u64>;
+
+    #[view(hasVoted)]
+    #[storage_mapper("poll_voted")]
+    fn poll_voted(&self, poll_id: u32, address: &ManagedAddress) -> SingleValueMapper<bool>;
+}
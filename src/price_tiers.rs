@@ -0,0 +1,64 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct PriceTier<M: ManagedTypeApi> {
+    // last mint number, 1-based, sold at this price
+    pub last_mint: u32,
+    pub price: BigUint<M>,
+}
+
+// Price schedule by supply milestone, e.g. mints 1-1000 at 0.2 EGLD and 1001-5000
+// at 0.3 EGLD. Mints past the last tier fall back to the fixed NFT price.
+#[elrond_wasm::module]
+pub trait PriceTiersModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::storage::StorageModule
+    + crate::timelock::TimelockModule
+{
+    // endpoints - role-gated
+
+    // replaces the whole schedule, tiers as (last mint, price) in increasing order
+    #[endpoint(setPriceTiers)]
+    fn set_price_tiers(&self, #[var_args] tiers: ManagedVarArgs<MultiArg2<u32, BigUint>>) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        self.price_tiers().clear();
+        self.log_collection_cleared(self.audit_key(b"price_tiers"));
+
+        let mut previous_last_mint = 0u32;
+        for tier in tiers.into_iter() {
+            let (last_mint, price) = tier.into_tuple();
+            require!(last_mint > previous_last_mint, "tiers must be in increasing order");
+            previous_last_mint = last_mint;
+
+            let tier = PriceTier { last_mint, price };
+            self.log_entry_added(self.audit_key(b"price_tiers"), &tier);
+            self.price_tiers().push(&tier);
+        }
+
+        Ok(())
+    }
+
+    // private
+
+    fn price_for_mint_number(&self, mint_number: u32) -> BigUint {
+        for tier in self.price_tiers().iter() {
+            if mint_number <= tier.last_mint {
+                return tier.price;
+            }
+        }
+
+        self.nft_token_price().get()
+    }
+
+    // storage
+
+    #[view(getPriceTiers)]
+    #[storage_mapper("price_tiers")]
+    fn price_tiers(&self) -> VecMapper<PriceTier<Self::Api>>;
+}
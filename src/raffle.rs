@@ -0,0 +1,211 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::pause::Feature;
+use crate::states::RaffleState;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct RaffleEntry<M: ManagedTypeApi> {
+    pub payment_token: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+}
+
+// Raffle for hyped drops instead of a first-come public sale. During the entry
+// window each address can enter once by paying the raffle price, which is
+// escrowed. After the window the owner draws winners with the block random seed
+// and mints to them through `drawWinners`; everyone not drawn can then take the
+// entry back with `claimRefund`. Entries keep the token they were paid in, so a
+// payment token migration does not touch them. A contract runs a single raffle.
+#[elrond_wasm::module]
+pub trait RaffleModule:
+    crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+    + crate::storage::StorageModule
+{
+    // endpoints - owner-only
+
+    // `price` is in the payment token
+    #[only_owner]
+    #[endpoint(setRaffle)]
+    fn set_raffle(&self, start_timestamp: u64, end_timestamp: u64, price: BigUint) -> SCResult<()> {
+        require!(self.raffle_entrant_count().get() == 0, "raffle already has entries");
+        require!(end_timestamp > start_timestamp, "raffle must end after it starts");
+        require!(price > 0, "price must be positive");
+
+        self.audited_set(&self.raffle_start(), self.audit_key(b"raffle_start"), &start_timestamp);
+        self.audited_set(&self.raffle_end(), self.audit_key(b"raffle_end"), &end_timestamp);
+        self.audited_set(&self.raffle_price(), self.audit_key(b"raffle_price"), &price);
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(enterRaffle)]
+    fn enter_raffle(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(Feature::Mint);
+        require!(!self.raffle_price().is_empty(), "no raffle");
+
+        let now = self.blockchain().get_block_timestamp();
+        require!(now >= self.raffle_start().get(), "raffle not started");
+        require!(now < self.raffle_end().get(), "raffle ended");
+        require!(payment_token == self.payment_token_id().get(), "not given token identifier");
+        require!(payment_amount == self.raffle_price().get(), "payment does not match the price");
+
+        let caller = self.blockchain().get_caller();
+        require!(self.raffle_entry(&caller).is_empty(), "already entered");
+
+        let entry = RaffleEntry {
+            payment_token,
+            amount: payment_amount,
+        };
+        let index = self.raffle_entrant_count().get();
+        self.audited_set(
+            &self.raffle_entrant(index),
+            self.audit_key_with(b"raffle_entrant", &index),
+            &caller,
+        );
+        self.audited_set(&self.raffle_entrant_count(), self.audit_key(b"raffle_entrant_count"), &(index + 1));
+        self.audited_set(
+            &self.raffle_entry(&caller),
+            self.audit_key_with(b"raffle_entry", &caller),
+            &entry,
+        );
+        self.protect_inventory(&entry.payment_token, 0, &entry.amount);
+
+        self.raffle_entered_event(&caller, &entry.amount);
+    }
+
+    #[endpoint(claimRefund)]
+    fn claim_refund(&self) -> BigUint {
+        require!(self.raffle_drawn().get(), "winners not drawn");
+
+        let caller = self.blockchain().get_caller();
+        require!(!self.raffle_entry(&caller).is_empty(), "nothing to refund");
+
+        let entry = self.raffle_entry(&caller).get();
+        self.audited_clear(&self.raffle_entry(&caller), self.audit_key_with(b"raffle_entry", &caller));
+        self.release_inventory(&entry.payment_token, 0, &entry.amount);
+        self.send().direct(&caller, &entry.payment_token, 0, &entry.amount, &[]);
+
+        entry.amount
+    }
+
+    // private
+
+    // closes the raffle and returns up to `count` winners with the entry each paid;
+    // the escrow is released, the caller mints and books the payments
+    fn draw_raffle_winners(&self, count: u32) -> Vec<(ManagedAddress, RaffleEntry<Self::Api>)> {
+        require!(!self.raffle_price().is_empty(), "no raffle");
+        require!(!self.raffle_drawn().get(), "winners already drawn");
+        require!(
+            self.blockchain().get_block_timestamp() >= self.raffle_end().get(),
+            "raffle still open"
+        );
+        self.audited_set(&self.raffle_drawn(), self.audit_key(b"raffle_drawn"), &true);
+
+        // partial Fisher-Yates over the entrant slots, winners end up in front
+        let entrant_count = self.raffle_entrant_count().get();
+        let winner_count = core::cmp::min(count, entrant_count);
+        let mut rng = RandomnessSource::<Self::Api>::new();
+        let mut winners = Vec::new();
+        for index in 0..winner_count {
+            let drawn_index = rng.next_u32_in_range(index, entrant_count);
+            let winner = self.raffle_entrant(drawn_index).get();
+            if drawn_index != index {
+                let displaced = self.raffle_entrant(index).get();
+                self.audited_set(
+                    &self.raffle_entrant(drawn_index),
+                    self.audit_key_with(b"raffle_entrant", &drawn_index),
+                    &displaced,
+                );
+                self.audited_set(
+                    &self.raffle_entrant(index),
+                    self.audit_key_with(b"raffle_entrant", &index),
+                    &winner,
+                );
+            }
+
+            let entry = self.raffle_entry(&winner).get();
+            self.audited_clear(&self.raffle_entry(&winner), self.audit_key_with(b"raffle_entry", &winner));
+            self.release_inventory(&entry.payment_token, 0, &entry.amount);
+            winners.push((winner, entry));
+        }
+        self.audited_set(&self.raffle_winner_count(), self.audit_key(b"raffle_winner_count"), &winner_count);
+
+        winners
+    }
+
+    // views
+
+    // `Registration` also before the start, once the raffle is set
+    #[view(getRaffleState)]
+    fn get_raffle_state(&self) -> RaffleState {
+        if self.raffle_price().is_empty() {
+            return RaffleState::None;
+        }
+        if self.raffle_drawn().get() {
+            return RaffleState::Drawn;
+        }
+        if self.blockchain().get_block_timestamp() < self.raffle_end().get() {
+            return RaffleState::Registration;
+        }
+
+        RaffleState::RegistrationClosed
+    }
+
+    // the first `getRaffleWinnerCount` entrants once drawn, `from` is a zero-based offset
+    #[view(getRaffleEntrants)]
+    fn get_raffle_entrants(&self, from: u32, size: u32) -> MultiResultVec<ManagedAddress> {
+        let end = core::cmp::min(from.saturating_add(size), self.raffle_entrant_count().get());
+        (from..end)
+            .map(|index| self.raffle_entrant(index).get())
+            .collect::<Vec<ManagedAddress>>()
+            .into()
+    }
+
+    // events
+
+    #[event("raffle_entered")]
+    fn raffle_entered_event(&self, #[indexed] entrant: &ManagedAddress, amount: &BigUint);
+
+    // storage
+
+    #[view(getRaffleStart)]
+    #[storage_mapper("raffle_start")]
+    fn raffle_start(&self) -> SingleValueMapper<u64>;
+
+    #[view(getRaffleEnd)]
+    #[storage_mapper("raffle_end")]
+    fn raffle_end(&self) -> SingleValueMapper<u64>;
+
+    #[view(getRafflePrice)]
+    #[storage_mapper("raffle_price")]
+    fn raffle_price(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getRaffleEntrantCount)]
+    #[storage_mapper("raffle_entrant_count")]
+    fn raffle_entrant_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("raffle_entrant")]
+    fn raffle_entrant(&self, index: u32) -> SingleValueMapper<ManagedAddress>;
+
+    // escrowed entry, cleared once the entrant won or was refunded
+    #[view(getRaffleEntry)]
+    #[storage_mapper("raffle_entry")]
+    fn raffle_entry(&self, address: &ManagedAddress) -> SingleValueMapper<RaffleEntry<Self::Api>>;
+
+    #[view(isRaffleDrawn)]
+    #[storage_mapper("raffle_drawn")]
+    fn raffle_drawn(&self) -> SingleValueMapper<bool>;
+
+    #[view(getRaffleWinnerCount)]
+    #[storage_mapper("raffle_winner_count")]
+    fn raffle_winner_count(&self) -> SingleValueMapper<u32>;
+}
@@ -0,0 +1,81 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+const BASIS_POINTS_MAX: u32 = 10_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct RarityBoost {
+    pub min_staked_seconds: u64,
+    // added on top of a rare tier's weight, in basis points
+    pub boost: u32,
+}
+
+// Long-term stakers get better odds on rare tiers when minting. The boost is read
+// from the staking records at mint time and applied to the weights of the rare
+// tiers by the random rarity roll.
+#[elrond_wasm::module]
+pub trait RarityBoostModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+    + crate::staking::StakingModule
+    + crate::storage::StorageModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(addRarityBoost)]
+    fn add_rarity_boost(&self, min_staked_seconds: u64, boost: u32) -> usize {
+        self.require_role(Role::Admin);
+
+        let rarity_boost = RarityBoost {
+            min_staked_seconds,
+            boost,
+        };
+        self.log_entry_added(self.audit_key(b"rarity_boosts"), &rarity_boost);
+
+        self.rarity_boosts().push(&rarity_boost)
+    }
+
+    #[endpoint(clearRarityBoosts)]
+    fn clear_rarity_boosts(&self) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.rarity_boosts().clear();
+        self.log_collection_cleared(self.audit_key(b"rarity_boosts"));
+
+        Ok(())
+    }
+
+    // private
+
+    fn boosted_weight(&self, weight: u64, boost: u32) -> u64 {
+        weight * (BASIS_POINTS_MAX + boost) as u64 / BASIS_POINTS_MAX as u64
+    }
+
+    // views
+
+    // best boost the address qualifies for through its longest running stake
+    #[view(getRarityBoost)]
+    fn get_rarity_boost(&self, address: ManagedAddress) -> u32 {
+        let staked_seconds = self.get_longest_stake_duration(address);
+        if staked_seconds == 0 {
+            return 0;
+        }
+
+        self.rarity_boosts()
+            .iter()
+            .filter(|rarity_boost| staked_seconds >= rarity_boost.min_staked_seconds)
+            .map(|rarity_boost| rarity_boost.boost)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // storage
+
+    #[view(getRarityBoosts)]
+    #[storage_mapper("rarity_boosts")]
+    fn rarity_boosts(&self) -> VecMapper<RarityBoost>;
+}
@@ -0,0 +1,188 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct TraitValue<M: ManagedTypeApi> {
+    pub name: ManagedBuffer<M>,
+    pub weight: u64,
+    // 0 is common, higher tiers are rarer and get the staking rarity boost
+    pub tier: u32,
+}
+
+// On-chain trait generation. The owner uploads one weighted table per trait before
+// the first mint; every mint then rolls one value per trait from the block random
+// seed. Trait and value ids start at 1. A token's rarity tier is the highest tier
+// among its rolled values.
+#[elrond_wasm::module]
+pub trait RarityTraitsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+    + crate::rarity_boost::RarityBoostModule
+    + crate::staking::StakingModule
+    + crate::storage::StorageModule
+    + crate::trait_stats::TraitStatsModule
+{
+    // endpoints - owner-only
+
+    // values as (name, weight, tier)
+    #[only_owner]
+    #[endpoint(addTrait)]
+    fn add_trait(
+        &self,
+        name: ManagedBuffer,
+        #[var_args] values: ManagedVarArgs<MultiArg3<ManagedBuffer, u64, u32>>,
+    ) -> usize {
+        self.require_traits_editable();
+        require!(!values.is_empty(), "trait needs at least one value");
+
+        self.log_entry_added(self.audit_key(b"trait_names"), &name);
+        let trait_id = self.trait_names().push(&name);
+
+        for value in values.into_iter() {
+            let (name, weight, tier) = value.into_tuple();
+            require!(weight > 0, "weight must be positive");
+
+            let value = TraitValue { name, weight, tier };
+            self.log_entry_added(self.audit_key_with(b"trait_table", &trait_id), &value);
+            self.trait_table(trait_id).push(&value);
+        }
+
+        trait_id
+    }
+
+    #[only_owner]
+    #[endpoint(clearTraits)]
+    fn clear_traits(&self) -> SCResult<()> {
+        self.require_traits_editable();
+
+        for trait_id in 1..=self.trait_names().len() {
+            self.trait_table(trait_id).clear();
+            self.log_collection_cleared(self.audit_key_with(b"trait_table", &trait_id));
+        }
+        self.trait_names().clear();
+        self.log_collection_cleared(self.audit_key(b"trait_names"));
+
+        Ok(())
+    }
+
+    // private
+
+    fn require_traits_editable(&self) {
+        require!(self.mint_count().get() == 0, "traits locked after the first mint");
+    }
+
+    // one value id per trait, `boost` in basis points added to the weight of rare tiers;
+    // a reused mint id replaces the values it was counted with before
+    fn roll_traits(&self, mint_id: u32, boost: u32) -> ManagedVec<Self::Api, u32> {
+        self.forget_traits(mint_id);
+
+        let mut rng = RandomnessSource::<Self::Api>::new();
+        let mut value_ids = ManagedVec::new();
+        let mut token_tier = 0u32;
+
+        for trait_id in 1..=self.trait_names().len() {
+            let table = self.trait_table(trait_id);
+            let total_weight: u64 = table.iter().map(|value| self.rolled_weight(&value, boost)).sum();
+
+            let mut roll = rng.next_u64_in_range(0, total_weight);
+            let mut value_id = table.len();
+            for (index, value) in table.iter().enumerate() {
+                let weight = self.rolled_weight(&value, boost);
+                if roll < weight {
+                    value_id = index + 1;
+                    token_tier = core::cmp::max(token_tier, value.tier);
+                    break;
+                }
+                roll -= weight;
+            }
+
+            self.record_trait(trait_id as u32, value_id as u32);
+            value_ids.push(value_id as u32);
+        }
+
+        if !value_ids.is_empty() {
+            self.audited_set(&self.token_traits(mint_id), self.audit_key_with(b"token_traits", &mint_id), &value_ids);
+            self.audited_update(&self.tier_supply(token_tier), self.audit_key_with(b"tier_supply", &token_tier), |v| {
+                *v += 1
+            });
+            if self.rarity_tiers().insert(token_tier) {
+                self.log_entry_added(self.audit_key(b"rarity_tiers"), &token_tier);
+            }
+        }
+
+        value_ids
+    }
+
+    // takes the token's values and tier out of the counts, e.g. once it is burned
+    fn forget_traits(&self, mint_id: u32) {
+        if self.token_traits(mint_id).is_empty() {
+            return;
+        }
+
+        let mut token_tier = 0u32;
+        for (index, value_id) in self.token_traits(mint_id).get().iter().enumerate() {
+            let trait_id = index + 1;
+            token_tier = core::cmp::max(token_tier, self.trait_table(trait_id).get(value_id as usize).tier);
+            self.unrecord_trait(trait_id as u32, value_id);
+        }
+        self.audited_update(&self.tier_supply(token_tier), self.audit_key_with(b"tier_supply", &token_tier), |v| {
+            *v -= 1
+        });
+        self.audited_clear(&self.token_traits(mint_id), self.audit_key_with(b"token_traits", &mint_id));
+    }
+
+    fn rolled_weight(&self, value: &TraitValue<Self::Api>, boost: u32) -> u64 {
+        if value.tier == 0 {
+            return value.weight;
+        }
+
+        self.boosted_weight(value.weight, boost)
+    }
+
+    // (trait name, value name) pairs rolled for the token
+    fn decode_traits(&self, mint_id: u32) -> MultiResultVec<MultiResult2<ManagedBuffer, ManagedBuffer>> {
+        let mut traits = Vec::new();
+        for (index, value_id) in self.token_traits(mint_id).get().iter().enumerate() {
+            let trait_id = index + 1;
+            let value = self.trait_table(trait_id).get(value_id as usize);
+            traits.push((self.trait_names().get(trait_id), value.name).into());
+        }
+
+        traits.into()
+    }
+
+    // views
+
+    // (tier, tokens minted in it) for every tier rolled so far
+    #[view(getTierSupplies)]
+    fn get_tier_supplies(&self) -> MultiResultVec<MultiResult2<u32, u32>> {
+        let mut supplies = Vec::new();
+        for tier in self.rarity_tiers().iter() {
+            supplies.push((tier, self.tier_supply(tier).get()).into());
+        }
+
+        supplies.into()
+    }
+
+    // storage
+
+    #[view(getTraitNames)]
+    #[storage_mapper("trait_names")]
+    fn trait_names(&self) -> VecMapper<ManagedBuffer>;
+
+    #[view(getTraitTable)]
+    #[storage_mapper("trait_table")]
+    fn trait_table(&self, trait_id: usize) -> VecMapper<TraitValue<Self::Api>>;
+
+    #[storage_mapper("token_traits")]
+    fn token_traits(&self, mint_id: u32) -> SingleValueMapper<ManagedVec<Self::Api, u32>>;
+
+    #[storage_mapper("rarity_tiers")]
+    fn rarity_tiers(&self) -> SetMapper<u32>;
+
+    #[view(getTierSupply)]
+    #[storage_mapper("tier_supply")]
+    fn tier_supply(&self, tier: u32) -> SingleValueMapper<u32>;
+}
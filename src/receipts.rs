@@ -0,0 +1,205 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+const RECEIPT_AMOUNT: u32 = 1;
+const RECEIPT_NAME: &[u8] = b"Mint Receipt";
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct ReceiptAttributes {
+    pub drop_id: usize,
+    pub phase_id: u32,
+}
+
+// Buyers of drops with receipts enabled also get one unit of a receipt SFT. Every
+// drop and phase pair has its own nonce, so balances double as raffle tickets or
+// proof of participation. Mints never fail because of receipts: when the collection
+// or its roles are missing the receipt is skipped.
+#[elrond_wasm::module]
+pub trait ReceiptsModule: crate::access_control::AccessControlModule + crate::audit::AuditModule {
+    // endpoints - owner-only
+
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(issueReceiptToken)]
+    fn issue_receipt_token(&self, token_name: ManagedBuffer, token_ticker: ManagedBuffer) -> AsyncCall {
+        require!(self.receipt_token_id().is_empty(), "Token already issued");
+        require!(!self.receipt_issue_pending().get(), "Token issuance already in progress");
+
+        self.audited_set(
+            &self.receipt_issue_pending(),
+            self.audit_key(b"receipt_issue_pending"),
+            &true,
+        );
+
+        let payment_amount = self.call_value().egld_value();
+        self.send()
+            .esdt_system_sc_proxy()
+            .issue_semi_fungible(
+                payment_amount,
+                &token_name,
+                &token_ticker,
+                SemiFungibleTokenProperties {
+                    can_freeze: false,
+                    can_wipe: false,
+                    can_pause: false,
+                    can_change_owner: true,
+                    can_upgrade: false,
+                    can_add_special_roles: true,
+                },
+            )
+            .async_call()
+            .with_callback(self.callbacks().receipt_issue_callback())
+    }
+
+    #[only_owner]
+    #[endpoint(setReceiptLocalRoles)]
+    fn set_receipt_local_roles(&self) -> AsyncCall {
+        require!(!self.receipt_token_id().is_empty(), "Receipt token not issued");
+        require!(!self.receipt_roles_set().get(), "Local roles already set");
+        require!(!self.receipt_roles_pending().get(), "Local roles request already in progress");
+
+        self.audited_set(
+            &self.receipt_roles_pending(),
+            self.audit_key(b"receipt_roles_pending"),
+            &true,
+        );
+        self.send()
+            .esdt_system_sc_proxy()
+            .set_special_roles(
+                &self.blockchain().get_sc_address(),
+                &self.receipt_token_id().get(),
+                [EsdtLocalRole::NftCreate, EsdtLocalRole::NftAddQuantity][..].iter().cloned(),
+            )
+            .async_call()
+            .with_callback(self.callbacks().receipt_roles_callback())
+    }
+
+    // endpoints - role-gated
+
+    #[endpoint(setReceiptsEnabled)]
+    fn set_receipts_enabled(&self, drop_id: usize, enabled: bool) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.audited_set(
+            &self.receipts_enabled(drop_id),
+            self.audit_key_with(b"receipts_enabled", &drop_id),
+            &enabled,
+        );
+
+        Ok(())
+    }
+
+    // private
+
+    fn issue_receipt(&self, buyer: &ManagedAddress, drop_id: usize, phase_id: u32) {
+        if !self.receipts_enabled(drop_id).get() || !self.receipt_roles_set().get() {
+            return;
+        }
+
+        let receipt_token_id = self.receipt_token_id().get();
+        let amount = BigUint::from(RECEIPT_AMOUNT);
+        let nonce_mapper = self.receipt_nonce(drop_id, phase_id);
+        let nonce = if nonce_mapper.is_empty() {
+            let nonce = self.send().esdt_nft_create(
+                &receipt_token_id,
+                &amount,
+                &ManagedBuffer::from(RECEIPT_NAME),
+                &BigUint::zero(),
+                &ManagedBuffer::new(),
+                &ReceiptAttributes { drop_id, phase_id },
+                &ManagedVec::new(),
+            );
+            self.audited_set(&nonce_mapper, self.audit_key_with(b"receipt_nonce", &(drop_id, phase_id)), &nonce);
+            nonce
+        } else {
+            let nonce = nonce_mapper.get();
+            self.send().esdt_local_mint(&receipt_token_id, nonce, &amount);
+            nonce
+        };
+
+        self.audited_update(
+            &self.receipt_supply(drop_id, phase_id),
+            self.audit_key_with(b"receipt_supply", &(drop_id, phase_id)),
+            |v| *v += RECEIPT_AMOUNT,
+        );
+        self.audited_update(
+            &self.drop_receipt_supply(drop_id),
+            self.audit_key_with(b"drop_receipt_supply", &drop_id),
+            |v| *v += RECEIPT_AMOUNT,
+        );
+
+        self.send().direct(buyer, &receipt_token_id, nonce, &amount, &[]);
+    }
+
+    // callbacks
+
+    #[callback]
+    fn receipt_issue_callback(&self, #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>) {
+        self.audited_clear(&self.receipt_issue_pending(), self.audit_key(b"receipt_issue_pending"));
+
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.audited_set(
+                    &self.receipt_token_id(),
+                    self.audit_key(b"receipt_token_id"),
+                    &token_id,
+                );
+            },
+            ManagedAsyncCallResult::Err(_) => {
+                let caller = self.blockchain().get_owner_address();
+                let (returned_tokens, token_id) = self.call_value().payment_token_pair();
+                if token_id.is_egld() && returned_tokens > 0 {
+                    self.send()
+                        .direct(&caller, &token_id, 0, &returned_tokens, &[]);
+                }
+            },
+        }
+    }
+
+    #[callback]
+    fn receipt_roles_callback(&self, #[call_result] result: ManagedAsyncCallResult<()>) {
+        self.audited_clear(&self.receipt_roles_pending(), self.audit_key(b"receipt_roles_pending"));
+
+        if let ManagedAsyncCallResult::Ok(()) = result {
+            self.audited_set(
+                &self.receipt_roles_set(),
+                self.audit_key(b"receipt_roles_set"),
+                &true,
+            );
+        }
+    }
+
+    // storage
+
+    #[storage_mapper("receipt_issue_pending")]
+    fn receipt_issue_pending(&self) -> SingleValueMapper<bool>;
+
+    #[view(areReceiptRolesSet)]
+    #[storage_mapper("receipt_roles_set")]
+    fn receipt_roles_set(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("receipt_roles_pending")]
+    fn receipt_roles_pending(&self) -> SingleValueMapper<bool>;
+
+    #[view(getReceiptTokenId)]
+    #[storage_mapper("receipt_token_id")]
+    fn receipt_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(areReceiptsEnabled)]
+    #[storage_mapper("receipts_enabled")]
+    fn receipts_enabled(&self, drop_id: usize) -> SingleValueMapper<bool>;
+
+    #[view(getReceiptNonce)]
+    #[storage_mapper("receipt_nonce")]
+    fn receipt_nonce(&self, drop_id: usize, phase_id: u32) -> SingleValueMapper<u64>;
+
+    #[view(getReceiptSupply)]
+    #[storage_mapper("receipt_supply")]
+    fn receipt_supply(&self, drop_id: usize, phase_id: u32) -> SingleValueMapper<u32>;
+
+    #[view(getDropReceiptSupply)]
+    #[storage_mapper("drop_receipt_supply")]
+    fn drop_receipt_supply(&self, drop_id: usize) -> SingleValueMapper<u32>;
+}
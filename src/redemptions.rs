@@ -0,0 +1,157 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::pause::Feature;
+use crate::states::RedemptionState;
+
+const REDEEMED_NFT_AMOUNT: u32 = 1;
+const SHIPPING_HASH_LENGTH: usize = 32;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Redemption<M: ManagedTypeApi> {
+    pub holder: ManagedAddress<M>,
+    pub nonce: u64,
+    // sha256 of the shipping details kept off-chain
+    pub shipping_hash: ManagedBuffer<M>,
+    pub state: RedemptionState,
+    pub redeemed_timestamp: u64,
+    pub fulfilled_timestamp: u64,
+}
+
+// Physical goods redeemed with collection NFTs. The holder hands in the NFT, which
+// is burned or kept locked in the contract depending on config, together with a
+// hash of its shipping details; operators mark redemptions fulfilled once shipped.
+#[elrond_wasm::module]
+pub trait RedemptionsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::holders::HoldersModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+    + crate::storage::StorageModule
+{
+    // endpoints - role-gated
+
+    // with `burn` unset redeemed NFTs stay locked in the contract
+    #[endpoint(setRedemptionConfig)]
+    fn set_redemption_config(&self, enabled: bool, burn: bool) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.audited_set(&self.redemption_enabled(), self.audit_key(b"redemption_enabled"), &enabled);
+        self.audited_set(&self.redemption_burns(), self.audit_key(b"redemption_burns"), &burn);
+
+        Ok(())
+    }
+
+    #[endpoint(markRedemptionFulfilled)]
+    fn mark_redemption_fulfilled(&self, redemption_id: u32) -> SCResult<()> {
+        self.require_role(Role::Operator);
+        require!(!self.redemptions(redemption_id).is_empty(), "redemption does not exist");
+
+        let mut redemption = self.redemptions(redemption_id).get();
+        require!(redemption.state == RedemptionState::Pending, "redemption not pending");
+
+        redemption.state = RedemptionState::Fulfilled;
+        redemption.fulfilled_timestamp = self.blockchain().get_block_timestamp();
+        self.audited_set(
+            &self.redemptions(redemption_id),
+            self.audit_key_with(b"redemptions", &redemption_id),
+            &redemption,
+        );
+        self.pending_redemptions().swap_remove(&redemption_id);
+        self.log_entry_removed(self.audit_key(b"pending_redemptions"), &redemption_id);
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(redeemPhysical)]
+    fn redeem_physical(
+        &self,
+        shipping_hash: ManagedBuffer,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_nonce] payment_nonce: u64,
+        #[payment_amount] payment_amount: BigUint,
+    ) -> u32 {
+        self.require_feature_active(Feature::Claims);
+        require!(self.redemption_enabled().get(), "redemptions disabled");
+        require!(payment_token == self.nft_token_id().get(), "only collection NFTs can be redeemed");
+        require!(payment_amount == REDEEMED_NFT_AMOUNT, "invalid amount");
+        require!(shipping_hash.len() == SHIPPING_HASH_LENGTH, "invalid shipping hash length");
+
+        let caller = self.blockchain().get_caller();
+        let redemption_id = self.redemption_count().get() + 1;
+        self.audited_set(
+            &self.redemptions(redemption_id),
+            self.audit_key_with(b"redemptions", &redemption_id),
+            &Redemption {
+                holder: caller.clone(),
+                nonce: payment_nonce,
+                shipping_hash,
+                state: RedemptionState::Pending,
+                redeemed_timestamp: self.blockchain().get_block_timestamp(),
+                fulfilled_timestamp: 0,
+            },
+        );
+        self.audited_set(&self.redemption_count(), self.audit_key(b"redemption_count"), &redemption_id);
+        self.pending_redemptions().insert(redemption_id);
+        self.log_entry_added(self.audit_key(b"pending_redemptions"), &redemption_id);
+
+        if self.redemption_burns().get() {
+            self.send().esdt_local_burn(&payment_token, payment_nonce, &payment_amount);
+        } else {
+            self.protect_inventory(&payment_token, payment_nonce, &payment_amount);
+        }
+        self.release_token(&caller, payment_nonce);
+
+        redemption_id
+    }
+
+    // views
+
+    #[view(getRedemptionState)]
+    fn get_redemption_state(&self, redemption_id: u32) -> RedemptionState {
+        if self.redemptions(redemption_id).is_empty() {
+            return RedemptionState::None;
+        }
+
+        self.redemptions(redemption_id).get().state
+    }
+
+    // (redemption id, redemption) for the queue awaiting fulfillment,
+    // `from` is a zero-based offset into it
+    #[view(getPendingRedemptions)]
+    fn get_pending_redemptions(&self, from: usize, size: usize) -> MultiResultVec<MultiResult2<u32, Redemption<Self::Api>>> {
+        self.pending_redemptions()
+            .iter()
+            .skip(from)
+            .take(size)
+            .map(|redemption_id| (redemption_id, self.redemptions(redemption_id).get()).into())
+            .collect::<Vec<MultiResult2<u32, Redemption<Self::Api>>>>()
+            .into()
+    }
+
+    // storage
+
+    #[view(isRedemptionEnabled)]
+    #[storage_mapper("redemption_enabled")]
+    fn redemption_enabled(&self) -> SingleValueMapper<bool>;
+
+    #[view(doRedemptionsBurn)]
+    #[storage_mapper("redemption_burns")]
+    fn redemption_burns(&self) -> SingleValueMapper<bool>;
+
+    #[view(getRedemptionCount)]
+    #[storage_mapper("redemption_count")]
+    fn redemption_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getRedemption)]
+    #[storage_mapper("redemptions")]
+    fn redemptions(&self, redemption_id: u32) -> SingleValueMapper<Redemption<Self::Api>>;
+
+    #[storage_mapper("pending_redemptions")]
+    fn pending_redemptions(&self) -> UnorderedSetMapper<u32>;
+}
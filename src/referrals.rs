@@ -0,0 +1,166 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+const BASIS_POINTS_MAX: u32 = 10_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct ReferrerStats<M: ManagedTypeApi> {
+    pub referrals: u32,
+    // paid by referred buyers, after their discount
+    pub volume: BigUint<M>,
+    pub commission: BigUint<M>,
+}
+
+// Public mints can name a referrer through `mintWithReferrer`. The referrer is
+// credited a commission on what the buyer paid and the buyer gets a discount, both
+// in basis points. Commissions come off the mint revenue before the shareholder
+// split, are escrowed until the referrer claims them, and count towards
+// per-referrer stats for leaderboards.
+#[elrond_wasm::module]
+pub trait ReferralsModule:
+    crate::access_control::AccessControlModule + crate::audit::AuditModule + crate::inventory::InventoryModule
+{
+    // endpoints - role-gated
+
+    // both 0 turns the program off
+    #[endpoint(setReferralRates)]
+    fn set_referral_rates(&self, commission: u32, discount: u32) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        require!(commission + discount <= BASIS_POINTS_MAX, "referral rates cannot exceed 100%");
+
+        self.audited_set(&self.referral_commission(), self.audit_key(b"referral_commission"), &commission);
+        self.audited_set(&self.referral_discount(), self.audit_key(b"referral_discount"), &discount);
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[endpoint(claimReferralRewards)]
+    fn claim_referral_rewards(&self, token_id: TokenIdentifier) -> BigUint {
+        let caller = self.blockchain().get_caller();
+        let balance = self.referral_balance(&caller, &token_id).get();
+        require!(balance > 0, "nothing to claim");
+
+        self.audited_clear(
+            &self.referral_balance(&caller, &token_id),
+            self.audit_key_with(b"referral_balance", &(&caller, &token_id)),
+        );
+        self.release_inventory(&token_id, 0, &balance);
+        self.send().direct(&caller, &token_id, 0, &balance, &[]);
+
+        self.referral_rewards_claimed_event(&caller, &token_id, &balance);
+
+        balance
+    }
+
+    // private
+
+    // the referrer counts when the program is on and the buyer is not referring themselves
+    fn valid_referrer(&self, buyer: &ManagedAddress, referrer: ManagedAddress) -> Option<ManagedAddress> {
+        if referrer.is_zero() || referrer == *buyer {
+            return None;
+        }
+        if self.referral_commission().get() == 0 && self.referral_discount().get() == 0 {
+            return None;
+        }
+
+        Some(referrer)
+    }
+
+    fn referral_discounted_price(&self, price: &BigUint) -> BigUint {
+        price * (BASIS_POINTS_MAX - self.referral_discount().get()) / BASIS_POINTS_MAX
+    }
+
+    // returns the commission, which the caller takes off the mint revenue
+    fn credit_referrer(&self, referrer: &ManagedAddress, token_id: &TokenIdentifier, paid: &BigUint) -> BigUint {
+        let commission = paid * self.referral_commission().get() / BASIS_POINTS_MAX;
+        if commission > 0 {
+            self.audited_update(
+                &self.referral_balance(referrer, token_id),
+                self.audit_key_with(b"referral_balance", &(referrer, token_id)),
+                |v| *v += &commission,
+            );
+            self.protect_inventory(token_id, 0, &commission);
+        }
+
+        let mut stats = if self.referrers().insert(referrer.clone()) {
+            self.log_entry_added(self.audit_key(b"referrers"), referrer);
+            ReferrerStats {
+                referrals: 0,
+                volume: BigUint::zero(),
+                commission: BigUint::zero(),
+            }
+        } else {
+            self.referrer_stats(referrer).get()
+        };
+        stats.referrals += 1;
+        stats.volume += paid;
+        stats.commission += &commission;
+        self.audited_set(&self.referrer_stats(referrer), self.audit_key_with(b"referrer_stats", referrer), &stats);
+        self.referral_credited_event(referrer, token_id, &commission);
+
+        commission
+    }
+
+    // views
+
+    // (referrer, stats) in the order referrers first brought a buyer, `from` is a
+    // zero-based offset; amounts are in the payment token of each mint
+    #[view(getReferrers)]
+    fn get_referrers(&self, from: usize, size: usize) -> MultiResultVec<MultiResult2<ManagedAddress, ReferrerStats<Self::Api>>> {
+        self.referrers()
+            .iter()
+            .skip(from)
+            .take(size)
+            .map(|referrer| {
+                let stats = self.referrer_stats(&referrer).get();
+                (referrer, stats).into()
+            })
+            .collect::<Vec<MultiResult2<ManagedAddress, ReferrerStats<Self::Api>>>>()
+            .into()
+    }
+
+    // events
+
+    #[event("referral_credited")]
+    fn referral_credited_event(
+        &self,
+        #[indexed] referrer: &ManagedAddress,
+        #[indexed] token_id: &TokenIdentifier,
+        commission: &BigUint,
+    );
+
+    #[event("referral_rewards_claimed")]
+    fn referral_rewards_claimed_event(
+        &self,
+        #[indexed] referrer: &ManagedAddress,
+        #[indexed] token_id: &TokenIdentifier,
+        amount: &BigUint,
+    );
+
+    // storage
+
+    // in basis points of what the referred buyer paid
+    #[view(getReferralCommission)]
+    #[storage_mapper("referral_commission")]
+    fn referral_commission(&self) -> SingleValueMapper<u32>;
+
+    // in basis points off the referred buyer's price
+    #[view(getReferralDiscount)]
+    #[storage_mapper("referral_discount")]
+    fn referral_discount(&self) -> SingleValueMapper<u32>;
+
+    #[view(getReferralBalance)]
+    #[storage_mapper("referral_balance")]
+    fn referral_balance(&self, referrer: &ManagedAddress, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getReferrerStats)]
+    #[storage_mapper("referrer_stats")]
+    fn referrer_stats(&self, referrer: &ManagedAddress) -> SingleValueMapper<ReferrerStats<Self::Api>>;
+
+    #[storage_mapper("referrers")]
+    fn referrers(&self) -> SetMapper<ManagedAddress>;
+}
@@ -0,0 +1,98 @@
+elrond_wasm::imports!();
+
+use crate::access_control::Role;
+
+// Gas reimbursement for relayers submitting sponsored mints on behalf of buyers.
+// Every executed sponsored mint credits the relayer a fixed EGLD fee taken from a
+// dedicated pool; pool and credited balances are protected until claimed.
+#[elrond_wasm::module]
+pub trait RelayersModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+{
+    // endpoints - role-gated
+
+    #[payable("EGLD")]
+    #[endpoint(fundRelayerPool)]
+    fn fund_relayer_pool(&self, #[payment_amount] payment_amount: BigUint) {
+        self.require_role(Role::Treasurer);
+        require!(payment_amount > 0, "amount must be positive");
+
+        self.audited_update(&self.relayer_pool(), self.audit_key(b"relayer_pool"), |v| *v += &payment_amount);
+        self.protect_inventory(&TokenIdentifier::egld(), 0, &payment_amount);
+    }
+
+    #[endpoint(setRelayerFee)]
+    fn set_relayer_fee(&self, fee: BigUint) -> SCResult<()> {
+        self.require_role(Role::Treasurer);
+
+        self.audited_set(&self.relayer_fee(), self.audit_key(b"relayer_fee"), &fee);
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[endpoint(claimRelayerReimbursement)]
+    fn claim_relayer_reimbursement(&self) -> BigUint {
+        let caller = self.blockchain().get_caller();
+        let amount = self.relayer_balance(&caller).get();
+        require!(amount > 0, "nothing to claim");
+
+        self.audited_clear(&self.relayer_balance(&caller), self.audit_key_with(b"relayer_balance", &caller));
+        self.audited_update(
+            &self.relayer_claimed(&caller),
+            self.audit_key_with(b"relayer_claimed", &caller),
+            |v| *v += &amount,
+        );
+
+        let egld = TokenIdentifier::egld();
+        self.release_inventory(&egld, 0, &amount);
+        self.send().direct(&caller, &egld, 0, &amount, &[]);
+
+        amount
+    }
+
+    // private
+
+    fn reimburse_relayer(&self, relayer: &ManagedAddress) {
+        let fee = self.relayer_fee().get();
+        require!(fee <= self.relayer_pool().get(), "relayer pool exhausted");
+
+        self.audited_update(&self.relayer_pool(), self.audit_key(b"relayer_pool"), |v| *v -= &fee);
+        self.audited_update(
+            &self.relayer_balance(relayer),
+            self.audit_key_with(b"relayer_balance", relayer),
+            |v| *v += &fee,
+        );
+        self.audited_update(
+            &self.relayer_mint_count(relayer),
+            self.audit_key_with(b"relayer_mint_count", relayer),
+            |v| *v += 1,
+        );
+    }
+
+    // storage
+
+    // EGLD not yet credited to any relayer
+    #[view(getRelayerPool)]
+    #[storage_mapper("relayer_pool")]
+    fn relayer_pool(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getRelayerFee)]
+    #[storage_mapper("relayer_fee")]
+    fn relayer_fee(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getRelayerBalance)]
+    #[storage_mapper("relayer_balance")]
+    fn relayer_balance(&self, relayer: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getRelayerClaimed)]
+    #[storage_mapper("relayer_claimed")]
+    fn relayer_claimed(&self, relayer: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getRelayerMintCount)]
+    #[storage_mapper("relayer_mint_count")]
+    fn relayer_mint_count(&self, relayer: &ManagedAddress) -> SingleValueMapper<u32>;
+}
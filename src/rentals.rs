@@ -0,0 +1,168 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::pause::Feature;
+
+const RENTED_NFT_AMOUNT: u32 = 1;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Rental<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    // in the payment token
+    pub price_per_day: BigUint<M>,
+    pub max_days: u32,
+    // the zero address until first rented
+    pub renter: ManagedAddress<M>,
+    pub rented_until: u64,
+}
+
+// NFT lending for token-gated apps. A holder deposits a main collection NFT with
+// a daily price; a renter pays for whole days up front, the payment goes straight
+// to the holder, and `isRentedBy` answers for the rental period. The NFT stays in
+// the contract and attributed to its holder, who can `reclaim` it whenever it is
+// not rented out.
+#[elrond_wasm::module]
+pub trait RentalsModule:
+    crate::audit::AuditModule
+    + crate::holders::HoldersModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+    + crate::storage::StorageModule
+{
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(listForRent)]
+    fn list_for_rent(
+        &self,
+        price_per_day: BigUint,
+        max_days: u32,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_nonce] payment_nonce: u64,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(Feature::Marketplace);
+        require!(payment_token == self.nft_token_id().get(), "only collection NFTs can be rented");
+        require!(payment_amount == RENTED_NFT_AMOUNT, "invalid amount");
+        require!(price_per_day > 0, "price must be positive");
+        require!(max_days > 0, "max days must be positive");
+
+        let caller = self.blockchain().get_caller();
+        self.audited_set(
+            &self.rentals(payment_nonce),
+            self.audit_key_with(b"rentals", &payment_nonce),
+            &Rental {
+                owner: caller.clone(),
+                price_per_day,
+                max_days,
+                renter: ManagedAddress::zero(),
+                rented_until: 0,
+            },
+        );
+        self.rental_nonces().insert(payment_nonce);
+        self.log_entry_added(self.audit_key(b"rental_nonces"), &payment_nonce);
+        self.protect_inventory(&payment_token, payment_nonce, &payment_amount);
+        self.reattribute_token(&caller, payment_nonce);
+    }
+
+    // pays `days` times the daily price
+    #[payable("*")]
+    #[endpoint(rent)]
+    fn rent(
+        &self,
+        nonce: u64,
+        days: u32,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(Feature::Marketplace);
+        self.require_for_rent(nonce);
+
+        let mut rental = self.rentals(nonce).get();
+        let now = self.blockchain().get_block_timestamp();
+        require!(now >= rental.rented_until, "already rented");
+        require!(days > 0 && days <= rental.max_days, "invalid rental duration");
+        require!(payment_token == self.payment_token_id().get(), "not given token identifier");
+        require!(payment_amount == &rental.price_per_day * days, "payment does not match the price");
+
+        let caller = self.blockchain().get_caller();
+        require!(caller != rental.owner, "owner cannot rent their own token");
+
+        rental.renter = caller.clone();
+        rental.rented_until = now + u64::from(days) * SECONDS_PER_DAY;
+        self.audited_set(&self.rentals(nonce), self.audit_key_with(b"rentals", &nonce), &rental);
+        self.send().direct(&rental.owner, &payment_token, 0, &payment_amount, &[]);
+
+        self.nft_rented_event(&caller, nonce, rental.rented_until, &payment_amount);
+    }
+
+    // returns the NFT to its owner once no rental is running
+    #[endpoint(reclaim)]
+    fn reclaim(&self, nonce: u64) {
+        self.require_for_rent(nonce);
+
+        let rental = self.rentals(nonce).get();
+        require!(self.blockchain().get_caller() == rental.owner, "only the owner can reclaim");
+        require!(self.blockchain().get_block_timestamp() >= rental.rented_until, "token still rented");
+
+        self.rental_nonces().swap_remove(&nonce);
+        self.log_entry_removed(self.audit_key(b"rental_nonces"), &nonce);
+        self.audited_clear(&self.rentals(nonce), self.audit_key_with(b"rentals", &nonce));
+
+        let nft_token_id = self.nft_token_id().get();
+        let amount = BigUint::from(RENTED_NFT_AMOUNT);
+        self.release_inventory(&nft_token_id, nonce, &amount);
+        self.send().direct(&rental.owner, &nft_token_id, nonce, &amount, &[]);
+    }
+
+    // private
+
+    fn require_for_rent(&self, nonce: u64) {
+        require!(self.rental_nonces().contains(&nonce), "token not for rent");
+    }
+
+    // views
+
+    #[view(isRentedBy)]
+    fn is_rented_by(&self, nonce: u64, address: ManagedAddress) -> bool {
+        if !self.rental_nonces().contains(&nonce) {
+            return false;
+        }
+
+        let rental = self.rentals(nonce).get();
+        rental.renter == address && self.blockchain().get_block_timestamp() < rental.rented_until
+    }
+
+    // (nonce, rental) for deposited tokens, `from` is a zero-based offset
+    #[view(getRentals)]
+    fn get_rentals(&self, from: usize, size: usize) -> MultiResultVec<MultiResult2<u64, Rental<Self::Api>>> {
+        self.rental_nonces()
+            .iter()
+            .skip(from)
+            .take(size)
+            .map(|nonce| (nonce, self.rentals(nonce).get()).into())
+            .collect::<Vec<MultiResult2<u64, Rental<Self::Api>>>>()
+            .into()
+    }
+
+    // events
+
+    #[event("nft_rented")]
+    fn nft_rented_event(
+        &self,
+        #[indexed] renter: &ManagedAddress,
+        #[indexed] nonce: u64,
+        #[indexed] rented_until: u64,
+        paid: &BigUint,
+    );
+
+    // storage
+
+    #[view(getRental)]
+    #[storage_mapper("rentals")]
+    fn rentals(&self, nonce: u64) -> SingleValueMapper<Rental<Self::Api>>;
+
+    #[storage_mapper("rental_nonces")]
+    fn rental_nonces(&self) -> UnorderedSetMapper<u64>;
+}
@@ -0,0 +1,225 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::pause::Feature;
+
+const SECONDS_PER_MINUTE: u64 = 60;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Reservation<M: ManagedTypeApi> {
+    // tokens still to be bought
+    pub amount: u32,
+    pub deposit_token: TokenIdentifier<M>,
+    pub deposit_per_token: BigUint<M>,
+    pub expiry_timestamp: u64,
+}
+
+// Buyers can hold supply for a while against a small deposit in the payment token.
+// Only the holder can mint reserved supply until it expires; each completed mint
+// refunds its share of the deposit. Anyone can release an expired reservation,
+// whose deposit is then refunded or forfeited to the treasury depending on config.
+#[elrond_wasm::module]
+pub trait ReservationsModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+    + crate::storage::StorageModule
+{
+    // endpoints - owner-only
+
+    // supply set aside for `mintTeamReserve`, no other mint path can use it
+    #[only_owner]
+    #[endpoint(setTeamReserve)]
+    fn set_team_reserve(&self, amount: u32) -> SCResult<()> {
+        let max_supply = self.max_supply().get();
+        require!(max_supply != 0, "team reserve requires a max supply");
+        require!(
+            self.mint_count().get() + self.reserved_supply().get() + amount <= max_supply,
+            "not enough supply left"
+        );
+
+        self.audited_set(&self.team_reserve(), self.audit_key(b"team_reserve"), &amount);
+
+        Ok(())
+    }
+
+    // endpoints - role-gated
+
+    // a duration of 0 disables reservations
+    #[endpoint(setReservationConfig)]
+    fn set_reservation_config(&self, deposit_per_token: BigUint, duration_minutes: u64, forfeit_expired: bool) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.audited_set(
+            &self.reservation_deposit(),
+            self.audit_key(b"reservation_deposit"),
+            &deposit_per_token,
+        );
+        self.audited_set(
+            &self.reservation_duration_minutes(),
+            self.audit_key(b"reservation_duration_minutes"),
+            &duration_minutes,
+        );
+        self.audited_set(
+            &self.reservation_forfeit_expired(),
+            self.audit_key(b"reservation_forfeit_expired"),
+            &forfeit_expired,
+        );
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(reserve)]
+    fn reserve(
+        &self,
+        amount: u32,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(Feature::Mint);
+
+        let duration_minutes = self.reservation_duration_minutes().get();
+        require!(duration_minutes > 0, "reservations disabled");
+        require!(amount > 0, "amount must be positive");
+        require!(self.has_unreserved_supply(amount), "not enough supply left");
+
+        let caller = self.blockchain().get_caller();
+        require!(self.reservations(&caller).is_empty(), "reservation already active");
+
+        let deposit_per_token = self.reservation_deposit().get();
+        let deposit_token = self.payment_token_id().get();
+        if deposit_per_token > 0 {
+            require!(payment_token == deposit_token, "not given token identifier");
+        }
+        require!(
+            payment_amount == &deposit_per_token * amount,
+            "deposit does not match the reserved amount"
+        );
+
+        let expiry_timestamp = self.blockchain().get_block_timestamp() + duration_minutes * SECONDS_PER_MINUTE;
+        self.audited_set(
+            &self.reservations(&caller),
+            self.audit_key_with(b"reservations", &caller),
+            &Reservation {
+                amount,
+                deposit_token: deposit_token.clone(),
+                deposit_per_token,
+                expiry_timestamp,
+            },
+        );
+        self.audited_update(&self.reserved_supply(), self.audit_key(b"reserved_supply"), |v| *v += amount);
+        if payment_amount > 0 {
+            self.protect_inventory(&deposit_token, 0, &payment_amount);
+        }
+    }
+
+    #[endpoint(releaseExpiredReservation)]
+    fn release_expired_reservation(&self, address: ManagedAddress) {
+        require!(!self.reservations(&address).is_empty(), "no reservation");
+
+        let reservation = self.reservations(&address).get();
+        require!(
+            self.blockchain().get_block_timestamp() >= reservation.expiry_timestamp,
+            "reservation not expired"
+        );
+
+        self.audited_clear(&self.reservations(&address), self.audit_key_with(b"reservations", &address));
+        self.audited_update(&self.reserved_supply(), self.audit_key(b"reserved_supply"), |v| {
+            *v -= reservation.amount
+        });
+
+        let deposit = &reservation.deposit_per_token * reservation.amount;
+        if deposit == 0 {
+            return;
+        }
+
+        // a forfeited deposit becomes withdrawable by the treasury
+        self.release_inventory(&reservation.deposit_token, 0, &deposit);
+        if !self.reservation_forfeit_expired().get() {
+            self.send().direct(&address, &reservation.deposit_token, 0, &deposit, &[]);
+        }
+    }
+
+    // private
+
+    // uses one reserved token of the buyer, if any, and refunds its deposit share
+    fn use_reservation(&self, buyer: &ManagedAddress) -> bool {
+        if self.reservations(buyer).is_empty() {
+            return false;
+        }
+
+        let mut reservation = self.reservations(buyer).get();
+        if self.blockchain().get_block_timestamp() >= reservation.expiry_timestamp {
+            return false;
+        }
+
+        reservation.amount -= 1;
+        if reservation.amount == 0 {
+            self.audited_clear(&self.reservations(buyer), self.audit_key_with(b"reservations", buyer));
+        } else {
+            self.audited_set(&self.reservations(buyer), self.audit_key_with(b"reservations", buyer), &reservation);
+        }
+        self.audited_update(&self.reserved_supply(), self.audit_key(b"reserved_supply"), |v| *v -= 1);
+
+        if reservation.deposit_per_token > 0 {
+            self.release_inventory(&reservation.deposit_token, 0, &reservation.deposit_per_token);
+            self.send().direct(buyer, &reservation.deposit_token, 0, &reservation.deposit_per_token, &[]);
+        }
+
+        true
+    }
+
+    fn take_team_reserve(&self, amount: u32) {
+        let team_reserve = self.team_reserve().get();
+        require!(amount <= team_reserve, "not enough team reserve left");
+
+        self.audited_set(&self.team_reserve(), self.audit_key(b"team_reserve"), &(team_reserve - amount));
+        self.audited_update(&self.team_reserve_minted(), self.audit_key(b"team_reserve_minted"), |v| {
+            *v += amount
+        });
+    }
+
+    fn has_unreserved_supply(&self, amount: u32) -> bool {
+        let max_supply = self.max_supply().get();
+        let reserved = self.reserved_supply().get() + self.team_reserve().get();
+
+        max_supply == 0 || self.mint_count().get() + reserved + amount <= max_supply
+    }
+
+    // storage
+
+    #[view(getReservationDeposit)]
+    #[storage_mapper("reservation_deposit")]
+    fn reservation_deposit(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getReservationDurationMinutes)]
+    #[storage_mapper("reservation_duration_minutes")]
+    fn reservation_duration_minutes(&self) -> SingleValueMapper<u64>;
+
+    #[view(isReservationDepositForfeited)]
+    #[storage_mapper("reservation_forfeit_expired")]
+    fn reservation_forfeit_expired(&self) -> SingleValueMapper<bool>;
+
+    // includes expired reservations until they are released
+    #[view(getReservedSupply)]
+    #[storage_mapper("reserved_supply")]
+    fn reserved_supply(&self) -> SingleValueMapper<u32>;
+
+    #[view(getReservation)]
+    #[storage_mapper("reservations")]
+    fn reservations(&self, address: &ManagedAddress) -> SingleValueMapper<Reservation<Self::Api>>;
+
+    // team allocation not minted yet
+    #[view(getTeamReserve)]
+    #[storage_mapper("team_reserve")]
+    fn team_reserve(&self) -> SingleValueMapper<u32>;
+
+    #[view(getTeamReserveMinted)]
+    #[storage_mapper("team_reserve_minted")]
+    fn team_reserve_minted(&self) -> SingleValueMapper<u32>;
+}
@@ -0,0 +1,44 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum RevenueSource {
+    PrimaryMint,
+    MarketplaceFees,
+    Royalties,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct RevenueBreakdown<M: ManagedTypeApi> {
+    pub primary_mint: BigUint<M>,
+    pub marketplace_fees: BigUint<M>,
+    pub royalties: BigUint<M>,
+}
+
+// Revenue is booked per payment token, amounts in different tokens are never added up.
+#[elrond_wasm::module]
+pub trait RevenueModule: crate::audit::AuditModule {
+    fn record_revenue(&self, source: RevenueSource, token_id: &TokenIdentifier, amount: &BigUint) {
+        self.audited_update(
+            &self.revenue_by_source(source, token_id),
+            self.audit_key_with(b"revenue_by_source", &(source, token_id)),
+            |v| *v += amount,
+        );
+    }
+
+    // views
+
+    #[view(getRevenueBreakdown)]
+    fn get_revenue_breakdown(&self, token_id: TokenIdentifier) -> RevenueBreakdown<Self::Api> {
+        RevenueBreakdown {
+            primary_mint: self.revenue_by_source(RevenueSource::PrimaryMint, &token_id).get(),
+            marketplace_fees: self.revenue_by_source(RevenueSource::MarketplaceFees, &token_id).get(),
+            royalties: self.revenue_by_source(RevenueSource::Royalties, &token_id).get(),
+        }
+    }
+
+    // storage
+
+    #[storage_mapper("revenue_by_source")]
+    fn revenue_by_source(&self, source: RevenueSource, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,118 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct RoyaltiesOverride {
+    pub last_mint_id: u32,
+    // in basis points
+    pub royalties: u32,
+}
+
+// Royalties for specific mint id ranges, e.g. 1/1 specials at a higher rate than
+// the collection. Overrides are keyed by the first id of their range, cannot
+// overlap, and only cover ids above everything minted so far, since royalties
+// are fixed at creation. Ids outside every range get the collection royalties.
+// Like `setRoyalties`, overrides are closed while a timelock is set and can only
+// lower the royalties of their range once the sale started.
+#[elrond_wasm::module]
+pub trait RoyaltyOverridesModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::mint_ids::MintIdsModule
+    + crate::storage::StorageModule
+    + crate::timelock::TimelockModule
+{
+    // endpoints - role-gated
+
+    // a single mint is the range `first_mint_id..=first_mint_id`
+    #[endpoint(setRoyaltiesOverride)]
+    fn set_royalties_override(&self, first_mint_id: u32, last_mint_id: u32, royalties: u32) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+        require!(first_mint_id > 0 && first_mint_id <= last_mint_id, "invalid mint id range");
+        require!(first_mint_id > self.highest_mint_id().get(), "range includes minted ids");
+        let max_supply = self.max_supply().get();
+        require!(max_supply == 0 || last_mint_id <= max_supply, "range exceeds max supply");
+        require!(
+            royalties <= self.max_royalties().get(),
+            "royalties cannot exceed the committed maximum"
+        );
+        require!(
+            !self.sale_started() || royalties <= self.royalties().get(),
+            "royalties can only decrease after sale start"
+        );
+        require!(
+            self.royalties_override()
+                .iter()
+                .all(|(first, entry)| last_mint_id < first || first_mint_id > entry.last_mint_id),
+            "range overlaps another override"
+        );
+
+        let entry = RoyaltiesOverride {
+            last_mint_id,
+            royalties,
+        };
+        self.royalties_override().insert(first_mint_id, entry.clone());
+        self.log_entry_added(self.audit_key(b"royalties_override"), &(first_mint_id, entry));
+
+        Ok(())
+    }
+
+    #[endpoint(removeRoyaltiesOverride)]
+    fn remove_royalties_override(&self, first_mint_id: u32) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        let entry = self.royalties_override().remove(&first_mint_id);
+        require!(entry.is_some(), "no override for this range");
+        let entry = entry.unwrap();
+        // the range falls back to the collection royalties
+        require!(
+            !self.sale_started() || entry.royalties >= self.royalties().get(),
+            "royalties can only decrease after sale start"
+        );
+        self.log_entry_removed(self.audit_key(b"royalties_override"), &(first_mint_id, entry));
+
+        Ok(())
+    }
+
+    // private
+
+    fn royalties_for_mint_id(&self, mint_id: u32) -> u32 {
+        self.royalties_override()
+            .iter()
+            .find(|(first, entry)| mint_id >= *first && mint_id <= entry.last_mint_id)
+            .map_or_else(|| self.royalties().get(), |(_, entry)| entry.royalties)
+    }
+
+    // views
+
+    // for the id sequential minting hands out next; recycled and random ids
+    // cannot be known in advance
+    #[view(getRoyaltiesForNext)]
+    fn get_royalties_for_next(&self) -> u32 {
+        let mut mint_id = self.last_sequential_mint_id().get() + 1;
+        while self.mint_id_taken(mint_id).get() {
+            mint_id += 1;
+        }
+
+        self.royalties_for_mint_id(mint_id)
+    }
+
+    // (first mint id, override) for every range
+    #[view(getRoyaltiesOverrides)]
+    fn get_royalties_overrides(&self) -> MultiResultVec<MultiResult2<u32, RoyaltiesOverride>> {
+        self.royalties_override()
+            .iter()
+            .map(|entry| entry.into())
+            .collect::<Vec<MultiResult2<u32, RoyaltiesOverride>>>()
+            .into()
+    }
+
+    // storage
+
+    #[storage_mapper("royalties_override")]
+    fn royalties_override(&self) -> MapMapper<u32, RoyaltiesOverride>;
+}
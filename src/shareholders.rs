@@ -0,0 +1,140 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+const BASIS_POINTS_MAX: u32 = 10_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct Shareholder<M: ManagedTypeApi> {
+    pub address: ManagedAddress<M>,
+    // in basis points, all shares add up to 100%
+    pub share: u32,
+}
+
+// Revenue split for mint proceeds. Once shareholders are registered every primary
+// mint payment is credited to them by share; credited funds are escrowed, so
+// `withdraw` only sends the rounding dust and other revenue to the treasury, and
+// each shareholder claims their own balance. Replacing the shareholders keeps
+// balances already credited.
+#[elrond_wasm::module]
+pub trait ShareholdersModule: crate::audit::AuditModule + crate::inventory::InventoryModule {
+    // endpoints - owner-only
+
+    // (address, share in basis points), an empty list stops the split
+    #[only_owner]
+    #[endpoint(setShareholders)]
+    fn set_shareholders(&self, #[var_args] shareholders: ManagedVarArgs<MultiArg2<ManagedAddress, u32>>) -> SCResult<()> {
+        self.shareholders().clear();
+        self.log_collection_cleared(self.audit_key(b"shareholders"));
+
+        let mut total_share = 0u32;
+        for entry in shareholders.into_iter() {
+            let (address, share) = entry.into_tuple();
+            require!(!address.is_zero(), "invalid shareholder address");
+            require!(share > 0, "share must be positive");
+            total_share += share;
+
+            let shareholder = Shareholder { address, share };
+            self.log_entry_added(self.audit_key(b"shareholders"), &shareholder);
+            self.shareholders().push(&shareholder);
+        }
+        require!(
+            self.shareholders().is_empty() || total_share == BASIS_POINTS_MAX,
+            "shares must add up to 100%"
+        );
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[endpoint(claimShare)]
+    fn claim_share(&self, token_id: TokenIdentifier) -> BigUint {
+        let caller = self.blockchain().get_caller();
+        let balance = self.share_balance(&caller, &token_id).get();
+        require!(balance > 0, "nothing to claim");
+
+        self.audited_clear(
+            &self.share_balance(&caller, &token_id),
+            self.audit_key_with(b"share_balance", &(&caller, &token_id)),
+        );
+        self.release_inventory(&token_id, 0, &balance);
+        self.send().direct(&caller, &token_id, 0, &balance, &[]);
+
+        self.share_claimed_event(&caller, &token_id, &balance);
+
+        balance
+    }
+
+    // private
+
+    fn accrue_shares(&self, token_id: &TokenIdentifier, amount: &BigUint) {
+        if *amount == 0 {
+            return;
+        }
+
+        let mut credited = BigUint::zero();
+        for shareholder in self.shareholders().iter() {
+            let part = amount * shareholder.share / BASIS_POINTS_MAX;
+            if part == 0 {
+                continue;
+            }
+
+            self.audited_update(
+                &self.share_balance(&shareholder.address, token_id),
+                self.audit_key_with(b"share_balance", &(&shareholder.address, token_id)),
+                |v| *v += &part,
+            );
+            self.share_credited_event(&shareholder.address, token_id, &part);
+            credited += part;
+        }
+
+        if credited > 0 {
+            self.protect_inventory(token_id, 0, &credited);
+            self.audited_update(
+                &self.total_distributed(token_id),
+                self.audit_key_with(b"total_distributed", token_id),
+                |v| *v += &credited,
+            );
+        }
+    }
+
+    // views
+
+    #[view(getClaimableAmount)]
+    fn get_claimable_amount(&self, address: ManagedAddress, token_id: TokenIdentifier) -> BigUint {
+        self.share_balance(&address, &token_id).get()
+    }
+
+    // events
+
+    #[event("share_credited")]
+    fn share_credited_event(
+        &self,
+        #[indexed] shareholder: &ManagedAddress,
+        #[indexed] token_id: &TokenIdentifier,
+        amount: &BigUint,
+    );
+
+    #[event("share_claimed")]
+    fn share_claimed_event(
+        &self,
+        #[indexed] shareholder: &ManagedAddress,
+        #[indexed] token_id: &TokenIdentifier,
+        amount: &BigUint,
+    );
+
+    // storage
+
+    #[view(getShareholders)]
+    #[storage_mapper("shareholders")]
+    fn shareholders(&self) -> VecMapper<Shareholder<Self::Api>>;
+
+    // credited to shareholders over the contract's lifetime, claimed or not
+    #[view(getTotalDistributed)]
+    #[storage_mapper("total_distributed")]
+    fn total_distributed(&self, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getShareBalance)]
+    #[storage_mapper("share_balance")]
+    fn share_balance(&self, shareholder: &ManagedAddress, token_id: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,91 @@
+elrond_wasm::imports!();
+
+use crate::access_control::Role;
+
+pub const ED25519_SIGNATURE_LENGTH: usize = 64;
+
+// Shared verification for every off-chain signed flow. The signed message is
+//   purpose | contract address | chain id | user | nonce | expiry | payload
+// so a signature is only valid for one flow, one deployment, one user and one use.
+#[elrond_wasm::module]
+pub trait SignaturesModule: crate::access_control::AccessControlModule + crate::audit::AuditModule {
+    // endpoints - role-gated
+
+    #[endpoint(setSigner)]
+    fn set_signer(&self, signer: ManagedAddress) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.audited_set(&self.signer(), self.audit_key(b"signer"), &signer);
+
+        Ok(())
+    }
+
+    // e.g. "D" for devnet, "1" for mainnet
+    #[only_owner]
+    #[endpoint(setChainId)]
+    fn set_chain_id(&self, chain_id: ManagedBuffer) -> SCResult<()> {
+        require!(!chain_id.is_empty(), "invalid chain id");
+
+        self.audited_set(&self.chain_id(), self.audit_key(b"chain_id"), &chain_id);
+
+        Ok(())
+    }
+
+    // private
+
+    // consumes the user's nonce, fails on any mismatch
+    fn verify_signature(
+        &self,
+        purpose: &[u8],
+        user: &ManagedAddress,
+        nonce: u64,
+        expiry: u64,
+        payload: &ManagedBuffer,
+        signature: &ManagedBuffer,
+    ) {
+        require!(!self.signer().is_empty(), "signer not set");
+        require!(!self.chain_id().is_empty(), "chain id not set");
+        require!(self.blockchain().get_block_timestamp() < expiry, "signature expired");
+        require!(nonce == self.signature_nonce(user).get(), "invalid signature nonce");
+        require!(signature.len() == ED25519_SIGNATURE_LENGTH, "invalid signature length");
+
+        let mut message = ManagedBuffer::new_from_bytes(purpose);
+        message.append(self.blockchain().get_sc_address().as_managed_buffer());
+        message.append(&self.chain_id().get());
+        message.append(user.as_managed_buffer());
+        message.append_bytes(&nonce.to_be_bytes());
+        message.append_bytes(&expiry.to_be_bytes());
+        message.append(payload);
+
+        let signer = self.signer().get().to_address();
+        require!(
+            self.crypto().verify_ed25519(
+                signer.as_bytes(),
+                message.to_boxed_bytes().as_slice(),
+                signature.to_boxed_bytes().as_slice(),
+            ),
+            "invalid signature"
+        );
+
+        self.audited_set(
+            &self.signature_nonce(user),
+            self.audit_key_with(b"signature_nonce", user),
+            &(nonce + 1),
+        );
+    }
+
+    // storage
+
+    #[view(getSigner)]
+    #[storage_mapper("signer")]
+    fn signer(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getChainId)]
+    #[storage_mapper("chain_id")]
+    fn chain_id(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    // next nonce the user must sign with
+    #[view(getSignatureNonce)]
+    #[storage_mapper("signature_nonce")]
+    fn signature_nonce(&self, user: &ManagedAddress) -> SingleValueMapper<u64>;
+}
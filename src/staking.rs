@@ -0,0 +1,634 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::pause::Feature;
+
+const STAKED_NFT_AMOUNT: u32 = 1;
+// scale of reward_per_weight, so small per-weight rewards are not rounded away
+const REWARD_PRECISION: u64 = 1_000_000_000_000_000_000;
+// weight of one staked NFT unless configured otherwise
+const DEFAULT_NFT_STAKE_WEIGHT: u64 = 1_000_000_000_000_000_000;
+const BASIS_POINTS_MAX: u32 = 10_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct StakingPosition<M: ManagedTypeApi> {
+    pub staked_nonces: ManagedVec<M, u64>,
+    pub unbonding_nonces: ManagedVec<M, u64>,
+    pub stake_weight: BigUint<M>,
+    pub claimable_rewards: BigUint<M>,
+    pub compounded_rewards: BigUint<M>,
+    pub auto_compound: bool,
+}
+
+// Custody of collection NFTs staked by their holders. Staked tokens stay attributed
+// to the staker and are protected in the inventory ledger while held.
+//
+// Rewards in the configured token are streamed from a funded reserve at a fixed
+// rate per second and shared by stake weight through a reward-per-weight
+// accumulator. Each staked NFT adds the NFT weight; stakers who turn on
+// auto-compounding have their rewards locked into the position instead, where
+// every compounded token adds one unit of weight until the position is closed.
+//
+// With an unbonding period set, unstaked NFTs wait out the period without weight
+// before they can be withdrawn. Stakers can skip the wait by giving up a share of
+// their claimable rewards, and of the compounded rewards if that closes the
+// position, and/or paying the exact fee in the reward token; the penalty is
+// shared between the remaining stakers, or goes back to the reserve if none are left.
+#[elrond_wasm::module]
+pub trait StakingModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::holders::HoldersModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+    + crate::storage::StorageModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(setStakingRewardToken)]
+    fn set_staking_reward_token(&self, token_id: TokenIdentifier) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        require!(
+            self.staking_reward_reserve().get() == 0 && self.total_stake_weight().get() == 0,
+            "staking rewards already in use"
+        );
+
+        self.audited_set(&self.staking_reward_token_id(), self.audit_key(b"staking_reward_token_id"), &token_id);
+
+        Ok(())
+    }
+
+    // reward tokens streamed per second across all stakers
+    #[endpoint(setStakingRewardRate)]
+    fn set_staking_reward_rate(&self, reward_rate: BigUint) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.update_staking_rewards();
+        self.audited_set(&self.staking_reward_rate(), self.audit_key(b"staking_reward_rate"), &reward_rate);
+
+        Ok(())
+    }
+
+    #[endpoint(setNftStakeWeight)]
+    fn set_nft_stake_weight(&self, weight: BigUint) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        require!(self.total_staked().get() == 0, "cannot change weight while NFTs are staked");
+        require!(weight > 0, "weight must be positive");
+
+        self.audited_set(&self.nft_stake_weight(), self.audit_key(b"nft_stake_weight"), &weight);
+
+        Ok(())
+    }
+
+    // 0 returns unstaked NFTs right away
+    #[endpoint(setUnbondingPeriod)]
+    fn set_unbonding_period(&self, unbonding_seconds: u64) -> SCResult<()> {
+        self.require_role(Role::Admin);
+
+        self.audited_set(&self.unbonding_period(), self.audit_key(b"unbonding_period"), &unbonding_seconds);
+
+        Ok(())
+    }
+
+    // `reward_penalty` in basis points of the claimable rewards, `fee` in the reward token
+    #[endpoint(setEarlyUnstakePenalty)]
+    fn set_early_unstake_penalty(&self, reward_penalty: u32, fee: BigUint) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        require!(reward_penalty <= BASIS_POINTS_MAX, "penalty cannot exceed 100%");
+        require!(
+            fee == 0 || !self.staking_reward_token_id().is_empty(),
+            "staking reward token not set"
+        );
+
+        self.audited_set(
+            &self.early_unstake_reward_penalty(),
+            self.audit_key(b"early_unstake_reward_penalty"),
+            &reward_penalty,
+        );
+        self.audited_set(&self.early_unstake_fee(), self.audit_key(b"early_unstake_fee"), &fee);
+
+        Ok(())
+    }
+
+    #[payable("*")]
+    #[endpoint(fundStakingRewards)]
+    fn fund_staking_rewards(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_role(Role::Treasurer);
+        require!(
+            payment_token == self.staking_reward_token_id().get(),
+            "not given token identifier"
+        );
+        require!(payment_amount > 0, "amount must be positive");
+
+        self.update_staking_rewards();
+        self.audited_update(
+            &self.staking_reward_reserve(),
+            self.audit_key(b"staking_reward_reserve"),
+            |v| *v += &payment_amount,
+        );
+        self.protect_inventory(&payment_token, 0, &payment_amount);
+    }
+
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(stake)]
+    fn stake(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_nonce] payment_nonce: u64,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(Feature::Staking);
+        require!(payment_token == self.nft_token_id().get(), "only collection NFTs can be staked");
+        require!(payment_amount == STAKED_NFT_AMOUNT, "invalid amount");
+
+        let caller = self.blockchain().get_caller();
+        self.update_staking_rewards();
+        self.settle_staker(&caller);
+
+        if self.staked_nonces(&caller).insert(payment_nonce) {
+            self.log_entry_added(self.audit_key_with(b"staked_nonces", &caller), &payment_nonce);
+        }
+        self.audited_set(
+            &self.stake_timestamp(payment_nonce),
+            self.audit_key_with(b"stake_timestamp", &payment_nonce),
+            &self.blockchain().get_block_timestamp(),
+        );
+        self.audited_update(&self.total_staked(), self.audit_key(b"total_staked"), |v| *v += 1);
+        self.protect_inventory(&payment_token, payment_nonce, &payment_amount);
+        // staked tokens stay attributed to the staker while in custody
+        self.reattribute_token(&caller, payment_nonce);
+
+        let weight = self.stake_weight(&caller).get() + self.get_nft_stake_weight();
+        self.set_stake_weight(&caller, weight);
+    }
+
+    #[endpoint(unstake)]
+    fn unstake(&self, nonce: u64) {
+        let caller = self.blockchain().get_caller();
+        require!(self.staked_nonces(&caller).contains(&nonce), "token not staked by caller");
+
+        self.update_staking_rewards();
+        self.remove_stake(&caller, nonce, 0);
+
+        let unbonding_period = self.unbonding_period().get();
+        if unbonding_period == 0 {
+            self.return_staked_nft(&caller, nonce);
+            return;
+        }
+
+        // no weight while unbonding, so nothing accrues
+        if self.unbonding_nonces(&caller).insert(nonce) {
+            self.log_entry_added(self.audit_key_with(b"unbonding_nonces", &caller), &nonce);
+        }
+        self.audited_set(
+            &self.unbond_timestamp(nonce),
+            self.audit_key_with(b"unbond_timestamp", &nonce),
+            &(self.blockchain().get_block_timestamp() + unbonding_period),
+        );
+    }
+
+    // returns every NFT of the caller whose unbonding period is over
+    #[endpoint(withdrawUnstaked)]
+    fn withdraw_unstaked(&self) -> usize {
+        let caller = self.blockchain().get_caller();
+        let now = self.blockchain().get_block_timestamp();
+        let ready: Vec<u64> = self
+            .unbonding_nonces(&caller)
+            .iter()
+            .filter(|nonce| now >= self.unbond_timestamp(*nonce).get())
+            .collect();
+        require!(!ready.is_empty(), "nothing to withdraw");
+
+        for nonce in ready.iter() {
+            self.unbonding_nonces(&caller).swap_remove(nonce);
+            self.log_entry_removed(self.audit_key_with(b"unbonding_nonces", &caller), nonce);
+            self.audited_clear(&self.unbond_timestamp(*nonce), self.audit_key_with(b"unbond_timestamp", nonce));
+            self.return_staked_nft(&caller, *nonce);
+        }
+
+        ready.len()
+    }
+
+    // returns a staked or unbonding NFT right away, against the early unstake penalty
+    #[payable("*")]
+    #[endpoint(unstakeEarly)]
+    fn unstake_early(
+        &self,
+        nonce: u64,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let staked = self.staked_nonces(&caller).contains(&nonce);
+        require!(
+            staked || self.unbonding_nonces(&caller).contains(&nonce),
+            "token not staked by caller"
+        );
+
+        let fee = self.early_unstake_fee().get();
+        if fee > 0 {
+            require!(
+                payment_token == self.staking_reward_token_id().get(),
+                "not given token identifier"
+            );
+            require!(payment_amount == fee, "payment does not match the fee");
+        } else {
+            require!(payment_amount == 0, "no fee required");
+        }
+
+        // compounded rewards released by closing the position are penalized as well
+        let penalty_rate = self.early_unstake_reward_penalty().get();
+        let mut reward_penalty = BigUint::zero();
+        self.update_staking_rewards();
+        if staked {
+            reward_penalty = self.remove_stake(&caller, nonce, penalty_rate);
+        } else {
+            self.unbonding_nonces(&caller).swap_remove(&nonce);
+            self.log_entry_removed(self.audit_key_with(b"unbonding_nonces", &caller), &nonce);
+            self.audited_clear(&self.unbond_timestamp(nonce), self.audit_key_with(b"unbond_timestamp", &nonce));
+        }
+
+        // the caller's weight is gone, so the penalty only reaches the others
+        let pending = self.pending_staking_rewards(&caller).get();
+        let pending_penalty = &pending * penalty_rate / BASIS_POINTS_MAX;
+        if pending_penalty > 0 {
+            self.audited_set(
+                &self.pending_staking_rewards(&caller),
+                self.audit_key_with(b"pending_staking_rewards", &caller),
+                &(pending - &pending_penalty),
+            );
+            reward_penalty += &pending_penalty;
+        }
+        if reward_penalty > 0 {
+            self.redistribute_penalty(&reward_penalty);
+        }
+        if payment_amount > 0 {
+            self.protect_inventory(&payment_token, 0, &payment_amount);
+            self.redistribute_penalty(&payment_amount);
+        }
+
+        self.return_staked_nft(&caller, nonce);
+    }
+
+    #[endpoint(claimStakingRewards)]
+    fn claim_staking_rewards(&self) {
+        self.require_feature_active(Feature::Claims);
+
+        let caller = self.blockchain().get_caller();
+        self.update_staking_rewards();
+        self.settle_staker(&caller);
+
+        let pending = self.pending_staking_rewards(&caller).get();
+        require!(pending > 0, "nothing to claim");
+
+        self.audited_clear(
+            &self.pending_staking_rewards(&caller),
+            self.audit_key_with(b"pending_staking_rewards", &caller),
+        );
+        self.pay_staking_rewards(&caller, &pending);
+    }
+
+    #[endpoint(setAutoCompound)]
+    fn set_auto_compound(&self, enabled: bool) {
+        let caller = self.blockchain().get_caller();
+        self.update_staking_rewards();
+        self.settle_staker(&caller);
+
+        self.audited_set(&self.auto_compound(&caller), self.audit_key_with(b"auto_compound", &caller), &enabled);
+    }
+
+    // private
+
+    // settles the staker and takes the NFT out of its position, rewards must be up to date;
+    // returns what `reward_penalty` basis points withheld from released compounded rewards
+    fn remove_stake(&self, address: &ManagedAddress, nonce: u64, reward_penalty: u32) -> BigUint {
+        self.settle_staker(address);
+
+        self.staked_nonces(address).swap_remove(&nonce);
+        self.log_entry_removed(self.audit_key_with(b"staked_nonces", address), &nonce);
+        self.audited_clear(&self.stake_timestamp(nonce), self.audit_key_with(b"stake_timestamp", &nonce));
+        self.audited_update(&self.total_staked(), self.audit_key(b"total_staked"), |v| *v -= 1);
+
+        let weight = self.stake_weight(address).get() - self.get_nft_stake_weight();
+        self.set_stake_weight(address, weight);
+
+        // closing the position unlocks what was compounded into it
+        if self.staked_nonces(address).is_empty() {
+            return self.release_compounded_rewards(address, reward_penalty);
+        }
+
+        BigUint::zero()
+    }
+
+    fn return_staked_nft(&self, address: &ManagedAddress, nonce: u64) {
+        let nft_token_id = self.nft_token_id().get();
+        let amount = BigUint::from(STAKED_NFT_AMOUNT);
+        self.release_inventory(&nft_token_id, nonce, &amount);
+        self.send().direct(address, &nft_token_id, nonce, &amount, &[]);
+    }
+
+    fn get_nft_stake_weight(&self) -> BigUint {
+        if self.nft_stake_weight().is_empty() {
+            return BigUint::from(DEFAULT_NFT_STAKE_WEIGHT);
+        }
+
+        self.nft_stake_weight().get()
+    }
+
+    // streams the rewards due since the last update into the accumulator
+    fn update_staking_rewards(&self) {
+        let now = self.blockchain().get_block_timestamp();
+        let last_update = self.last_reward_timestamp().get();
+        if now <= last_update {
+            return;
+        }
+
+        let total_weight = self.total_stake_weight().get();
+        if total_weight > 0 {
+            let reserve = self.staking_reward_reserve().get();
+            let mut reward = BigUint::from(now - last_update) * self.staking_reward_rate().get();
+            if reward > reserve {
+                reward = reserve;
+            }
+
+            if reward > 0 {
+                self.audited_update(
+                    &self.staking_reward_reserve(),
+                    self.audit_key(b"staking_reward_reserve"),
+                    |v| *v -= &reward,
+                );
+                self.distribute_to_stakers(&reward);
+            }
+        }
+
+        self.audited_set(&self.last_reward_timestamp(), self.audit_key(b"last_reward_timestamp"), &now);
+    }
+
+    // shares `amount` between the current total weight, nothing happens without stakers
+    fn distribute_to_stakers(&self, amount: &BigUint) {
+        let total_weight = self.total_stake_weight().get();
+        if total_weight == 0 {
+            return;
+        }
+
+        let increment = amount * &BigUint::from(REWARD_PRECISION) / total_weight;
+        self.audited_update(&self.reward_per_weight(), self.audit_key(b"reward_per_weight"), |v| *v += &increment);
+    }
+
+    // shares an already protected penalty between the current stakers; the rounding
+    // remainder, or everything when nobody is staked, goes back to the reserve
+    fn redistribute_penalty(&self, amount: &BigUint) {
+        let total_weight = self.total_stake_weight().get();
+        let mut distributed = BigUint::zero();
+        if total_weight > 0 {
+            let increment = amount * &BigUint::from(REWARD_PRECISION) / &total_weight;
+            distributed = &increment * &total_weight / REWARD_PRECISION;
+            self.audited_update(&self.reward_per_weight(), self.audit_key(b"reward_per_weight"), |v| *v += &increment);
+        }
+
+        let remainder = amount - &distributed;
+        if remainder > 0 {
+            self.audited_update(
+                &self.staking_reward_reserve(),
+                self.audit_key(b"staking_reward_reserve"),
+                |v| *v += &remainder,
+            );
+        }
+    }
+
+    fn accrued_rewards(&self, address: &ManagedAddress) -> BigUint {
+        let weight = self.stake_weight(address).get();
+        let earned = weight * self.reward_per_weight().get() / REWARD_PRECISION;
+
+        earned - self.reward_debt(address).get()
+    }
+
+    // books what the staker earned since its last settlement
+    fn settle_staker(&self, address: &ManagedAddress) {
+        let accrued = self.accrued_rewards(address);
+        if accrued > 0 {
+            if self.auto_compound(address).get() {
+                self.audited_update(
+                    &self.compounded_rewards(address),
+                    self.audit_key_with(b"compounded_rewards", address),
+                    |v| *v += &accrued,
+                );
+                let weight = self.stake_weight(address).get() + &accrued;
+                self.set_stake_weight(address, weight);
+                return;
+            }
+
+            self.audited_update(
+                &self.pending_staking_rewards(address),
+                self.audit_key_with(b"pending_staking_rewards", address),
+                |v| *v += &accrued,
+            );
+        }
+
+        self.sync_reward_debt(address);
+    }
+
+    // callers settle first, so the debt can be reset to the new weight
+    fn set_stake_weight(&self, address: &ManagedAddress, weight: BigUint) {
+        let previous = self.stake_weight(address).get();
+        self.audited_update(&self.total_stake_weight(), self.audit_key(b"total_stake_weight"), |v| {
+            *v += &weight;
+            *v -= &previous;
+        });
+        self.audited_set(&self.stake_weight(address), self.audit_key_with(b"stake_weight", address), &weight);
+
+        self.sync_reward_debt(address);
+    }
+
+    fn sync_reward_debt(&self, address: &ManagedAddress) {
+        let debt = self.stake_weight(address).get() * self.reward_per_weight().get() / REWARD_PRECISION;
+        self.audited_set(&self.reward_debt(address), self.audit_key_with(b"reward_debt", address), &debt);
+    }
+
+    // pays out all but `reward_penalty` basis points, the withheld part stays protected
+    fn release_compounded_rewards(&self, address: &ManagedAddress, reward_penalty: u32) -> BigUint {
+        let compounded = self.compounded_rewards(address).get();
+        if compounded == 0 {
+            return BigUint::zero();
+        }
+
+        self.audited_clear(
+            &self.compounded_rewards(address),
+            self.audit_key_with(b"compounded_rewards", address),
+        );
+        let weight = self.stake_weight(address).get() - &compounded;
+        self.set_stake_weight(address, weight);
+
+        let withheld = &compounded * reward_penalty / BASIS_POINTS_MAX;
+        let payout = compounded - &withheld;
+        if payout > 0 {
+            self.pay_staking_rewards(address, &payout);
+        }
+
+        withheld
+    }
+
+    fn pay_staking_rewards(&self, address: &ManagedAddress, amount: &BigUint) {
+        let reward_token_id = self.staking_reward_token_id().get();
+        self.release_inventory(&reward_token_id, 0, amount);
+        self.send().direct(address, &reward_token_id, 0, amount, &[]);
+    }
+
+    // views
+
+    #[view(getStakedNonces)]
+    fn get_staked_nonces(&self, address: ManagedAddress) -> MultiResultVec<u64> {
+        self.staked_nonces(&address).iter().collect::<Vec<u64>>().into()
+    }
+
+    // seconds since the address's oldest current stake, 0 if nothing is staked
+    #[view(getLongestStakeDuration)]
+    fn get_longest_stake_duration(&self, address: ManagedAddress) -> u64 {
+        let now = self.blockchain().get_block_timestamp();
+
+        self.staked_nonces(&address)
+            .iter()
+            .map(|nonce| now - self.stake_timestamp(nonce).get())
+            .max()
+            .unwrap_or(0)
+    }
+
+    // (nonce, timestamp from which it can be withdrawn)
+    #[view(getPendingUnbonds)]
+    fn get_pending_unbonds(&self, address: ManagedAddress) -> MultiResultVec<MultiResult2<u64, u64>> {
+        self.unbonding_nonces(&address)
+            .iter()
+            .map(|nonce| (nonce, self.unbond_timestamp(nonce).get()).into())
+            .collect::<Vec<MultiResult2<u64, u64>>>()
+            .into()
+    }
+
+    // everything the staking page shows for one address, in one query
+    #[view(getStakingPosition)]
+    fn get_staking_position(&self, address: ManagedAddress) -> StakingPosition<Self::Api> {
+        let mut staked_nonces = ManagedVec::new();
+        for nonce in self.staked_nonces(&address).iter() {
+            staked_nonces.push(nonce);
+        }
+        let mut unbonding_nonces = ManagedVec::new();
+        for nonce in self.unbonding_nonces(&address).iter() {
+            unbonding_nonces.push(nonce);
+        }
+
+        StakingPosition {
+            staked_nonces,
+            unbonding_nonces,
+            stake_weight: self.stake_weight(&address).get(),
+            claimable_rewards: self.get_claimable_staking_rewards(address.clone()),
+            compounded_rewards: self.compounded_rewards(&address).get(),
+            auto_compound: self.auto_compound(&address).get(),
+        }
+    }
+
+    // claimable rewards, as they would be after settling now (0 when compounding)
+    #[view(getClaimableStakingRewards)]
+    fn get_claimable_staking_rewards(&self, address: ManagedAddress) -> BigUint {
+        let mut reward_per_weight = self.reward_per_weight().get();
+        let total_weight = self.total_stake_weight().get();
+        let elapsed = self.blockchain().get_block_timestamp() - self.last_reward_timestamp().get();
+        if total_weight > 0 && elapsed > 0 {
+            let mut reward = BigUint::from(elapsed) * self.staking_reward_rate().get();
+            let reserve = self.staking_reward_reserve().get();
+            if reward > reserve {
+                reward = reserve;
+            }
+            reward_per_weight += reward * REWARD_PRECISION / total_weight;
+        }
+
+        let pending = self.pending_staking_rewards(&address).get();
+        if self.auto_compound(&address).get() {
+            return pending;
+        }
+
+        let earned = self.stake_weight(&address).get() * reward_per_weight / REWARD_PRECISION;
+        pending + earned - self.reward_debt(&address).get()
+    }
+
+    // storage
+
+    #[storage_mapper("staked_nonces")]
+    fn staked_nonces(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[view(getStakeTimestamp)]
+    #[storage_mapper("stake_timestamp")]
+    fn stake_timestamp(&self, nonce: u64) -> SingleValueMapper<u64>;
+
+    #[view(getTotalStaked)]
+    #[storage_mapper("total_staked")]
+    fn total_staked(&self) -> SingleValueMapper<u32>;
+
+    #[view(getUnbondingPeriod)]
+    #[storage_mapper("unbonding_period")]
+    fn unbonding_period(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("unbonding_nonces")]
+    fn unbonding_nonces(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("unbond_timestamp")]
+    fn unbond_timestamp(&self, nonce: u64) -> SingleValueMapper<u64>;
+
+    #[view(getEarlyUnstakeRewardPenalty)]
+    #[storage_mapper("early_unstake_reward_penalty")]
+    fn early_unstake_reward_penalty(&self) -> SingleValueMapper<u32>;
+
+    #[view(getEarlyUnstakeFee)]
+    #[storage_mapper("early_unstake_fee")]
+    fn early_unstake_fee(&self) -> SingleValueMapper<BigUint>;
+
+    // rewards
+
+    #[view(getStakingRewardTokenId)]
+    #[storage_mapper("staking_reward_token_id")]
+    fn staking_reward_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getStakingRewardRate)]
+    #[storage_mapper("staking_reward_rate")]
+    fn staking_reward_rate(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getStakingRewardReserve)]
+    #[storage_mapper("staking_reward_reserve")]
+    fn staking_reward_reserve(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("nft_stake_weight")]
+    fn nft_stake_weight(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("last_reward_timestamp")]
+    fn last_reward_timestamp(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("reward_per_weight")]
+    fn reward_per_weight(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalStakeWeight)]
+    #[storage_mapper("total_stake_weight")]
+    fn total_stake_weight(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getStakeWeight)]
+    #[storage_mapper("stake_weight")]
+    fn stake_weight(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("reward_debt")]
+    fn reward_debt(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("pending_staking_rewards")]
+    fn pending_staking_rewards(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getCompoundedRewards)]
+    #[storage_mapper("compounded_rewards")]
+    fn compounded_rewards(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(isAutoCompounding)]
+    #[storage_mapper("auto_compound")]
+    fn auto_compound(&self, address: &ManagedAddress) -> SingleValueMapper<bool>;
+}
@@ -0,0 +1,73 @@
+elrond_wasm::derive_imports!();
+
+// States returned by views; new variants are only ever appended so existing
+// discriminants stay stable for SDK consumers.
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum SaleState {
+    NotStarted,
+    Active,
+    Paused,
+    SoldOut,
+    Ended,
+}
+
+// `Sold` and `Cancelled` describe the token's last fixed-price listing
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum ListingState {
+    None,
+    Active,
+    Sold,
+    Cancelled,
+}
+
+// `Ended` once the deadline passed until someone ends the auction, `Settled` after
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum AuctionState {
+    None,
+    Running,
+    Ended,
+    Settled,
+}
+
+// registration runs from the raffle start until its end
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum RaffleState {
+    None,
+    Registration,
+    RegistrationClosed,
+    Drawn,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum RedemptionState {
+    None,
+    Pending,
+    Fulfilled,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum UpgradeProposalState {
+    None,
+    Pending,
+    Vetoed,
+    Executed,
+    Cancelled,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum SalePhase {
+    None,
+    Presale,
+    Public,
+    Closed,
+}
+
+// token issuance and the contract's own local roles
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum SetupStatus {
+    NotStarted,
+    Pending,
+    Done,
+    Failed,
+}
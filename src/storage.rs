@@ -0,0 +1,176 @@
+elrond_wasm::imports!();
+
+use crate::states::SetupStatus;
+
+#[elrond_wasm::module]
+pub trait StorageModule {
+    #[view(getNftTokenId)]
+    #[storage_mapper("nft_token_id")]
+    fn nft_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    // set while the issue request awaits its callback
+    #[storage_mapper("nft_issue_pending")]
+    fn nft_issue_pending(&self) -> SingleValueMapper<bool>;
+
+    #[view(areLocalRolesSet)]
+    #[storage_mapper("local_roles_set")]
+    fn local_roles_set(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("local_roles_pending")]
+    fn local_roles_pending(&self) -> SingleValueMapper<bool>;
+
+    // kept past the callback, so a failure can be retried
+    #[storage_mapper("issue_status")]
+    fn issue_status(&self) -> SingleValueMapper<SetupStatus>;
+
+    #[storage_mapper("roles_status")]
+    fn roles_status(&self) -> SingleValueMapper<SetupStatus>;
+
+    // chosen at issuance, the transfer role comes with the local roles
+    #[view(isSoulbound)]
+    #[storage_mapper("soulbound")]
+    fn soulbound(&self) -> SingleValueMapper<bool>;
+
+    // irreversible, set by `stopCreation`
+    #[view(isCreationStopped)]
+    #[storage_mapper("creation_stopped")]
+    fn creation_stopped(&self) -> SingleValueMapper<bool>;
+
+    // addresses other than this contract granted the create role by `setLocalRoles`
+    #[storage_mapper("create_role_addresses")]
+    fn create_role_addresses(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    // the token manager the collection was handed to, empty while it is managed here
+    #[view(getCollectionNewOwner)]
+    #[storage_mapper("collection_new_owner")]
+    fn collection_new_owner(&self) -> SingleValueMapper<ManagedAddress>;
+
+    // token properties chosen at issuance
+    #[view(canFreeze)]
+    #[storage_mapper("can_freeze")]
+    fn can_freeze(&self) -> SingleValueMapper<bool>;
+
+    #[view(canWipe)]
+    #[storage_mapper("can_wipe")]
+    fn can_wipe(&self) -> SingleValueMapper<bool>;
+
+    // addresses holding the transfer role of a soulbound collection
+    #[storage_mapper("transfer_role_addresses")]
+    fn transfer_role_addresses(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getNftTokenPrice)]
+    #[storage_mapper("nft_token_price")]
+    fn nft_token_price(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getPaymentTokenId)]
+    #[storage_mapper("payment_token_id")]
+    fn payment_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(isPaused)]
+    #[storage_mapper("paused")]
+    fn paused(&self) -> SingleValueMapper<bool>;
+
+    // 0 for deployments older than the version tracking
+    #[view(getVersion)]
+    #[storage_mapper("contract_version")]
+    fn contract_version(&self) -> SingleValueMapper<u32>;
+
+    #[view(getMintCount)]
+    #[storage_mapper("mint_count")]
+    fn mint_count(&self) -> SingleValueMapper<u32>;
+
+    // public mints from smart contract callers are rejected unless allowed
+    #[view(areContractCallersAllowed)]
+    #[storage_mapper("contract_callers_allowed")]
+    fn contract_callers_allowed(&self) -> SingleValueMapper<bool>;
+
+    // tokens burned through `burn`; they stay counted as minted
+    #[view(getBurnedCount)]
+    #[storage_mapper("burned_count")]
+    fn burned_count(&self) -> SingleValueMapper<u32>;
+
+    // paid in the payment token for every burned token
+    #[view(getBurnRefund)]
+    #[storage_mapper("burn_refund")]
+    fn burn_refund(&self) -> SingleValueMapper<BigUint>;
+
+    // 0 means unlimited
+    #[view(getMaxSupply)]
+    #[storage_mapper("max_supply")]
+    fn max_supply(&self) -> SingleValueMapper<u32>;
+
+    // 0 means unlimited
+    #[view(getMaxMintsPerAddress)]
+    #[storage_mapper("max_mints_per_address")]
+    fn max_mints_per_address(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("max_mints_per_tx")]
+    fn max_mints_per_tx(&self) -> SingleValueMapper<u32>;
+
+    // 0 means unlimited
+    #[view(getMaxMintsPerBlock)]
+    #[storage_mapper("max_mints_per_block")]
+    fn max_mints_per_block(&self) -> SingleValueMapper<u32>;
+
+    #[view(getMintsInBlock)]
+    #[storage_mapper("mints_in_block")]
+    fn mints_in_block(&self, block_nonce: u64) -> SingleValueMapper<u32>;
+
+    #[view(getMintsForAddress)]
+    #[storage_mapper("mints_per_address")]
+    fn mints_per_address(&self, address: &ManagedAddress) -> SingleValueMapper<u32>;
+
+    // mints made through signed allowlist grants
+    #[view(getAllowlistMints)]
+    #[storage_mapper("allowlist_mints")]
+    fn allowlist_mints(&self, address: &ManagedAddress) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("sale_start_timestamp")]
+    fn sale_start_timestamp(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("sale_end_timestamp")]
+    fn sale_end_timestamp(&self) -> SingleValueMapper<u64>;
+
+    // base metadatas
+
+    #[view(getNftTokenName)]
+    #[storage_mapper("nft_token_name")]
+    fn nft_token_name(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getRoyalties)]
+    #[storage_mapper("royalties")]
+    fn royalties(&self) -> SingleValueMapper<u32>;
+
+    #[view(getMaxRoyalties)]
+    #[storage_mapper("max_royalties")]
+    fn max_royalties(&self) -> SingleValueMapper<u32>;
+
+    #[view(getProvenanceHash)]
+    #[storage_mapper("provenance_hash")]
+    fn provenance_hash(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getImageBaseUri)]
+    #[storage_mapper("image_base_uri")]
+    fn image_base_uri(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getMetadataBaseUri)]
+    #[storage_mapper("metadata_base_uri")]
+    fn metadata_base_uri(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getMetadataCid)]
+    #[storage_mapper("metadata_cid")]
+    fn metadata_cid(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getMetadataHash)]
+    #[storage_mapper("metadata_hash")]
+    fn metadata_hash(&self, mint_id: u32) -> SingleValueMapper<ManagedBuffer>;
+
+    // set by mintWithMessage
+    #[view(getMintMessage)]
+    #[storage_mapper("mint_message")]
+    fn mint_message(&self, mint_id: u32) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getTags)]
+    #[storage_mapper("tags")]
+    fn tags(&self) -> SingleValueMapper<ManagedBuffer>;
+}
@@ -0,0 +1,212 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq)]
+pub enum TimelockAction {
+    Price,
+    Royalties,
+    ImageBaseUri,
+    MetadataBaseUri,
+    TimelockDelay,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct PendingAction<M: ManagedTypeApi> {
+    pub action: TimelockAction,
+    // the argument the direct setter takes, top-encoded
+    pub value: ManagedBuffer<M>,
+    pub executable_at: u64,
+}
+
+// Delay on parameter changes holders care about. Once a delay is set the direct
+// setters for price, royalties and base URIs are closed; changes are proposed,
+// published in `getPendingActions`, and can only be executed after the delay.
+// Other pricing setters (sale phases, dutch auction, price tiers, presale price,
+// royalty overrides, pieces, editions, payment token migration) have no proposal
+// flow and stay closed while a delay is set.
+// The delay itself can be raised at once but only lowered through a proposal,
+// and treasury changes wait at least as long before they can be accepted.
+#[elrond_wasm::module]
+pub trait TimelockModule:
+    crate::access_control::AccessControlModule + crate::audit::AuditModule + crate::storage::StorageModule
+{
+    // endpoints - owner-only
+
+    #[only_owner]
+    #[endpoint(increaseTimelockDelay)]
+    fn increase_timelock_delay(&self, delay: u64) -> SCResult<()> {
+        require!(delay > self.timelock_delay().get(), "timelock delay can only increase");
+
+        self.audited_set(&self.timelock_delay(), self.audit_key(b"timelock_delay"), &delay);
+
+        Ok(())
+    }
+
+    // endpoints - role-gated
+
+    // `value` is the argument the matching direct setter takes
+    #[endpoint(proposeAction)]
+    fn propose_action(&self, action: TimelockAction, value: ManagedBuffer) -> u32 {
+        self.require_action_permission(action);
+        self.validate_action_value(action, &value);
+
+        let action_id = self.pending_action_count().get() + 1;
+        let executable_at = self.blockchain().get_block_timestamp() + self.timelock_delay().get();
+        let pending_action = PendingAction {
+            action,
+            value,
+            executable_at,
+        };
+        self.audited_set(
+            &self.pending_actions(action_id),
+            self.audit_key_with(b"pending_actions", &action_id),
+            &pending_action,
+        );
+        self.audited_set(&self.pending_action_count(), self.audit_key(b"pending_action_count"), &action_id);
+        self.pending_action_ids().insert(action_id);
+        self.log_entry_added(self.audit_key(b"pending_action_ids"), &action_id);
+
+        self.action_proposed_event(action_id, action, executable_at, &pending_action.value);
+
+        action_id
+    }
+
+    #[endpoint(cancelAction)]
+    fn cancel_action(&self, action_id: u32) -> SCResult<()> {
+        self.require_pending_action(action_id);
+        let pending_action = self.pending_actions(action_id).get();
+        self.require_action_permission(pending_action.action);
+
+        self.remove_pending_action(action_id);
+        self.action_cancelled_event(action_id);
+
+        Ok(())
+    }
+
+    // private
+
+    fn require_not_timelocked(&self) {
+        require!(self.timelock_delay().get() == 0, "change is timelocked, use proposeAction");
+    }
+
+    // once true, economic parameters only move in the holders' favor
+    fn sale_started(&self) -> bool {
+        if self.mint_count().get() > 0 {
+            return true;
+        }
+
+        let start = self.sale_start_timestamp().get();
+        start != 0 && self.blockchain().get_block_timestamp() >= start
+    }
+
+    fn require_pending_action(&self, action_id: u32) {
+        require!(self.pending_action_ids().contains(&action_id), "no such pending action");
+    }
+
+    // the permission of the matching direct setter
+    fn require_action_permission(&self, action: TimelockAction) {
+        match action {
+            TimelockAction::Price | TimelockAction::Royalties => self.require_role(Role::Admin),
+            TimelockAction::ImageBaseUri | TimelockAction::MetadataBaseUri => self.require_role(Role::MetadataManager),
+            TimelockAction::TimelockDelay => {
+                require!(
+                    self.blockchain().get_caller() == self.blockchain().get_owner_address(),
+                    "permission denied"
+                );
+            },
+        }
+    }
+
+    // decoding fails on a malformed value; the setter checks run again on execution
+    fn validate_action_value(&self, action: TimelockAction, value: &ManagedBuffer) {
+        match action {
+            TimelockAction::Price => {
+                let _: BigUint = self.serializer().top_decode_from_managed_buffer(value);
+            },
+            TimelockAction::Royalties => {
+                let royalties: u32 = self.serializer().top_decode_from_managed_buffer(value);
+                require!(
+                    royalties <= self.max_royalties().get(),
+                    "royalties cannot exceed the committed maximum"
+                );
+            },
+            TimelockAction::ImageBaseUri | TimelockAction::MetadataBaseUri => {},
+            TimelockAction::TimelockDelay => {
+                let _: u64 = self.serializer().top_decode_from_managed_buffer(value);
+            },
+        }
+    }
+
+    // removes a due action from the queue and hands it over for execution
+    fn take_executable_action(&self, action_id: u32) -> PendingAction<Self::Api> {
+        self.require_pending_action(action_id);
+        let pending_action = self.pending_actions(action_id).get();
+        require!(
+            self.blockchain().get_block_timestamp() >= pending_action.executable_at,
+            "action still timelocked"
+        );
+
+        self.remove_pending_action(action_id);
+        self.action_executed_event(action_id);
+
+        pending_action
+    }
+
+    fn remove_pending_action(&self, action_id: u32) {
+        self.pending_action_ids().swap_remove(&action_id);
+        self.log_entry_removed(self.audit_key(b"pending_action_ids"), &action_id);
+        self.audited_clear(&self.pending_actions(action_id), self.audit_key_with(b"pending_actions", &action_id));
+    }
+
+    fn apply_timelock_delay(&self, value: &ManagedBuffer) {
+        let delay: u64 = self.serializer().top_decode_from_managed_buffer(value);
+        self.audited_set(&self.timelock_delay(), self.audit_key(b"timelock_delay"), &delay);
+    }
+
+    // views
+
+    // (action id, action) for every proposal not yet executed or cancelled
+    #[view(getPendingActions)]
+    fn get_pending_actions(&self) -> MultiResultVec<MultiResult2<u32, PendingAction<Self::Api>>> {
+        self.pending_action_ids()
+            .iter()
+            .map(|action_id| (action_id, self.pending_actions(action_id).get()).into())
+            .collect::<Vec<MultiResult2<u32, PendingAction<Self::Api>>>>()
+            .into()
+    }
+
+    // events
+
+    #[event("action_proposed")]
+    fn action_proposed_event(
+        &self,
+        #[indexed] action_id: u32,
+        #[indexed] action: TimelockAction,
+        #[indexed] executable_at: u64,
+        value: &ManagedBuffer,
+    );
+
+    #[event("action_executed")]
+    fn action_executed_event(&self, #[indexed] action_id: u32);
+
+    #[event("action_cancelled")]
+    fn action_cancelled_event(&self, #[indexed] action_id: u32);
+
+    // storage
+
+    // in seconds, 0 leaves the direct setters open
+    #[view(getTimelockDelay)]
+    #[storage_mapper("timelock_delay")]
+    fn timelock_delay(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("pending_action_count")]
+    fn pending_action_count(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("pending_actions")]
+    fn pending_actions(&self, action_id: u32) -> SingleValueMapper<PendingAction<Self::Api>>;
+
+    #[storage_mapper("pending_action_ids")]
+    fn pending_action_ids(&self) -> UnorderedSetMapper<u32>;
+}
@@ -0,0 +1,46 @@
+elrond_wasm::imports!();
+
+#[elrond_wasm::module]
+pub trait TraitStatsModule: crate::audit::AuditModule {
+    fn record_trait(&self, trait_id: u32, value_id: u32) {
+        if self.trait_values(trait_id).insert(value_id) {
+            self.log_entry_added(self.audit_key_with(b"trait_values", &trait_id), &value_id);
+        }
+        self.audited_update(
+            &self.trait_value_count(trait_id, value_id),
+            self.audit_key_with(b"trait_value_count", &(trait_id, value_id)),
+            |v| *v += 1,
+        );
+    }
+
+    fn unrecord_trait(&self, trait_id: u32, value_id: u32) {
+        self.audited_update(
+            &self.trait_value_count(trait_id, value_id),
+            self.audit_key_with(b"trait_value_count", &(trait_id, value_id)),
+            |v| *v -= 1,
+        );
+    }
+
+    // views
+
+    // (value_id, count) for every value of the trait assigned so far
+    #[view(getTraitDistribution)]
+    fn get_trait_distribution(&self, trait_id: u32) -> MultiResultVec<MultiResult2<u32, u32>> {
+        let mut distribution = Vec::new();
+        for value_id in self.trait_values(trait_id).iter() {
+            let count = self.trait_value_count(trait_id, value_id).get();
+            distribution.push((value_id, count).into());
+        }
+
+        distribution.into()
+    }
+
+    // storage
+
+    #[storage_mapper("trait_values")]
+    fn trait_values(&self, trait_id: u32) -> SetMapper<u32>;
+
+    #[view(getTraitValueCount)]
+    #[storage_mapper("trait_value_count")]
+    fn trait_value_count(&self, trait_id: u32, value_id: u32) -> SingleValueMapper<u32>;
+}
@@ -0,0 +1,99 @@
+elrond_wasm::imports!();
+
+// minimum seconds a proposed treasury has to wait before it can accept the role,
+// longer when the timelock delay is longer
+pub const TREASURY_CHANGE_DELAY: u64 = 2 * 24 * 60 * 60;
+
+// Sale proceeds can only leave the contract towards the registered treasury. A
+// new treasury is proposed by the owner and must accept after the delay, which
+// leaves the current treasury time to cancel a change it did not ask for.
+#[elrond_wasm::module]
+pub trait TreasuryModule: crate::audit::AuditModule + crate::timelock::TimelockModule {
+    // endpoints - owner-only
+
+    #[only_owner]
+    #[endpoint(proposeTreasury)]
+    fn propose_treasury(&self, address: ManagedAddress) -> SCResult<()> {
+        require!(!address.is_zero(), "invalid treasury address");
+
+        let delay = core::cmp::max(TREASURY_CHANGE_DELAY, self.timelock_delay().get());
+        let activation_timestamp = self.blockchain().get_block_timestamp() + delay;
+        self.audited_set(
+            &self.pending_treasury_address(),
+            self.audit_key(b"pending_treasury_address"),
+            &address,
+        );
+        self.audited_set(
+            &self.pending_treasury_timestamp(),
+            self.audit_key(b"pending_treasury_timestamp"),
+            &activation_timestamp,
+        );
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[endpoint(acceptTreasury)]
+    fn accept_treasury(&self) -> SCResult<()> {
+        require!(!self.pending_treasury_address().is_empty(), "no pending treasury change");
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.pending_treasury_address().get(),
+            "only the pending treasury can accept"
+        );
+        require!(
+            self.blockchain().get_block_timestamp() >= self.pending_treasury_timestamp().get(),
+            "treasury change still timelocked"
+        );
+
+        self.audited_set(&self.treasury_address(), self.audit_key(b"treasury_address"), &caller);
+        self.clear_pending_treasury();
+
+        Ok(())
+    }
+
+    // the owner or the current treasury can drop a proposal before it is accepted
+    #[endpoint(cancelTreasuryChange)]
+    fn cancel_treasury_change(&self) -> SCResult<()> {
+        require!(!self.pending_treasury_address().is_empty(), "no pending treasury change");
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.blockchain().get_owner_address() || caller == self.treasury_address().get(),
+            "permission denied"
+        );
+
+        self.clear_pending_treasury();
+
+        Ok(())
+    }
+
+    // private
+
+    fn clear_pending_treasury(&self) {
+        self.audited_clear(
+            &self.pending_treasury_address(),
+            self.audit_key(b"pending_treasury_address"),
+        );
+        self.audited_clear(
+            &self.pending_treasury_timestamp(),
+            self.audit_key(b"pending_treasury_timestamp"),
+        );
+    }
+
+    // storage
+
+    #[view(getTreasuryAddress)]
+    #[storage_mapper("treasury_address")]
+    fn treasury_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getPendingTreasuryAddress)]
+    #[storage_mapper("pending_treasury_address")]
+    fn pending_treasury_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getPendingTreasuryTimestamp)]
+    #[storage_mapper("pending_treasury_timestamp")]
+    fn pending_treasury_timestamp(&self) -> SingleValueMapper<u64>;
+}
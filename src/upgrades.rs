@@ -0,0 +1,177 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::states::UpgradeProposalState;
+
+pub const CODE_HASH_LENGTH: usize = 32;
+// seconds staked holders have to block an announced upgrade
+pub const UPGRADE_VETO_WINDOW: u64 = 5 * 24 * 60 * 60;
+// share of the stake weight at announcement, in basis points, that blocks a proposal
+pub const UPGRADE_VETO_THRESHOLD: u32 = 3_000;
+const BASIS_POINTS_MAX: u32 = 10_000;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct UpgradeProposal<M: ManagedTypeApi> {
+    pub code_hash: ManagedByteArray<M, CODE_HASH_LENGTH>,
+    pub description_uri: ManagedBuffer<M>,
+    pub proposed_timestamp: u64,
+    pub veto_end_timestamp: u64,
+    pub total_weight: BigUint<M>,
+    pub veto_weight: BigUint<M>,
+    pub state: UpgradeProposalState,
+}
+
+// The owner announces upgrades and major config changes here before carrying them
+// out. Only NFTs staked before the announcement count towards a veto, so weight
+// cannot be moved around during the window to vote twice.
+#[elrond_wasm::module]
+pub trait UpgradesModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::holders::HoldersModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+    + crate::staking::StakingModule
+    + crate::storage::StorageModule
+{
+    // endpoints - owner-only
+
+    #[only_owner]
+    #[endpoint(proposeUpgrade)]
+    fn propose_upgrade(
+        &self,
+        code_hash: ManagedByteArray<Self::Api, CODE_HASH_LENGTH>,
+        description_uri: ManagedBuffer,
+    ) -> u32 {
+        require!(!description_uri.is_empty(), "description uri required");
+
+        let now = self.blockchain().get_block_timestamp();
+        let proposal_id = self.upgrade_proposal_count().get() + 1;
+        self.audited_set(
+            &self.upgrade_proposals(proposal_id),
+            self.audit_key_with(b"upgrade_proposals", &proposal_id),
+            &UpgradeProposal {
+                code_hash,
+                description_uri,
+                proposed_timestamp: now,
+                veto_end_timestamp: now + UPGRADE_VETO_WINDOW,
+                total_weight: self.total_stake_weight().get(),
+                veto_weight: BigUint::zero(),
+                state: UpgradeProposalState::Pending,
+            },
+        );
+        self.audited_set(&self.upgrade_proposal_count(), self.audit_key(b"upgrade_proposal_count"), &proposal_id);
+
+        proposal_id
+    }
+
+    // records that the announced change went ahead, only after an unblocked window
+    #[only_owner]
+    #[endpoint(executeUpgradeProposal)]
+    fn execute_upgrade_proposal(&self, proposal_id: u32) -> SCResult<()> {
+        let mut proposal = self.require_pending_upgrade_proposal(proposal_id);
+        require!(
+            self.blockchain().get_block_timestamp() >= proposal.veto_end_timestamp,
+            "veto window still open"
+        );
+
+        proposal.state = UpgradeProposalState::Executed;
+        self.upgrade_executed_event(proposal_id, &proposal.code_hash);
+        self.audited_set(
+            &self.upgrade_proposals(proposal_id),
+            self.audit_key_with(b"upgrade_proposals", &proposal_id),
+            &proposal,
+        );
+
+        Ok(())
+    }
+
+    #[only_owner]
+    #[endpoint(cancelUpgradeProposal)]
+    fn cancel_upgrade_proposal(&self, proposal_id: u32) -> SCResult<()> {
+        let mut proposal = self.require_pending_upgrade_proposal(proposal_id);
+
+        proposal.state = UpgradeProposalState::Cancelled;
+        self.audited_set(
+            &self.upgrade_proposals(proposal_id),
+            self.audit_key_with(b"upgrade_proposals", &proposal_id),
+            &proposal,
+        );
+
+        Ok(())
+    }
+
+    /// endpoint
+
+    #[endpoint(vetoUpgrade)]
+    fn veto_upgrade(&self, proposal_id: u32) -> BigUint {
+        let mut proposal = self.require_pending_upgrade_proposal(proposal_id);
+        require!(
+            self.blockchain().get_block_timestamp() < proposal.veto_end_timestamp,
+            "veto window closed"
+        );
+
+        let caller = self.blockchain().get_caller();
+        require!(!self.upgrade_vetoed_by(proposal_id, &caller).get(), "already vetoed");
+
+        let eligible_nonces = self
+            .staked_nonces(&caller)
+            .iter()
+            .filter(|nonce| self.stake_timestamp(*nonce).get() < proposal.proposed_timestamp)
+            .count();
+        require!(eligible_nonces > 0, "no stake eligible for this proposal");
+
+        let weight = self.get_nft_stake_weight() * eligible_nonces as u32;
+        self.audited_set(
+            &self.upgrade_vetoed_by(proposal_id, &caller),
+            self.audit_key_with(b"upgrade_vetoed_by", &(proposal_id, &caller)),
+            &true,
+        );
+
+        proposal.veto_weight += &weight;
+        if proposal.veto_weight.clone() * BASIS_POINTS_MAX >= proposal.total_weight.clone() * UPGRADE_VETO_THRESHOLD {
+            proposal.state = UpgradeProposalState::Vetoed;
+        }
+        self.audited_set(
+            &self.upgrade_proposals(proposal_id),
+            self.audit_key_with(b"upgrade_proposals", &proposal_id),
+            &proposal,
+        );
+
+        weight
+    }
+
+    // private
+
+    fn require_pending_upgrade_proposal(&self, proposal_id: u32) -> UpgradeProposal<Self::Api> {
+        require!(!self.upgrade_proposals(proposal_id).is_empty(), "proposal does not exist");
+
+        let proposal = self.upgrade_proposals(proposal_id).get();
+        require!(proposal.state == UpgradeProposalState::Pending, "proposal not pending");
+
+        proposal
+    }
+
+    // events
+
+    #[event("upgrade_executed")]
+    fn upgrade_executed_event(
+        &self,
+        #[indexed] proposal_id: u32,
+        code_hash: &ManagedByteArray<Self::Api, CODE_HASH_LENGTH>,
+    );
+
+    // storage
+
+    #[view(getUpgradeProposalCount)]
+    #[storage_mapper("upgrade_proposal_count")]
+    fn upgrade_proposal_count(&self) -> SingleValueMapper<u32>;
+
+    #[view(getUpgradeProposal)]
+    #[storage_mapper("upgrade_proposals")]
+    fn upgrade_proposals(&self, proposal_id: u32) -> SingleValueMapper<UpgradeProposal<Self::Api>>;
+
+    #[view(hasVetoedUpgrade)]
+    #[storage_mapper("upgrade_vetoed_by")]
+    fn upgrade_vetoed_by(&self, proposal_id: u32, address: &ManagedAddress) -> SingleValueMapper<bool>;
+}
@@ -0,0 +1,254 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::access_control::Role;
+use crate::{IMAGE_FILE_EXTENSION, METADATA_FILE_EXTENSION, URI_SLASH};
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct DropUris<M: ManagedTypeApi> {
+    pub first_mint_id: u32,
+    pub last_mint_id: u32,
+    pub image_base_uri: ManagedBuffer<M>,
+    pub metadata_base_uri: ManagedBuffer<M>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct TokenUris<M: ManagedTypeApi> {
+    pub image_uri: ManagedBuffer<M>,
+    pub metadata_uri: ManagedBuffer<M>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
+pub struct MediaBaseUri<M: ManagedTypeApi> {
+    pub base_uri: ManagedBuffer<M>,
+    // including the dot, e.g. ".mp4"
+    pub extension: ManagedBuffer<M>,
+}
+
+// Single place where token URIs are built. Lookup order for every mint id:
+// per-token override -> drop base covering the id -> collection default base.
+// With placeholders set, tokens minted before the reveal get the placeholder URIs.
+#[elrond_wasm::module]
+pub trait UriResolverModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::storage::StorageModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(setTokenUriOverride)]
+    fn set_token_uri_override(&self, mint_id: u32, image_uri: ManagedBuffer, metadata_uri: ManagedBuffer) -> SCResult<()> {
+        self.require_role(Role::MetadataManager);
+
+        self.audited_set(
+            &self.token_uri_override(mint_id),
+            self.audit_key_with(b"token_uri_override", &mint_id),
+            &TokenUris {
+                image_uri,
+                metadata_uri,
+            },
+        );
+
+        Ok(())
+    }
+
+    #[endpoint(clearTokenUriOverride)]
+    fn clear_token_uri_override(&self, mint_id: u32) -> SCResult<()> {
+        self.require_role(Role::MetadataManager);
+
+        self.audited_clear(
+            &self.token_uri_override(mint_id),
+            self.audit_key_with(b"token_uri_override", &mint_id),
+        );
+
+        Ok(())
+    }
+
+    #[endpoint(addDropBaseUris)]
+    fn add_drop_base_uris(
+        &self,
+        first_mint_id: u32,
+        last_mint_id: u32,
+        image_base_uri: ManagedBuffer,
+        metadata_base_uri: ManagedBuffer,
+    ) -> usize {
+        self.require_role(Role::MetadataManager);
+
+        require!(first_mint_id > 0 && first_mint_id <= last_mint_id, "invalid mint id range");
+        for drop in self.drop_base_uris().iter() {
+            require!(
+                last_mint_id < drop.first_mint_id || first_mint_id > drop.last_mint_id,
+                "range overlaps an existing drop"
+            );
+        }
+
+        let drop = DropUris {
+            first_mint_id,
+            last_mint_id,
+            image_base_uri,
+            metadata_base_uri,
+        };
+        self.log_entry_added(self.audit_key(b"drop_base_uris"), &drop);
+
+        self.drop_base_uris().push(&drop)
+    }
+
+    // shared by every token minted before the reveal
+    #[endpoint(setPlaceholderUris)]
+    fn set_placeholder_uris(&self, image_uri: ManagedBuffer, metadata_uri: ManagedBuffer) -> SCResult<()> {
+        self.require_role(Role::MetadataManager);
+        require!(!self.revealed().get(), "collection already revealed");
+
+        self.audited_set(&self.placeholder_image_uri(), self.audit_key(b"placeholder_image_uri"), &image_uri);
+        self.audited_set(
+            &self.placeholder_metadata_uri(),
+            self.audit_key(b"placeholder_metadata_uri"),
+            &metadata_uri,
+        );
+
+        Ok(())
+    }
+
+    // extra media (animation, audio, ...) appended after the image and metadata URIs
+    #[endpoint(addMediaBaseUri)]
+    fn add_media_base_uri(&self, base_uri: ManagedBuffer, extension: ManagedBuffer) -> usize {
+        self.require_role(Role::MetadataManager);
+
+        let media = MediaBaseUri { base_uri, extension };
+        self.log_entry_added(self.audit_key(b"media_base_uris"), &media);
+
+        self.media_base_uris().push(&media)
+    }
+
+    #[endpoint(clearMediaBaseUris)]
+    fn clear_media_base_uris(&self) -> SCResult<()> {
+        self.require_role(Role::MetadataManager);
+
+        self.media_base_uris().clear();
+        self.log_collection_cleared(self.audit_key(b"media_base_uris"));
+
+        Ok(())
+    }
+
+    // endpoints - owner-only
+
+    // final bases for new mints; earlier tokens find theirs through getRevealedUri
+    #[only_owner]
+    #[endpoint(reveal)]
+    fn reveal(&self, final_image_base: ManagedBuffer, final_metadata_base: ManagedBuffer) -> SCResult<()> {
+        require!(!self.revealed().get(), "collection already revealed");
+
+        self.audited_set(&self.image_base_uri(), self.audit_key(b"image_base_uri"), &final_image_base);
+        self.audited_set(&self.metadata_base_uri(), self.audit_key(b"metadata_base_uri"), &final_metadata_base);
+        self.audited_set(&self.revealed(), self.audit_key(b"revealed"), &true);
+
+        Ok(())
+    }
+
+    // private
+
+    // every URI attached to the token at create time, in order
+    fn resolve_uri_list(&self, mint_id: u32) -> ManagedVec<ManagedBuffer> {
+        let mut uris = ManagedVec::new();
+        if !self.revealed().get() && !self.placeholder_image_uri().is_empty() {
+            uris.push(self.placeholder_image_uri().get());
+            uris.push(self.placeholder_metadata_uri().get());
+
+            return uris;
+        }
+
+        let token_uris = self.resolve_token_uris(mint_id);
+
+        uris.push(token_uris.image_uri);
+        uris.push(token_uris.metadata_uri);
+
+        for media in self.media_base_uris().iter() {
+            uris.push(self.build_uri(&media.base_uri, mint_id, media.extension.to_boxed_bytes().as_slice()));
+        }
+
+        uris
+    }
+
+    fn resolve_token_uris(&self, mint_id: u32) -> TokenUris<Self::Api> {
+        if !self.token_uri_override(mint_id).is_empty() {
+            return self.token_uri_override(mint_id).get();
+        }
+
+        let (image_base_uri, metadata_base_uri) = self.resolve_base_uris(mint_id);
+
+        TokenUris {
+            image_uri: self.build_uri(&image_base_uri, mint_id, IMAGE_FILE_EXTENSION),
+            metadata_uri: self.build_uri(&metadata_base_uri, mint_id, METADATA_FILE_EXTENSION),
+        }
+    }
+
+    fn resolve_base_uris(&self, mint_id: u32) -> (ManagedBuffer, ManagedBuffer) {
+        for drop in self.drop_base_uris().iter() {
+            if mint_id >= drop.first_mint_id && mint_id <= drop.last_mint_id {
+                return (drop.image_base_uri, drop.metadata_base_uri);
+            }
+        }
+
+        (self.image_base_uri().get(), self.metadata_base_uri().get())
+    }
+
+    // index of the drop covering the id, 0 for ids outside every drop
+    fn drop_id_of(&self, mint_id: u32) -> usize {
+        for (drop_id, drop) in self.drop_base_uris().iter().enumerate() {
+            if mint_id >= drop.first_mint_id && mint_id <= drop.last_mint_id {
+                return drop_id + 1;
+            }
+        }
+
+        0
+    }
+
+    fn build_uri(&self, base_uri: &ManagedBuffer, mint_id: u32, extension: &[u8]) -> ManagedBuffer {
+        use alloc::string::ToString;
+
+        let mut uri = ManagedBuffer::new();
+        uri.append(base_uri);
+        uri.append(&ManagedBuffer::new_from_bytes(URI_SLASH));
+        uri.append(&ManagedBuffer::new_from_bytes(mint_id.to_string().as_bytes()));
+        uri.append(&ManagedBuffer::new_from_bytes(extension));
+
+        uri
+    }
+
+    // views
+
+    #[view(getTokenUris)]
+    fn get_token_uris(&self, mint_id: u32) -> TokenUris<Self::Api> {
+        self.resolve_token_uris(mint_id)
+    }
+
+    #[view(getTokenUriList)]
+    fn get_token_uri_list(&self, mint_id: u32) -> ManagedMultiResultVec<ManagedBuffer> {
+        self.resolve_uri_list(mint_id).into()
+    }
+
+    // storage
+
+    #[storage_mapper("token_uri_override")]
+    fn token_uri_override(&self, mint_id: u32) -> SingleValueMapper<TokenUris<Self::Api>>;
+
+    #[view(isRevealed)]
+    #[storage_mapper("revealed")]
+    fn revealed(&self) -> SingleValueMapper<bool>;
+
+    #[view(getPlaceholderImageUri)]
+    #[storage_mapper("placeholder_image_uri")]
+    fn placeholder_image_uri(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getPlaceholderMetadataUri)]
+    #[storage_mapper("placeholder_metadata_uri")]
+    fn placeholder_metadata_uri(&self) -> SingleValueMapper<ManagedBuffer>;
+
+    #[view(getMediaBaseUris)]
+    #[storage_mapper("media_base_uris")]
+    fn media_base_uris(&self) -> VecMapper<MediaBaseUri<Self::Api>>;
+
+    #[view(getDropBaseUris)]
+    #[storage_mapper("drop_base_uris")]
+    fn drop_base_uris(&self) -> VecMapper<DropUris<Self::Api>>;
+}
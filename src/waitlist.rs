@@ -0,0 +1,131 @@
+elrond_wasm::imports!();
+elrond_wasm::derive_imports!();
+
+use crate::pause::Feature;
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct WaitlistEntry<M: ManagedTypeApi> {
+    pub address: ManagedAddress<M>,
+    pub payment_token: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub joined_timestamp: u64,
+}
+
+// Once the collection is sold out, buyers can queue with their payment escrowed.
+// Supply that comes back (recycled burns, released reservations) is handed to the
+// queue in FIFO order through settleWaitlist, and regular mints wait until the
+// queue is empty. Leaving the queue refunds the full escrow.
+#[elrond_wasm::module]
+pub trait WaitlistModule:
+    crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+    + crate::pause::PauseModule
+    + crate::storage::StorageModule
+{
+    /// endpoint
+
+    #[payable("*")]
+    #[endpoint(joinWaitlist)]
+    fn join_waitlist(
+        &self,
+        #[payment_token] payment_token: TokenIdentifier,
+        #[payment_amount] payment_amount: BigUint,
+    ) {
+        self.require_feature_active(Feature::Mint);
+
+        let max_supply = self.max_supply().get();
+        let sold_out = max_supply != 0 && self.mint_count().get() >= max_supply;
+        require!(sold_out || !self.waitlist().is_empty(), "supply not exhausted");
+        require!(
+            payment_token == self.payment_token_id().get(),
+            "not given token identifier"
+        );
+        require!(payment_amount >= self.nft_token_price().get(), "not enough tokens");
+
+        let caller = self.blockchain().get_caller();
+        require!(self.waitlist_node_id(&caller).is_empty(), "already on the waitlist");
+
+        let entry = WaitlistEntry {
+            address: caller.clone(),
+            payment_token,
+            amount: payment_amount,
+            joined_timestamp: self.blockchain().get_block_timestamp(),
+        };
+        let node = self.waitlist().push_back(entry.clone());
+        self.log_entry_added(self.audit_key(b"waitlist"), &entry);
+        self.audited_set(
+            &self.waitlist_node_id(&caller),
+            self.audit_key_with(b"waitlist_node_id", &caller),
+            &node.get_node_id(),
+        );
+        self.protect_inventory(&entry.payment_token, 0, &entry.amount);
+    }
+
+    #[endpoint(leaveWaitlist)]
+    fn leave_waitlist(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(!self.waitlist_node_id(&caller).is_empty(), "not on the waitlist");
+
+        let node_id = self.waitlist_node_id(&caller).get();
+        let entry = self.remove_waitlist_entry(node_id);
+
+        self.send().direct(&caller, &entry.payment_token, 0, &entry.amount, &[]);
+    }
+
+    // private
+
+    // the escrow is released, the caller decides where the payment goes
+    fn pop_waitlist_entry(&self) -> Option<WaitlistEntry<Self::Api>> {
+        let node_id = self.waitlist().front()?.get_node_id();
+
+        Some(self.remove_waitlist_entry(node_id))
+    }
+
+    fn remove_waitlist_entry(&self, node_id: u32) -> WaitlistEntry<Self::Api> {
+        let entry = self.waitlist().remove_node_by_id(node_id).unwrap().into_value();
+        self.log_entry_removed(self.audit_key(b"waitlist"), &entry);
+        self.audited_clear(
+            &self.waitlist_node_id(&entry.address),
+            self.audit_key_with(b"waitlist_node_id", &entry.address),
+        );
+        self.release_inventory(&entry.payment_token, 0, &entry.amount);
+
+        entry
+    }
+
+    // views
+
+    #[view(getWaitlistLength)]
+    fn get_waitlist_length(&self) -> usize {
+        self.waitlist().len()
+    }
+
+    // 1 is next in line, 0 when not queued
+    #[view(getWaitlistPosition)]
+    fn get_waitlist_position(&self, address: ManagedAddress) -> usize {
+        self.waitlist()
+            .iter()
+            .position(|node| node.get_value_as_ref().address == address)
+            .map_or(0, |index| index + 1)
+    }
+
+    // `from` is a zero-based offset into the queue
+    #[view(getWaitlist)]
+    fn get_waitlist(&self, from: usize, size: usize) -> MultiResultVec<WaitlistEntry<Self::Api>> {
+        self.waitlist()
+            .iter()
+            .skip(from)
+            .take(size)
+            .map(|node| node.into_value())
+            .collect::<Vec<WaitlistEntry<Self::Api>>>()
+            .into()
+    }
+
+    // storage
+
+    #[storage_mapper("waitlist")]
+    fn waitlist(&self) -> LinkedListMapper<WaitlistEntry<Self::Api>>;
+
+    #[storage_mapper("waitlist_node_id")]
+    fn waitlist_node_id(&self, address: &ManagedAddress) -> SingleValueMapper<u32>;
+}
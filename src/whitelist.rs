@@ -0,0 +1,95 @@
+elrond_wasm::imports!();
+
+use crate::access_control::Role;
+use crate::merkle_rewards::MERKLE_HASH_LENGTH;
+
+// Presale for an off-chain whitelist: the admin publishes the root of a Merkle tree
+// whose leaves are sha256(address | allocation u32 big-endian), hashed like the
+// reward trees. Each address can mint up to its allocation at the presale price
+// while the presale is open, independently of the public sale window.
+#[elrond_wasm::module]
+pub trait WhitelistModule:
+    crate::access_control::AccessControlModule
+    + crate::audit::AuditModule
+    + crate::inventory::InventoryModule
+    + crate::merkle_rewards::MerkleRewardsModule
+    + crate::pause::PauseModule
+    + crate::timelock::TimelockModule
+{
+    // endpoints - role-gated
+
+    #[endpoint(setWhitelist)]
+    fn set_whitelist(
+        &self,
+        merkle_root: ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH>,
+        presale_price: BigUint,
+    ) -> SCResult<()> {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        self.audited_set(&self.whitelist_root(), self.audit_key(b"whitelist_root"), &merkle_root);
+        self.audited_set(&self.presale_price(), self.audit_key(b"presale_price"), &presale_price);
+
+        Ok(())
+    }
+
+    #[endpoint(setPresaleOpen)]
+    fn set_presale_open(&self, open: bool) -> SCResult<()> {
+        self.require_role(Role::Operator);
+        require!(!open || !self.whitelist_root().is_empty(), "whitelist not set");
+
+        self.audited_set(&self.presale_open(), self.audit_key(b"presale_open"), &open);
+
+        Ok(())
+    }
+
+    // private
+
+    fn consume_presale_allocation(
+        &self,
+        address: &ManagedAddress,
+        allocation: u32,
+        proof: ManagedVarArgs<ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH>>,
+    ) {
+        require!(self.presale_open().get(), "presale not open");
+
+        let leaf = self.whitelist_leaf(address, allocation);
+        require!(
+            self.compute_merkle_root(leaf, proof) == self.whitelist_root().get(),
+            "invalid proof"
+        );
+
+        let minted = self.presale_mints(address).get();
+        require!(minted < allocation, "presale allocation used up");
+        self.audited_set(
+            &self.presale_mints(address),
+            self.audit_key_with(b"presale_mints", address),
+            &(minted + 1),
+        );
+    }
+
+    fn whitelist_leaf(&self, address: &ManagedAddress, allocation: u32) -> ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH> {
+        let mut leaf_data = address.as_managed_buffer().clone();
+        leaf_data.append_u32_be(allocation);
+
+        self.crypto().sha256(&leaf_data)
+    }
+
+    // storage
+
+    #[view(getWhitelistRoot)]
+    #[storage_mapper("whitelist_root")]
+    fn whitelist_root(&self) -> SingleValueMapper<ManagedByteArray<Self::Api, MERKLE_HASH_LENGTH>>;
+
+    #[view(getPresalePrice)]
+    #[storage_mapper("presale_price")]
+    fn presale_price(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(isPresaleOpen)]
+    #[storage_mapper("presale_open")]
+    fn presale_open(&self) -> SingleValueMapper<bool>;
+
+    #[view(getPresaleMints)]
+    #[storage_mapper("presale_mints")]
+    fn presale_mints(&self, address: &ManagedAddress) -> SingleValueMapper<u32>;
+}
@@ -1,13 +1,66 @@
 use elrond_nftmanager::*;
+use access_control::{AccessControlModule, Role};
+use attribute_updates::AttributeUpdatesModule;
+use collections::CollectionsModule;
+use coupons::CouponsModule;
+use creators::CreatorsModule;
+use dutch_auction::DutchAuctionModule;
+use ed25519_dalek::{ExpandedSecretKey, PublicKey, SecretKey};
+use editions::{EditionKind, EditionsModule};
+use export::{ExportEncoding, ExportModule};
+use fusion::FusionModule;
+use holders::HoldersModule;
+use inventory::InventoryModule;
+use marketplace::MarketplaceModule;
 use elrond_wasm::{
-    types::{Address, SCResult, ManagedBuffer, OptionalArg, BigUint},
+    contract_base::ContractBase,
+    types::{Address, SCResult, ManagedBuffer, ManagedVarArgs, BigUint, EsdtLocalRole, OptionalArg, ManagedVec, MultiArg2, MultiArg3, ManagedByteArray, ManagedAsyncCallResult},
 };
 use elrond_wasm_debug::{
-    rust_biguint, testing_framework::*,
-    DebugApi,
+    managed_address, managed_biguint, managed_token_id, rust_biguint, testing_framework::*,
+    tx_mock::TxInputESDT, DebugApi,
 };
+use merkle_rewards::MerkleRewardsModule;
+use mint_discounts::MintDiscountsModule;
+use mint_history::MintHistoryModule;
+use mint_ids::MintIdsModule;
+use mint_passes::MintPassesModule;
+use mint_stats::MintStatsModule;
+use partners::PartnersModule;
+use pause::{Feature, PauseModule};
+use phases::PhasesModule;
+use payouts::PayoutsModule;
+use pieces::PiecesModule;
+use polls::{PollsModule, PollTarget};
+use price_tiers::PriceTiersModule;
+use raffle::RaffleModule;
+use rarity_boost::RarityBoostModule;
+use rarity_traits::RarityTraitsModule;
+use receipts::{ReceiptAttributes, ReceiptsModule};
+use redemptions::RedemptionsModule;
+use relayers::RelayersModule;
+use rentals::RentalsModule;
+use reservations::ReservationsModule;
+use revenue::RevenueModule;
+use royalty_overrides::RoyaltyOverridesModule;
+use shareholders::ShareholdersModule;
+use signatures::SignaturesModule;
+use staking::{StakingModule, StakingPosition};
+use states::{AuctionState, ListingState, RaffleState, RedemptionState, SalePhase, SetupStatus, UpgradeProposalState};
+use storage::StorageModule;
+use timelock::{TimelockAction, TimelockModule};
+use trait_stats::TraitStatsModule;
+use treasury::{TreasuryModule, TREASURY_CHANGE_DELAY};
+use upgrades::{UpgradesModule, UPGRADE_VETO_WINDOW};
+use uri_resolver::UriResolverModule;
+use waitlist::WaitlistModule;
+use whitelist::WhitelistModule;
 
-const WASM_PATH: &'static str = "output/elrond-nftmanager.wasm";
+const WASM_PATH: &str = "output/elrond-nftmanager.wasm";
+
+const PAYMENT_TOKEN_ID: &[u8] = b"TOKEN-123456";
+const NFT_TOKEN_ID: &[u8] = b"IWC-123456";
+const NFT_TOKEN_PRICE: u64 = 1_000_000_000_000_000_000;
 
 struct NftManagerSetup<NftManagerObjBuilder>
 where
@@ -15,9 +68,33 @@ where
 {
     pub blockchain_wrapper: BlockchainStateWrapper,
     pub owner_address: Address,
+    pub user_address: Address,
     pub em_wrapper: ContractObjWrapper<elrond_nftmanager::ContractObj<DebugApi>, NftManagerObjBuilder>,
 }
 
+const ALLOWLIST_SIGNER_SECRET: [u8; 32] = [7u8; 32];
+
+// the signer's address is its ed25519 public key
+fn allowlist_signer_address() -> Address {
+    let secret = SecretKey::from_bytes(&ALLOWLIST_SIGNER_SECRET).unwrap();
+    Address::from(PublicKey::from(&secret).to_bytes())
+}
+
+// signs a mintWithSignature allocation the way the off-chain service does, for chain id "D"
+fn sign_allowlist_mint(sc_address: &Address, user_address: &Address, nonce: u64, expiry: u64, max_allowed: u32) -> Vec<u8> {
+    let mut message = b"allowlist_mint".to_vec();
+    message.extend_from_slice(sc_address.as_bytes());
+    message.extend_from_slice(b"D");
+    message.extend_from_slice(user_address.as_bytes());
+    message.extend_from_slice(&nonce.to_be_bytes());
+    message.extend_from_slice(&expiry.to_be_bytes());
+    message.extend_from_slice(&max_allowed.to_be_bytes());
+
+    let secret = SecretKey::from_bytes(&ALLOWLIST_SIGNER_SECRET).unwrap();
+    let public = PublicKey::from(&secret);
+    ExpandedSecretKey::from(&secret).sign(&message, &public).to_bytes().to_vec()
+}
+
 fn setup_elrond_nftmanager<NftManagerObjBuilder>(
     em_builder: NftManagerObjBuilder,
 ) -> NftManagerSetup<NftManagerObjBuilder>
@@ -28,7 +105,9 @@ where
     let rust_zero = rust_biguint!(0u64);
     let mut blockchain_wrapper = BlockchainStateWrapper::new();
     let owner_address = blockchain_wrapper.create_user_account(&rust_zero);
-    
+    let user_address = blockchain_wrapper.create_user_account(&rust_zero);
+    blockchain_wrapper.set_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE * 10));
+
     let em_wrapper = blockchain_wrapper.create_sc_account(
         &rust_zero,
         Some(&owner_address),
@@ -36,12 +115,11 @@ where
         WASM_PATH,
     );
 
-    blockchain_wrapper.execute_tx(&owner_address, &em_wrapper, &rust_zero, |sc| {
-        let payment_token_id_bytes: &[u8] = b"TOKEN-123456";
-
-        let payment_token_id = TokenIdentifier::from(payment_token_id_bytes);
-        let nft_token_price = BigUint::from(1000_000_000_000_000_000 as u64);
+    let result = blockchain_wrapper.execute_tx(&owner_address, &em_wrapper, &rust_zero, |sc| {
+        let payment_token_id = TokenIdentifier::from(PAYMENT_TOKEN_ID);
+        let nft_token_price = BigUint::from(NFT_TOKEN_PRICE);
         let royalties: u32 = 300;
+        let max_royalties: u32 = 1_000;
         let image_base_uri = ManagedBuffer::<DebugApi>::from(b"https://ipfs.io/ipfs/QmXSFnUfdot3SgLsuZFdpefXii31YuyvtAD23NKdz9toar");
         let metadata_base_uri = ManagedBuffer::<DebugApi>::from(b"https://ipfs.io/ipfs/QmS1Zn9ytigCjkNtQPAduFuzptadNw2kC9asptoYq9ZBwS");
 
@@ -50,28 +128,50 @@ where
             nft_token_price,
             royalties,
             image_base_uri,
-            metadata_base_uri
+            metadata_base_uri,
+            max_royalties,
+            0,
         );
         assert_eq!(result, SCResult::Ok(()));
 
-        let token_name = ManagedBuffer::<DebugApi>::from(b"IceWorld");
-        let token_ticker = ManagedBuffer::<DebugApi>::from(b"IWC");
-        sc.issue_nft(token_name, token_ticker);
-
-        sc._mint();
+        // the issue callback cannot run in the testing framework, so the token is stored directly
+        sc.nft_token_name().set(&ManagedBuffer::from(b"IceWorld"));
+        sc.nft_token_id().set(&TokenIdentifier::from(NFT_TOKEN_ID));
 
         StateChange::Commit
     });
+    assert_eq!(result.result_status, 0);
+
+    blockchain_wrapper.set_esdt_local_roles(em_wrapper.address_ref(), NFT_TOKEN_ID, &[EsdtLocalRole::NftCreate]);
 
     blockchain_wrapper.add_mandos_set_account(em_wrapper.address_ref());
 
     NftManagerSetup {
         blockchain_wrapper,
         owner_address,
+        user_address,
         em_wrapper,
     }
 }
 
+fn mint_one<NftManagerObjBuilder>(em_setup: &mut NftManagerSetup<NftManagerObjBuilder>) -> u64
+where
+    NftManagerObjBuilder: 'static + Copy + Fn() -> elrond_nftmanager::ContractObj<DebugApi>,
+{
+    em_setup.blockchain_wrapper.execute_esdt_transfer(
+        &em_setup.user_address,
+        &em_setup.em_wrapper,
+        PAYMENT_TOKEN_ID,
+        0,
+        &rust_biguint!(NFT_TOKEN_PRICE),
+        |sc| {
+            sc.mint(TokenIdentifier::from(PAYMENT_TOKEN_ID), BigUint::from(NFT_TOKEN_PRICE), OptionalArg::None);
+
+            StateChange::Commit
+        },
+    ).result_status
+}
+
 // //////////////////////////////////////////////////////////////
 
 #[test]
@@ -81,3 +181,3537 @@ fn init_test() {
         .blockchain_wrapper
         .write_mandos_output("_generated_init.scen.json");
 }
+
+#[test]
+fn mint_history_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.get_mint_history_length(), 2);
+
+        let history = sc.get_mint_history(1, 10).into_vec();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].nonce, 2);
+        assert_eq!(history[0].buyer, ManagedAddress::from_address(&user_address));
+        assert_eq!(history[0].price, BigUint::from(NFT_TOKEN_PRICE));
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn royalties_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.get_royalties_percent(), ManagedBuffer::from(b"3.00"));
+    });
+    assert_eq!(result.result_status, 0);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_royalties(1_001);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "royalties cannot exceed the committed maximum");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_royalties(1_000), SCResult::Ok(()));
+        assert_eq!(sc.get_royalties_percent(), ManagedBuffer::from(b"10.00"));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn uri_resolver_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.add_drop_base_uris(11, 20, ManagedBuffer::from(b"ipfs://drop2/images"), ManagedBuffer::from(b"ipfs://drop2/json"));
+        let _ = sc.set_token_uri_override(15, ManagedBuffer::from(b"ipfs://special.png"), ManagedBuffer::from(b"ipfs://special.json"));
+
+        let default_uris = sc.get_token_uris(1);
+        assert_eq!(default_uris.image_uri, ManagedBuffer::from(b"https://ipfs.io/ipfs/QmXSFnUfdot3SgLsuZFdpefXii31YuyvtAD23NKdz9toar/1.png"));
+        assert_eq!(default_uris.metadata_uri, ManagedBuffer::from(b"https://ipfs.io/ipfs/QmS1Zn9ytigCjkNtQPAduFuzptadNw2kC9asptoYq9ZBwS/1.json"));
+
+        let drop_uris = sc.get_token_uris(12);
+        assert_eq!(drop_uris.image_uri, ManagedBuffer::from(b"ipfs://drop2/images/12.png"));
+        assert_eq!(drop_uris.metadata_uri, ManagedBuffer::from(b"ipfs://drop2/json/12.json"));
+
+        let override_uris = sc.get_token_uris(15);
+        assert_eq!(override_uris.image_uri, ManagedBuffer::from(b"ipfs://special.png"));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn access_control_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.pause_minting();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "permission denied");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.grant_role(Role::Admin, managed_address!(&user_address)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // admins act as operators but cannot appoint treasurers
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.pause_minting(), SCResult::Ok(()));
+        assert!(sc.has_role(Role::Operator, managed_address!(&user_address)));
+        assert!(!sc.has_role(Role::Treasurer, managed_address!(&user_address)));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.grant_role(Role::Treasurer, managed_address!(&user_address));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "permission denied");
+}
+
+#[test]
+fn treasury_change_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.treasury_address().get(), managed_address!(&owner_address));
+        assert_eq!(sc.propose_treasury(managed_address!(&user_address)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.accept_treasury();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "treasury change still timelocked");
+
+    em_setup.blockchain_wrapper.set_block_timestamp(TREASURY_CHANGE_DELAY);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.accept_treasury(), SCResult::Ok(()));
+        assert_eq!(sc.treasury_address().get(), managed_address!(&user_address));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn rescue_protected_inventory_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&sc_address, b"STRAY-123456", &rust_biguint!(100));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let token_id = managed_token_id!(b"STRAY-123456");
+        sc.protect_inventory(&token_id, 0, &managed_biguint!(60));
+        assert_eq!(sc.get_rescuable_amount(token_id.clone(), 0), managed_biguint!(40));
+
+        let _ = sc.rescue_tokens(token_id, 0, managed_biguint!(41), managed_address!(&owner_address));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "amount exceeds unprotected balance");
+}
+
+#[test]
+fn signature_checks_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_signer(managed_address!(&owner_address)), SCResult::Ok(()));
+        assert_eq!(sc.set_chain_id(ManagedBuffer::from(b"D")), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(100);
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        sc.verify_signature(b"mint", &managed_address!(&user_address), 0, 100, &ManagedBuffer::new(), &ManagedBuffer::new());
+    });
+    assert_eq!(result.result_message, "signature expired");
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        sc.verify_signature(b"mint", &managed_address!(&user_address), 1, 200, &ManagedBuffer::new(), &ManagedBuffer::new());
+    });
+    assert_eq!(result.result_message, "invalid signature nonce");
+}
+
+#[test]
+fn parameter_ratchet_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    mint_one(&mut em_setup);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_royalties(400);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "royalties can only decrease after sale start");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_royalties(200), SCResult::Ok(()));
+        assert_eq!(sc.set_max_supply(100), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_max_supply(0);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "max supply can only decrease after sale start");
+}
+
+#[test]
+fn admin_ordering_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.start_minting();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "local roles not set");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.local_roles_set().set(true);
+        let _ = sc.set_local_roles(OptionalArg::None, ManagedVarArgs::new());
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "Local roles already set");
+}
+
+#[test]
+fn mint_discount_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.add_discount_tier(managed_biguint!(NFT_TOKEN_PRICE), 30 * 86_400, 1_500);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.lock_tokens(30 * 86_400, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE));
+        assert_eq!(sc.get_mint_discount(managed_address!(&user_address)), 1_500);
+        assert_eq!(sc.get_mint_price_for(managed_address!(&user_address)), managed_biguint!(NFT_TOKEN_PRICE / 100 * 85));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.unlock_tokens();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "tokens still locked");
+}
+
+#[test]
+fn staking_rarity_boost_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let nonce = 1u64;
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.add_rarity_boost(7 * 86_400, 2_000);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, nonce, &rust_biguint!(1), |sc| {
+        sc.stake(managed_token_id!(NFT_TOKEN_ID), nonce, managed_biguint!(1));
+        assert_eq!(sc.total_staked().get(), 1);
+        assert_eq!(sc.get_rarity_boost(managed_address!(&user_address)), 0);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(7 * 86_400);
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.get_rarity_boost(managed_address!(&user_address)), 2_000);
+        assert_eq!(sc.boosted_weight(100, 2_000), 120);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn merkle_reward_claim_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(100));
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(100), |sc| {
+        sc.deposit_rewards(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(100));
+
+        // two leaves: the user gets 40, the owner 60
+        let owner_leaf = sc.reward_leaf(&managed_address!(&owner_address), &managed_biguint!(60));
+        let user_leaf = sc.reward_leaf(&managed_address!(&user_address), &managed_biguint!(40));
+        let mut proof = ManagedVarArgs::new();
+        proof.push(owner_leaf);
+        let root = sc.compute_merkle_root(user_leaf, proof);
+
+        assert_eq!(sc.create_reward_distribution(root, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(100), 1_000), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let owner_leaf = sc.reward_leaf(&managed_address!(&owner_address), &managed_biguint!(60));
+        let mut proof = ManagedVarArgs::new();
+        proof.push(owner_leaf);
+        sc.claim_reward(1, managed_biguint!(40), proof);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE * 10 + 40));
+
+    em_setup.blockchain_wrapper.set_block_timestamp(1_000);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.sweep_unclaimed_rewards(1);
+        assert_eq!(sc.reward_pool(&managed_token_id!(PAYMENT_TOKEN_ID)).get(), managed_biguint!(60));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn staking_auto_compound_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let nonce = 1u64;
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(10_000));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_staking_reward_token(managed_token_id!(PAYMENT_TOKEN_ID)), SCResult::Ok(()));
+        assert_eq!(sc.set_staking_reward_rate(managed_biguint!(10)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(10_000), |sc| {
+        sc.fund_staking_rewards(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(10_000));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, nonce, &rust_biguint!(1), |sc| {
+        sc.stake(managed_token_id!(NFT_TOKEN_ID), nonce, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(100);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.get_claimable_staking_rewards(managed_address!(&user_address)), managed_biguint!(1_000));
+        sc.set_auto_compound(true);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(200);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.unstake(nonce);
+        assert_eq!(sc.pending_staking_rewards(&managed_address!(&user_address)).get(), managed_biguint!(1_000));
+        assert_eq!(sc.stake_weight(&managed_address!(&user_address)).get(), managed_biguint!(0));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // the second period was compounded and paid out when the position closed
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE * 9 + 1_000));
+}
+
+#[test]
+fn staking_unbonding_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let nonce = 1u64;
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_unbonding_period(7 * 86_400), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, nonce, &rust_biguint!(1), |sc| {
+        sc.stake(managed_token_id!(NFT_TOKEN_ID), nonce, managed_biguint!(1));
+        sc.unstake(nonce);
+        assert_eq!(sc.unbond_timestamp(nonce).get(), 7 * 86_400);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.withdraw_unstaked();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "nothing to withdraw");
+
+    em_setup.blockchain_wrapper.set_block_timestamp(7 * 86_400);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.withdraw_unstaked(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert!(sc.unbonding_nonces(&managed_address!(&user_address)).is_empty());
+        assert_eq!(sc.get_rescuable_amount(managed_token_id!(NFT_TOKEN_ID), nonce), managed_biguint!(0));
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn staking_early_unstake_penalty_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(10_000));
+    em_setup.blockchain_wrapper.set_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+    em_setup.blockchain_wrapper.set_nft_balance(&owner_address, NFT_TOKEN_ID, 2, &rust_biguint!(1), &());
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_staking_reward_token(managed_token_id!(PAYMENT_TOKEN_ID)), SCResult::Ok(()));
+        assert_eq!(sc.set_staking_reward_rate(managed_biguint!(10)), SCResult::Ok(()));
+        assert_eq!(sc.set_unbonding_period(7 * 86_400), SCResult::Ok(()));
+        assert_eq!(sc.set_early_unstake_penalty(5_000, managed_biguint!(0)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(10_000), |sc| {
+        sc.fund_staking_rewards(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(10_000));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.stake(managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 2, &rust_biguint!(1), |sc| {
+        sc.stake(managed_token_id!(NFT_TOKEN_ID), 2, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // half of the user's 500 goes to the owner, the only staker left
+    em_setup.blockchain_wrapper.set_block_timestamp(100);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.unstake_early(1, managed_token_id!(b"EGLD"), managed_biguint!(0));
+        assert_eq!(sc.pending_staking_rewards(&managed_address!(&user_address)).get(), managed_biguint!(250));
+        assert_eq!(sc.get_claimable_staking_rewards(managed_address!(&owner_address)), managed_biguint!(750));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+}
+
+#[test]
+fn staking_early_unstake_compounded_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(10_000));
+    em_setup.blockchain_wrapper.set_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+    em_setup.blockchain_wrapper.set_nft_balance(&owner_address, NFT_TOKEN_ID, 2, &rust_biguint!(1), &());
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_staking_reward_token(managed_token_id!(PAYMENT_TOKEN_ID)), SCResult::Ok(()));
+        assert_eq!(sc.set_staking_reward_rate(managed_biguint!(10)), SCResult::Ok(()));
+        assert_eq!(sc.set_early_unstake_penalty(5_000, managed_biguint!(10)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(10_000), |sc| {
+        sc.fund_staking_rewards(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(10_000));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.stake(managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+        sc.set_auto_compound(true);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 2, &rust_biguint!(1), |sc| {
+        sc.stake(managed_token_id!(NFT_TOKEN_ID), 2, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(100);
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(11), |sc| {
+        sc.unstake_early(1, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(11));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "payment does not match the fee");
+
+    // half of the 500 compounded goes to the owner, along with the fee
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(10), |sc| {
+        sc.unstake_early(1, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(10));
+        assert_eq!(sc.compounded_rewards(&managed_address!(&user_address)).get(), managed_biguint!(0));
+        assert_eq!(sc.get_claimable_staking_rewards(managed_address!(&owner_address)), managed_biguint!(760));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE * 10 - 10 + 250));
+}
+
+#[test]
+fn export_views_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.encode_export(&ManagedBuffer::from(b"Man"), ExportEncoding::Base64), ManagedBuffer::from(b"TWFu"));
+        assert_eq!(sc.encode_export(&ManagedBuffer::from(b"Ma"), ExportEncoding::Base64), ManagedBuffer::from(b"TWE="));
+        assert_eq!(sc.encode_export(&ManagedBuffer::from(b"Ma"), ExportEncoding::Hex), ManagedBuffer::from(b"4d61"));
+
+        let history = sc.export_mint_history(0, ExportEncoding::Hex);
+        assert_eq!(history.entry_count, 2);
+        assert_eq!(history.next_from, 2);
+        assert!(history.complete);
+
+        let holders = sc.export_holders(0, ExportEncoding::Base64);
+        assert_eq!(holders.entry_count, 1);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn mint_deadline_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_block_timestamp(101);
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::Some(100));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "transaction deadline passed");
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::Some(101));
+        assert_eq!(sc.mint_count().get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn mint_with_message_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint_with_message(ManagedBuffer::from(b"gm;tags:x"), managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "message contains invalid characters");
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint_with_message(ManagedBuffer::from(b"Happy birthday, Sam!"), managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+        assert_eq!(sc.mint_message(1).get(), ManagedBuffer::from(b"Happy birthday, Sam!"));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn mint_picked_id_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_pick_mode(true, managed_biguint!(100)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        let mut preferred_ids = ManagedVec::<DebugApi, u32>::new();
+        preferred_ids.push(7);
+        sc.mint_picked(preferred_ids, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "not enough tokens");
+
+    // 7 is gone by the second pick, which falls back to 8
+    for _ in 0..2 {
+        let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE + 100), |sc| {
+            let mut preferred_ids = ManagedVec::<DebugApi, u32>::new();
+            preferred_ids.push(7);
+            preferred_ids.push(8);
+            sc.mint_picked(preferred_ids, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE + 100), OptionalArg::None);
+
+            StateChange::Commit
+        });
+        assert_eq!(result.result_status, 0);
+    }
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert!(!sc.is_mint_id_available(7));
+        assert!(!sc.is_mint_id_available(8));
+        assert!(!sc.is_mint_id_available(1));
+        assert_eq!(sc.get_available_mint_ids(6, 3).into_vec(), vec![6, 9, 10]);
+        assert_eq!(sc.highest_mint_id().get(), 8);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn waitlist_recycle_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_local_roles(&sc_address, NFT_TOKEN_ID, &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftBurn]);
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_max_supply(1), SCResult::Ok(()));
+        assert_eq!(sc.set_recycle_mode(true), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.join_waitlist(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE));
+        assert_eq!(sc.get_waitlist_position(managed_address!(&owner_address)), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.burn_to_recycle(managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+        assert!(!sc.is_sold_out());
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // the freed supply belongs to the queue
+    assert_eq!(mint_one(&mut em_setup), 4);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.settle_waitlist(10), 1);
+        assert_eq!(sc.nonce_mint_id(2).get(), 1);
+        assert_eq!(sc.get_waitlist_length(), 0);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&owner_address, NFT_TOKEN_ID, 2, &rust_biguint!(1), &());
+}
+
+#[test]
+fn mint_reservation_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE + 20));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_max_supply(2), SCResult::Ok(()));
+        assert_eq!(sc.set_reservation_config(managed_biguint!(10), 5, false), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(20), |sc| {
+        sc.reserve(2, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(20));
+        assert_eq!(sc.reserved_supply().get(), 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "remaining supply is reserved");
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+        assert_eq!(sc.reserved_supply().get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(5 * 60);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.release_expired_reservation(managed_address!(&owner_address));
+        assert_eq!(sc.reserved_supply().get(), 0);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(20));
+    assert_eq!(mint_one(&mut em_setup), 0);
+}
+
+#[test]
+fn partner_claim_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_nft_balance(&user_address, b"PARTNER-123456", 5, &rust_biguint!(1), &());
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.register_partner(managed_token_id!(b"PARTNER-123456"), 10, 0, 1_000, managed_biguint!(0)), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, b"PARTNER-123456", 5, &rust_biguint!(1), |sc| {
+        sc.claim_partner_mint(1);
+        assert_eq!(sc.get_partner_remaining_quota(1), 9);
+        assert_eq!(sc.mint_count().get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, b"PARTNER-123456", 5, &rust_biguint!(1), &());
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, b"PARTNER-123456", 5, &rust_biguint!(1), |sc| {
+        sc.claim_partner_mint(1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "partner token already used");
+}
+
+#[test]
+fn scheduled_payouts_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.add_payout_schedule(managed_address!(&owner_address), managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(100), 86_400, 86_400), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.trigger_payouts(), 0);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // two missed periods are paid at once
+    em_setup.blockchain_wrapper.set_block_timestamp(2 * 86_400 + 10);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.trigger_payouts(), 1);
+        assert_eq!(sc.payout_schedules(1).get().next_payout_timestamp, 3 * 86_400);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(200));
+}
+
+#[test]
+fn physical_redemption_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_redemption_config(true, false), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        assert_eq!(sc.redeem_physical(ManagedBuffer::from(&[7u8; 32][..]), managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1)), 1);
+        assert_eq!(sc.get_pending_redemptions(0, 10).len(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.mark_redemption_fulfilled(1), SCResult::Ok(()));
+        assert!(sc.get_redemption_state(1) == RedemptionState::Fulfilled);
+        assert_eq!(sc.get_pending_redemptions(0, 10).len(), 0);
+        assert_eq!(sc.get_rescuable_amount(managed_token_id!(NFT_TOKEN_ID), 1), managed_biguint!(0));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn holder_poll_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut options = ManagedVarArgs::new();
+        options.push(MultiArg2((ManagedBuffer::from(b"low"), managed_biguint!(NFT_TOKEN_PRICE / 2))));
+        options.push(MultiArg2((ManagedBuffer::from(b"high"), managed_biguint!(NFT_TOKEN_PRICE * 2))));
+        assert_eq!(sc.open_poll(PollTarget::NftTokenPrice, 1_000, options), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.vote(1, 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "only holders can vote");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.vote(1, 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(1_000);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.finalize_poll(1), 1);
+        assert_eq!(sc.nft_token_price().get(), managed_biguint!(NFT_TOKEN_PRICE / 2));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn migrate_payment_token_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.migrate_payment_token(TokenIdentifier::from(b"USDC-123456"), managed_biguint!(2), managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "mint must be paused");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.pause_feature(Feature::Mint);
+        sc.presale_price().set(&managed_biguint!(500));
+        sc.burn_refund().set(&managed_biguint!(100));
+        sc.migrate_payment_token(TokenIdentifier::from(b"USDC-123456"), managed_biguint!(2), managed_biguint!(1));
+        assert_eq!(sc.payment_token_id().get(), TokenIdentifier::from(b"USDC-123456"));
+        assert_eq!(sc.nft_token_price().get(), managed_biguint!(NFT_TOKEN_PRICE * 2));
+        assert_eq!(sc.presale_price().get(), managed_biguint!(1_000));
+        assert_eq!(sc.burn_refund().get(), managed_biguint!(200));
+        assert_eq!(sc.get_lock_token_id(), TokenIdentifier::from(PAYMENT_TOKEN_ID));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE));
+
+    // revenue stays booked in the token it was paid in
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let breakdown = sc.get_revenue_breakdown(TokenIdentifier::from(PAYMENT_TOKEN_ID));
+        assert_eq!(breakdown.primary_mint, managed_biguint!(NFT_TOKEN_PRICE));
+        let breakdown = sc.get_revenue_breakdown(TokenIdentifier::from(b"USDC-123456"));
+        assert_eq!(breakdown.primary_mint, managed_biguint!(0));
+        assert_eq!(sc.get_daily_mint_stats(0, TokenIdentifier::from(PAYMENT_TOKEN_ID)).revenue, managed_biguint!(NFT_TOKEN_PRICE));
+        let stats = sc.get_daily_mint_stats(0, TokenIdentifier::from(b"USDC-123456"));
+        assert_eq!(stats.mint_count, 1);
+        assert_eq!(stats.revenue, managed_biguint!(0));
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn mint_receipt_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_local_roles(&sc_address, b"RECEIPT-123456", &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftAddQuantity]);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.receipt_token_id().set(&TokenIdentifier::from(b"RECEIPT-123456"));
+        sc.receipt_roles_set().set(&true);
+        sc.set_receipts_enabled(0, true);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let attributes = ReceiptAttributes { drop_id: 0, phase_id: 0 };
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, b"RECEIPT-123456", 1, &rust_biguint!(2), &attributes);
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.receipt_supply(0, 0).get(), 2);
+        assert_eq!(sc.drop_receipt_supply(0).get(), 2);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn transfer_from_vault_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_nft_balance(&sc_address, b"PRIZE-123456", 1, &rust_biguint!(1), &());
+    em_setup.blockchain_wrapper.set_nft_balance(&sc_address, b"PRIZE-123456", 2, &rust_biguint!(10), &());
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut transfers = ManagedVarArgs::new();
+        transfers.push(MultiArg3((managed_address!(&user_address), 1, managed_biguint!(1))));
+        transfers.push(MultiArg3((managed_address!(&owner_address), 2, managed_biguint!(4))));
+        assert_eq!(sc.transfer_from_vault(TokenIdentifier::from(b"PRIZE-123456"), transfers), 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, b"PRIZE-123456", 1, &rust_biguint!(1), &());
+    em_setup.blockchain_wrapper.check_nft_balance(&owner_address, b"PRIZE-123456", 2, &rust_biguint!(4), &());
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut transfers = ManagedVarArgs::new();
+        transfers.push(MultiArg3((managed_address!(&user_address), 2, managed_biguint!(7))));
+        sc.transfer_from_vault(TokenIdentifier::from(b"PRIZE-123456"), transfers);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "amount exceeds unprotected balance");
+}
+
+#[test]
+fn known_owner_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.known_owner(1).get(), managed_address!(&user_address));
+    });
+    assert_eq!(result.result_status, 0);
+
+    // a wallet-to-wallet transfer is not seen until the new holder refreshes
+    em_setup.blockchain_wrapper.set_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(0), &());
+    em_setup.blockchain_wrapper.set_nft_balance(&owner_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.refresh_known_owner(TokenIdentifier::from(NFT_TOKEN_ID), 1, managed_biguint!(1));
+        assert_eq!(sc.known_owner(1).get(), managed_address!(&owner_address));
+        assert_eq!(sc.attributed_token_count(&managed_address!(&user_address)).get(), 0);
+        assert_eq!(sc.attributed_token_count(&managed_address!(&owner_address)).get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&owner_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+}
+
+#[test]
+fn relayer_reimbursement_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_egld_balance(&owner_address, &rust_biguint!(100));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(100), |sc| {
+        sc.fund_relayer_pool(managed_biguint!(100));
+        sc.set_relayer_fee(managed_biguint!(30));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.mint_sponsored(managed_address!(&owner_address), 0, 1_000, ManagedBuffer::from(&[0u8; 64][..]));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "signer not set");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.reimburse_relayer(&managed_address!(&user_address));
+        sc.reimburse_relayer(&managed_address!(&user_address));
+        assert_eq!(sc.relayer_pool().get(), managed_biguint!(40));
+        assert_eq!(sc.claim_relayer_reimbursement(), managed_biguint!(60));
+        assert_eq!(sc.relayer_mint_count(&managed_address!(&user_address)).get(), 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_egld_balance(&user_address, &rust_biguint!(60));
+}
+
+#[test]
+fn upgrade_veto_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.stake(managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(10);
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let code_hash = ManagedByteArray::new_from_bytes(&[1u8; 32]);
+        assert_eq!(sc.propose_upgrade(code_hash.clone(), ManagedBuffer::from(b"ipfs://first")), 1);
+        assert_eq!(sc.propose_upgrade(code_hash, ManagedBuffer::from(b"ipfs://second")), 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.veto_upgrade(1);
+        assert!(sc.upgrade_proposals(1).get().state == UpgradeProposalState::Vetoed);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(10 + UPGRADE_VETO_WINDOW);
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.execute_upgrade_proposal(1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "proposal not pending");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.execute_upgrade_proposal(2), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn max_supply_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.get_remaining_supply(), u32::MAX);
+        assert_eq!(sc.set_max_supply(1), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.get_remaining_supply(), 0);
+        assert!(sc.is_sold_out());
+    });
+    assert_eq!(result.result_status, 0);
+    assert_eq!(mint_one(&mut em_setup), 4);
+}
+
+#[test]
+fn mint_limit_per_address_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_max_mints_per_address(2), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 4);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.mints_per_address(&managed_address!(&user_address)).get(), 2);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn mint_multiple_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE * 3), |sc| {
+        let nonces = sc.mint_multiple(3, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE * 3), OptionalArg::None);
+        assert_eq!(nonces.into_vec(), vec![1, 2, 3]);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, NFT_TOKEN_ID, 3, &rust_biguint!(1), &());
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE * 7), |sc| {
+        sc.mint_multiple(11, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE * 7), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "amount exceeds per transaction cap");
+}
+
+#[test]
+fn whitelist_presale_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let owner_leaf = sc.whitelist_leaf(&managed_address!(&owner_address), 3);
+        let user_leaf = sc.whitelist_leaf(&managed_address!(&user_address), 1);
+        let mut proof = ManagedVarArgs::new();
+        proof.push(owner_leaf);
+        let root = sc.compute_merkle_root(user_leaf, proof);
+        assert_eq!(sc.set_whitelist(root, managed_biguint!(NFT_TOKEN_PRICE / 2)), SCResult::Ok(()));
+        assert_eq!(sc.set_presale_open(true), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    for expected_status in [0, 4] {
+        let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE / 2), |sc| {
+            let mut proof = ManagedVarArgs::new();
+            proof.push(sc.whitelist_leaf(&managed_address!(&owner_address), 3));
+            sc.mint_whitelist(1, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE / 2), proof);
+
+            StateChange::Commit
+        });
+        assert_eq!(result.result_status, expected_status);
+    }
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+}
+
+#[test]
+fn mint_with_signature_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint_with_signature(2, 0, 1_000, ManagedBuffer::from(&[0u8; 64][..]), TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "signer not set");
+
+    let owner_address = em_setup.owner_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_signer(managed_address!(&allowlist_signer_address())), SCResult::Ok(()));
+        assert_eq!(sc.set_chain_id(ManagedBuffer::from(b"D")), SCResult::Ok(()));
+        // before the auction starts its start price applies
+        let step = managed_biguint!(NFT_TOKEN_PRICE / 4);
+        assert_eq!(sc.set_dutch_auction(100, managed_biguint!(NFT_TOKEN_PRICE * 2), managed_biguint!(NFT_TOKEN_PRICE), step, 60), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let signature = sign_allowlist_mint(&sc_address, &user_address, 0, 1_000, 1);
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint_with_signature(1, 0, 1_000, ManagedBuffer::from(&signature[..]), TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "not enough tokens");
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE * 2), |sc| {
+        sc.mint_with_signature(1, 0, 1_000, ManagedBuffer::from(&signature[..]), TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE * 2));
+        assert_eq!(sc.allowlist_mints(&managed_address!(&user_address)).get(), 1);
+        assert_eq!(sc.signature_nonce(&managed_address!(&user_address)).get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE * 8));
+
+    let signature = sign_allowlist_mint(&sc_address, &user_address, 1, 1_000, 1);
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE * 2), |sc| {
+        sc.mint_with_signature(1, 1, 1_000, ManagedBuffer::from(&signature[..]), TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE * 2));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "allowlist allocation used up");
+}
+
+#[test]
+fn metadata_hashes_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let content_hash = [0xabu8; 32];
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut hashes = ManagedVarArgs::new();
+        hashes.push(MultiArg2((1, ManagedBuffer::from(&content_hash[..16]))));
+        sc.set_metadata_hashes(hashes);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "invalid metadata hash length");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut hashes = ManagedVarArgs::new();
+        hashes.push(MultiArg2((1, ManagedBuffer::from(&content_hash[..]))));
+        assert_eq!(sc.set_metadata_hashes(hashes), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut hashes = ManagedVarArgs::new();
+        hashes.push(MultiArg2((1, ManagedBuffer::from(&content_hash[..]))));
+        sc.set_metadata_hashes(hashes);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "token already minted");
+
+    // listing puts the token in the contract, where its data can be read
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.list_nft(managed_biguint!(1_000), managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let token_data = sc.blockchain().get_esdt_token_data(&sc.blockchain().get_sc_address(), &TokenIdentifier::from(NFT_TOKEN_ID), 1);
+        assert_eq!(token_data.hash, ManagedBuffer::from(&content_hash[..]));
+        assert!(sc.verify_metadata(1, ManagedBuffer::from(&content_hash[..])));
+        assert!(!sc.verify_metadata(1, ManagedBuffer::from(&[0u8; 32][..])));
+        assert!(!sc.verify_metadata(2, ManagedBuffer::from(&content_hash[..])));
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn sale_phases_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.add_sale_phase(SalePhase::Presale, 100, 200, managed_biguint!(NFT_TOKEN_PRICE / 2), 0, true), 1);
+        assert_eq!(sc.add_sale_phase(SalePhase::Public, 200, 300, managed_biguint!(NFT_TOKEN_PRICE), 1, false), 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(50);
+    assert_eq!(mint_one(&mut em_setup), 4);
+    em_setup.blockchain_wrapper.set_block_timestamp(150);
+    assert_eq!(mint_one(&mut em_setup), 4);
+    em_setup.blockchain_wrapper.set_block_timestamp(250);
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 4);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert!(sc.get_current_phase() == SalePhase::Public);
+        assert_eq!(sc.phase_mint_count(2).get(), 1);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn sale_window_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_sale_window(100, 200), SCResult::Ok(()));
+        assert_eq!(sc.get_sale_window().into_tuple(), (100, 200));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 4);
+    em_setup.blockchain_wrapper.set_block_timestamp(100);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.pause_minting(), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    assert_eq!(mint_one(&mut em_setup), 4);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(200);
+    assert_eq!(mint_one(&mut em_setup), 4);
+}
+
+#[test]
+fn dutch_auction_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let step = managed_biguint!(NFT_TOKEN_PRICE / 4);
+        assert_eq!(sc.set_dutch_auction(100, managed_biguint!(NFT_TOKEN_PRICE * 2), managed_biguint!(NFT_TOKEN_PRICE), step, 60), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(100 + 130);
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.get_current_price(), managed_biguint!(NFT_TOKEN_PRICE * 3 / 2));
+    });
+    assert_eq!(result.result_status, 0);
+    assert_eq!(mint_one(&mut em_setup), 4);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(100 + 10_000);
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.get_current_price(), managed_biguint!(NFT_TOKEN_PRICE));
+    });
+    assert_eq!(result.result_status, 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+}
+
+#[test]
+fn price_tiers_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut tiers = ManagedVarArgs::new();
+        tiers.push(MultiArg2((1, managed_biguint!(NFT_TOKEN_PRICE / 2))));
+        tiers.push(MultiArg2((3, managed_biguint!(NFT_TOKEN_PRICE))));
+        assert_eq!(sc.set_price_tiers(tiers), SCResult::Ok(()));
+        assert_eq!(sc.get_price_for_next_mint(), managed_biguint!(NFT_TOKEN_PRICE / 2));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // mints 1 and 2 straddle the first boundary
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE * 3 / 2), |sc| {
+        sc.mint_multiple(2, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE * 3 / 2), OptionalArg::None);
+        assert_eq!(sc.get_price_for_next_mint(), managed_biguint!(NFT_TOKEN_PRICE));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn mint_overpayment_refund_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE + 500), |sc| {
+        sc.mint(TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE + 500), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE * 9));
+}
+
+#[test]
+fn airdrop_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut receivers = ManagedVarArgs::new();
+        receivers.push(managed_address!(&user_address));
+        receivers.push(managed_address!(&owner_address));
+        assert_eq!(sc.airdrop(receivers), 2);
+        assert_eq!(sc.mint_count().get(), 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+    em_setup.blockchain_wrapper.check_nft_balance(&owner_address, NFT_TOKEN_ID, 2, &rust_biguint!(1), &());
+}
+
+#[test]
+fn mint_for_recipient_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint_for(managed_address!(&owner_address), TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+        assert_eq!(sc.mints_per_address(&managed_address!(&owner_address)).get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&owner_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+}
+
+#[test]
+fn delayed_reveal_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_placeholder_uris(ManagedBuffer::from(b"ipfs://hidden.png"), ManagedBuffer::from(b"ipfs://hidden.json")), SCResult::Ok(()));
+        assert_eq!(sc.resolve_uri_list(1).get(0), ManagedBuffer::from(b"ipfs://hidden.png"));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.reveal(ManagedBuffer::from(b"ipfs://images"), ManagedBuffer::from(b"ipfs://metadata")), SCResult::Ok(()));
+        assert_eq!(sc.get_revealed_uri(1).image_uri, ManagedBuffer::from(b"ipfs://images/1.png"));
+        assert_eq!(sc.resolve_uri_list(2).get(0), ManagedBuffer::from(b"ipfs://images/2.png"));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn provenance_hash_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_provenance_hash(ManagedBuffer::from(&[7u8; 32][..])), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_provenance_hash(ManagedBuffer::from(&[8u8; 32][..]));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "provenance hash already set");
+}
+
+#[test]
+fn random_mint_id_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_max_supply(3), SCResult::Ok(()));
+        assert_eq!(sc.set_random_mode(true), SCResult::Ok(()));
+        assert_eq!(sc.random_pool_size().get(), 3);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    for _ in 0..3 {
+        assert_eq!(mint_one(&mut em_setup), 0);
+    }
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let mut mint_ids = [sc.get_mint_id_for_nonce(1), sc.get_mint_id_for_nonce(2), sc.get_mint_id_for_nonce(3)];
+        mint_ids.sort_unstable();
+        assert_eq!(mint_ids, [1, 2, 3]);
+        assert_eq!(sc.random_pool_size().get(), 0);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn rarity_traits_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    let owner_address = em_setup.owner_address.clone();
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut values = ManagedVarArgs::new();
+        values.push(MultiArg3((ManagedBuffer::from(b"gold"), 1u64, 2u32)));
+        assert_eq!(sc.add_trait(ManagedBuffer::from(b"background"), values), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let (trait_name, value_name) = sc.get_attributes(1).into_vec().pop().unwrap().into_tuple();
+        assert_eq!(trait_name, ManagedBuffer::from(b"background"));
+        assert_eq!(value_name, ManagedBuffer::from(b"gold"));
+        assert_eq!(sc.tier_supply(2).get(), 1);
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.clear_traits();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "traits locked after the first mint");
+}
+
+#[test]
+fn open_edition_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_local_roles(&sc_address, b"EDITION-123456", &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftAddQuantity]);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.edition_token_id().set(&TokenIdentifier::from(b"EDITION-123456"));
+        sc.edition_roles_set().set(&true);
+        let edition_id = sc.create_open_edition(
+            ManagedBuffer::from(b"open"),
+            0,
+            TokenIdentifier::from(PAYMENT_TOKEN_ID),
+            managed_biguint!(NFT_TOKEN_PRICE),
+            3,
+            0,
+            ManagedVarArgs::new(),
+        );
+        assert_eq!(edition_id, 1);
+        assert!(sc.editions(1).get().kind == EditionKind::Open);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.create_open_edition(
+            ManagedBuffer::from(b"greedy"),
+            1_001,
+            TokenIdentifier::from(PAYMENT_TOKEN_ID),
+            managed_biguint!(NFT_TOKEN_PRICE),
+            1,
+            0,
+            ManagedVarArgs::new(),
+        );
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "royalties cannot exceed the committed maximum");
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE + 1), |sc| {
+        sc.mint_edition(1, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE + 1), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "payment does not match the price");
+
+    // edition nonces stay out of the main collection's history
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint_edition(1, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+        assert_eq!(sc.get_mint_history_length(), 0);
+        assert_eq!(sc.get_token_mint_history_length(TokenIdentifier::from(b"EDITION-123456")), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, b"EDITION-123456", 1, &rust_biguint!(3), &());
+    em_setup.blockchain_wrapper.check_nft_balance(&sc_address, b"EDITION-123456", 1, &rust_biguint!(1), &());
+}
+
+#[test]
+fn limited_edition_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_local_roles(&sc_address, b"EDITION-123456", &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftAddQuantity]);
+
+    // the whole supply is created up front and held for the buyers
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.edition_token_id().set(&TokenIdentifier::from(b"EDITION-123456"));
+        sc.edition_roles_set().set(&true);
+        let edition_id = sc.create_edition(
+            ManagedBuffer::from(b"limited"),
+            500,
+            TokenIdentifier::from(PAYMENT_TOKEN_ID),
+            managed_biguint!(NFT_TOKEN_PRICE),
+            2,
+            ManagedVarArgs::new(),
+        );
+        assert_eq!(edition_id, 1);
+        let edition = sc.editions(1).get();
+        assert!(edition.kind == EditionKind::Limited);
+        assert_eq!(edition.remaining, 2);
+        assert_eq!(sc.protected_amount(&TokenIdentifier::from(b"EDITION-123456"), edition.nonce).get(), managed_biguint!(2));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&sc_address, b"EDITION-123456", 1, &rust_biguint!(2), &());
+
+    for remaining in [1u32, 0] {
+        let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+            sc.mint_edition(1, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+            assert_eq!(sc.editions(1).get().remaining, remaining);
+            assert_eq!(sc.protected_amount(&TokenIdentifier::from(b"EDITION-123456"), 1).get(), managed_biguint!(remaining as u64));
+
+            StateChange::Commit
+        });
+        assert_eq!(result.result_status, 0);
+    }
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint_edition(1, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "edition sold out");
+
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, b"EDITION-123456", 1, &rust_biguint!(2), &());
+    em_setup.blockchain_wrapper.check_nft_balance(&sc_address, b"EDITION-123456", 1, &rust_biguint!(0), &());
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE * 8));
+}
+
+#[test]
+fn extra_collection_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_local_roles(&sc_address, b"SECOND-123456", &[EsdtLocalRole::NftCreate]);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let collection_id = sc.add_collection(
+            ManagedBuffer::from(b"Second"),
+            TokenIdentifier::from(PAYMENT_TOKEN_ID),
+            managed_biguint!(NFT_TOKEN_PRICE),
+            0,
+            ManagedBuffer::from(b"ipfs://second-images"),
+            ManagedBuffer::from(b"ipfs://second-metadata"),
+            1,
+        );
+        assert_eq!(collection_id, 1);
+        sc.collection_token_id(1).set(&TokenIdentifier::from(b"SECOND-123456"));
+        sc.collection_roles_set(1).set(&true);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        assert_eq!(sc.mint_from_collection(1, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None), 1);
+        assert_eq!(sc.collections(1).get().mint_count, 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, b"SECOND-123456", 1, &rust_biguint!(1), &());
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint_from_collection(1, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "sold out");
+}
+
+#[test]
+fn burn_with_refund_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_local_roles(&sc_address, NFT_TOKEN_ID, &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftBurn]);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    em_setup.blockchain_wrapper.set_esdt_balance(&sc_address, PAYMENT_TOKEN_ID, &rust_biguint!(1_000));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_burn_refund(managed_biguint!(100)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.burn(TokenIdentifier::from(NFT_TOKEN_ID), 1, managed_biguint!(1));
+        assert_eq!(sc.burned_count().get(), 1);
+        assert_eq!(sc.mint_count().get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&sc_address, NFT_TOKEN_ID, 1, &rust_biguint!(0), &());
+    em_setup.blockchain_wrapper.check_esdt_balance(&sc_address, PAYMENT_TOKEN_ID, &rust_biguint!(900));
+}
+
+#[test]
+fn fusion_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_local_roles(&sc_address, NFT_TOKEN_ID, &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftBurn]);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_fusion_input_count(2), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let inputs = [
+        TxInputESDT { token_identifier: NFT_TOKEN_ID.to_vec(), nonce: 1, value: rust_biguint!(1) },
+        TxInputESDT { token_identifier: NFT_TOKEN_ID.to_vec(), nonce: 2, value: rust_biguint!(1) },
+    ];
+    let result = em_setup.blockchain_wrapper.execute_esdt_multi_transfer(&user_address, &em_setup.em_wrapper, &inputs, |sc| {
+        assert_eq!(sc.fuse(), 3);
+        assert_eq!(sc.nonce_mint_id(3).get(), 1);
+        assert_eq!(sc.fusion_level(1).get(), 1);
+        assert_eq!(sc.mint_count().get(), 1);
+        assert_eq!(sc.burned_count().get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, NFT_TOKEN_ID, 3, &rust_biguint!(1), &());
+}
+
+#[test]
+fn trait_distribution_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut values = ManagedVarArgs::new();
+        values.push(MultiArg3((ManagedBuffer::from(b"gold"), 1u64, 0u32)));
+        values.push(MultiArg3((ManagedBuffer::from(b"silver"), 1u64, 0u32)));
+        assert_eq!(sc.add_trait(ManagedBuffer::from(b"background"), values), 1);
+        let mut values = ManagedVarArgs::new();
+        values.push(MultiArg3((ManagedBuffer::from(b"round"), 1u64, 0u32)));
+        assert_eq!(sc.add_trait(ManagedBuffer::from(b"shape"), values), 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    for _ in 0..3 {
+        assert_eq!(mint_one(&mut em_setup), 0);
+    }
+
+    // every counter matches the values the tokens were rolled with
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let mut expected = [0u32; 2];
+        for mint_id in 1..=3u32 {
+            let value_ids = sc.token_traits(mint_id).get();
+            assert_eq!(value_ids.len(), 2);
+            expected[value_ids.get(0) as usize - 1] += 1;
+            assert_eq!(value_ids.get(1), 1);
+        }
+
+        let distribution = sc.get_trait_distribution(1).into_vec();
+        assert_eq!(distribution.iter().map(|entry| entry.clone().into_tuple().1).sum::<u32>(), 3);
+        for entry in distribution {
+            let (value_id, count) = entry.into_tuple();
+            assert_eq!(count, expected[value_id as usize - 1]);
+            assert_eq!(sc.trait_value_count(1, value_id).get(), count);
+        }
+
+        let distribution = sc.get_trait_distribution(2).into_vec();
+        assert_eq!(distribution.len(), 1);
+        assert_eq!(distribution[0].clone().into_tuple(), (1, 3));
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn fusion_trait_counts_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_local_roles(&sc_address, NFT_TOKEN_ID, &[EsdtLocalRole::NftCreate, EsdtLocalRole::NftBurn]);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut values = ManagedVarArgs::new();
+        values.push(MultiArg3((ManagedBuffer::from(b"gold"), 1u64, 2u32)));
+        assert_eq!(sc.add_trait(ManagedBuffer::from(b"background"), values), 1);
+        assert_eq!(sc.set_fusion_input_count(2), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    // the fused token is counted once, the burned inputs not at all
+    let inputs = [
+        TxInputESDT { token_identifier: NFT_TOKEN_ID.to_vec(), nonce: 1, value: rust_biguint!(1) },
+        TxInputESDT { token_identifier: NFT_TOKEN_ID.to_vec(), nonce: 2, value: rust_biguint!(1) },
+    ];
+    let result = em_setup.blockchain_wrapper.execute_esdt_multi_transfer(&user_address, &em_setup.em_wrapper, &inputs, |sc| {
+        assert_eq!(sc.fuse(), 3);
+        assert_eq!(sc.trait_value_count(1, 1).get(), 1);
+        assert_eq!(sc.tier_supply(2).get(), 1);
+        assert!(sc.token_traits(2).is_empty());
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 3, &rust_biguint!(1), |sc| {
+        sc.burn(managed_token_id!(NFT_TOKEN_ID), 3, managed_biguint!(1));
+        assert_eq!(sc.trait_value_count(1, 1).get(), 0);
+        assert_eq!(sc.tier_supply(2).get(), 0);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn pending_attribute_update_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert!(!sc.update_attributes(1, ManagedBuffer::from(b"level:2")));
+        assert_eq!(sc.pending_attributes(1).get(), ManagedBuffer::from(b"level:2"));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.update_attributes(1, ManagedBuffer::from(b"level:3"));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "permission denied");
+}
+
+#[test]
+fn pending_add_uri_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut uris = ManagedVarArgs::new();
+        uris.push(ManagedBuffer::from(b"ipfs://animation/1.mp4"));
+        assert!(!sc.add_uri(1, uris));
+
+        let mut uris = ManagedVarArgs::new();
+        uris.push(ManagedBuffer::from(b"ipfs://metadata-v2/1.json"));
+        assert!(!sc.add_uri(1, uris));
+
+        let pending = sc.pending_uris(1).get();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending.get(1), ManagedBuffer::from(b"ipfs://metadata-v2/1.json"));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn full_config_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_max_supply(100), SCResult::Ok(()));
+        assert_eq!(sc.set_sale_window(100, 200), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let config = sc.get_full_config();
+        assert_eq!(config.nft_token_id, TokenIdentifier::from(NFT_TOKEN_ID));
+        assert_eq!(config.nft_token_name, ManagedBuffer::from(b"IceWorld"));
+        assert_eq!(config.payment_token_id, TokenIdentifier::from(PAYMENT_TOKEN_ID));
+        assert_eq!(config.nft_token_price, managed_biguint!(NFT_TOKEN_PRICE));
+        assert_eq!(config.royalties, 300);
+        assert_eq!(config.max_royalties, 1_000);
+        assert_eq!(config.image_base_uri, ManagedBuffer::from(b"https://ipfs.io/ipfs/QmXSFnUfdot3SgLsuZFdpefXii31YuyvtAD23NKdz9toar"));
+        assert_eq!(config.metadata_base_uri, ManagedBuffer::from(b"https://ipfs.io/ipfs/QmS1Zn9ytigCjkNtQPAduFuzptadNw2kC9asptoYq9ZBwS"));
+        assert!(!config.paused);
+        assert_eq!(config.max_supply, 100);
+        assert_eq!(config.sale_start_timestamp, 100);
+        assert_eq!(config.sale_end_timestamp, 200);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn sale_countdown_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    // no end configured
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.get_seconds_until_sale_start(), 0);
+        assert_eq!(sc.get_seconds_until_sale_end(), 0);
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_sale_window(100, 200), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // (timestamp, until start, until end): before the start, during the sale, at and after the end
+    for (timestamp, until_start, until_end) in [(40u64, 60u64, 160u64), (150, 0, 50), (200, 0, 0), (500, 0, 0)] {
+        em_setup.blockchain_wrapper.set_block_timestamp(timestamp);
+        let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+            assert_eq!(sc.get_seconds_until_sale_start(), until_start);
+            assert_eq!(sc.get_seconds_until_sale_end(), until_end);
+        });
+        assert_eq!(result.result_status, 0);
+    }
+}
+
+#[test]
+fn media_base_uris_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.add_media_base_uri(ManagedBuffer::from(b"ipfs://animations"), ManagedBuffer::from(b".mp4")), 1);
+        assert_eq!(sc.add_media_base_uri(ManagedBuffer::from(b"ipfs://audio"), ManagedBuffer::from(b".mp3")), 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // media URIs follow the image and metadata URIs, in the order they were added
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let uris = sc.get_token_uri_list(7).into_vec_of_buffers();
+        assert_eq!(uris.len(), 4);
+        assert_eq!(uris.get(0), ManagedBuffer::from(b"https://ipfs.io/ipfs/QmXSFnUfdot3SgLsuZFdpefXii31YuyvtAD23NKdz9toar/7.png"));
+        assert_eq!(uris.get(1), ManagedBuffer::from(b"https://ipfs.io/ipfs/QmS1Zn9ytigCjkNtQPAduFuzptadNw2kC9asptoYq9ZBwS/7.json"));
+        assert_eq!(uris.get(2), ManagedBuffer::from(b"ipfs://animations/7.mp4"));
+        assert_eq!(uris.get(3), ManagedBuffer::from(b"ipfs://audio/7.mp3"));
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+// nested-encoded before and after buffers, as carried by `value_changed`
+fn encoded_value_change(before: &[u8], after: &[u8]) -> Vec<u8> {
+    let mut data = Vec::new();
+    for value in [before, after] {
+        data.extend_from_slice(&(value.len() as u32).to_be_bytes());
+        data.extend_from_slice(value);
+    }
+
+    data
+}
+
+#[test]
+fn audit_events_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    // setters log the storage key with the value before and after
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_price(managed_biguint!(NFT_TOKEN_PRICE / 2)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    let log = result
+        .result_logs
+        .iter()
+        .find(|log| log.topics[0] == b"value_changed" && log.topics[1] == b"nft_token_price")
+        .expect("no value_changed event for the price");
+    let before = rust_biguint!(NFT_TOKEN_PRICE).to_bytes_be();
+    let after = rust_biguint!(NFT_TOKEN_PRICE / 2).to_bytes_be();
+    assert_eq!(log.data, encoded_value_change(&before, &after));
+
+    // collection mappers log each entry they gain or lose
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.pause_feature(Feature::Marketplace), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    let log = result
+        .result_logs
+        .iter()
+        .find(|log| log.topics[0] == b"entry_added" && log.topics[1] == b"paused_features")
+        .expect("no entry_added event for the paused feature");
+    assert_eq!(log.data, vec![Feature::Marketplace as u8]);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.unpause_feature(Feature::Marketplace), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    let log = result
+        .result_logs
+        .iter()
+        .find(|log| log.topics[0] == b"entry_removed" && log.topics[1] == b"paused_features")
+        .expect("no entry_removed event for the paused feature");
+    assert_eq!(log.data, vec![Feature::Marketplace as u8]);
+}
+
+#[test]
+fn sale_parameter_setters_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_price(managed_biguint!(NFT_TOKEN_PRICE / 2)), SCResult::Ok(()));
+        assert_eq!(sc.set_image_base_uri(ManagedBuffer::from(b"ipfs://images-v2")), SCResult::Ok(()));
+        assert_eq!(sc.set_metadata_base_uri(ManagedBuffer::from(b"ipfs://metadata-v2")), SCResult::Ok(()));
+        assert_eq!(sc.nft_token_price().get(), managed_biguint!(NFT_TOKEN_PRICE / 2));
+        assert_eq!(sc.image_base_uri().get(), ManagedBuffer::from(b"ipfs://images-v2"));
+        assert_eq!(sc.metadata_base_uri().get(), ManagedBuffer::from(b"ipfs://metadata-v2"));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_price(managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 4);
+
+    // base URIs follow the metadata manager role, not ownership
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.grant_role(Role::MetadataManager, managed_address!(&user_address)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_image_base_uri(ManagedBuffer::from(b"ipfs://images-v3")), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn shareholders_split_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut shareholders = ManagedVarArgs::new();
+        shareholders.push(MultiArg2((managed_address!(&owner_address), 6_000)));
+        shareholders.push(MultiArg2((managed_address!(&user_address), 3_000)));
+        assert_eq!(sc.set_shareholders(shareholders), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "shares must add up to 100%");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut shareholders = ManagedVarArgs::new();
+        shareholders.push(MultiArg2((managed_address!(&owner_address), 6_000)));
+        shareholders.push(MultiArg2((managed_address!(&user_address), 4_000)));
+        assert_eq!(sc.set_shareholders(shareholders), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let payment_token = TokenIdentifier::from(PAYMENT_TOKEN_ID);
+        assert_eq!(sc.share_balance(&managed_address!(&owner_address), &payment_token).get(), managed_biguint!(NFT_TOKEN_PRICE / 10 * 6));
+        assert_eq!(sc.claim_share(payment_token.clone()), managed_biguint!(NFT_TOKEN_PRICE / 10 * 4));
+        assert_eq!(sc.protected_amount(&payment_token, 0).get(), managed_biguint!(NFT_TOKEN_PRICE / 10 * 6));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn shareholder_accounting_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut shareholders = ManagedVarArgs::new();
+        shareholders.push(MultiArg2((managed_address!(&user_address), 10_000)));
+        assert_eq!(sc.set_shareholders(shareholders), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let payment_token = TokenIdentifier::from(PAYMENT_TOKEN_ID);
+        assert_eq!(sc.get_claimable_amount(managed_address!(&user_address), payment_token.clone()), managed_biguint!(NFT_TOKEN_PRICE * 2));
+        assert_eq!(sc.total_distributed(&payment_token).get(), managed_biguint!(NFT_TOKEN_PRICE * 2));
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn withdraw_after_mint_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.pause_minting(), SCResult::Ok(()));
+        assert_eq!(sc.withdraw(OptionalArg::Some(TokenIdentifier::from(PAYMENT_TOKEN_ID))), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(em_setup.em_wrapper.address_ref(), PAYMENT_TOKEN_ID, &rust_biguint!(0));
+}
+
+#[test]
+fn minter_tracking_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let user_address = em_setup.user_address.clone();
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.minter_of(1).get(), managed_address!(&user_address));
+        assert_eq!(sc.get_nonces_minted_by(managed_address!(&user_address), 1, 5).into_vec(), vec![2]);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn minted_tokens_pagination_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let user_address = em_setup.user_address.clone();
+
+    for _ in 0..3 {
+        assert_eq!(mint_one(&mut em_setup), 0);
+    }
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.get_collection_mint_count(), 3);
+        assert_eq!(sc.get_minted_nonces(1, 10).into_vec(), vec![2, 3]);
+
+        let records = sc.get_mint_records(2, 1).into_vec();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].nonce, 3);
+        assert_eq!(records[0].mint_id, 3);
+        assert_eq!(records[0].minter, managed_address!(&user_address));
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn sale_status_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let status = sc.get_sale_status();
+        assert_eq!(status.token_id, TokenIdentifier::from(NFT_TOKEN_ID));
+        assert_eq!(status.price, managed_biguint!(NFT_TOKEN_PRICE));
+        assert_eq!(status.payment_token, TokenIdentifier::from(PAYMENT_TOKEN_ID));
+        assert_eq!(status.minted, 1);
+        assert!(!status.paused);
+        assert!(status.current_phase == SalePhase::None);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn admins_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.add_admin(managed_address!(&user_address)), SCResult::Ok(()));
+        assert_eq!(sc.get_admins().into_vec(), vec![managed_address!(&user_address)]);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_price(managed_biguint!(NFT_TOKEN_PRICE * 2)), SCResult::Ok(()));
+        let _ = sc.add_admin(managed_address!(&user_address));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "Endpoint can only be called by owner");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.remove_admin(managed_address!(&user_address)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_price(managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "permission denied");
+}
+
+#[test]
+fn contract_caller_guard_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let proxy_wrapper = em_setup.blockchain_wrapper.create_sc_account(
+        &rust_biguint!(0),
+        Some(&owner_address),
+        elrond_nftmanager::contract_obj,
+        WASM_PATH,
+    );
+    let proxy_address = proxy_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&proxy_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE * 2));
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&proxy_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint(TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "contracts cannot mint");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_contract_callers_allowed(true), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&proxy_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint(TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn block_mint_throttle_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_max_mints_per_block(1), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_nonce(5);
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 4);
+
+    em_setup.blockchain_wrapper.set_block_nonce(6);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.mints_in_block(5).get(), 1);
+        assert_eq!(sc.mints_in_block(6).get(), 1);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn timelock_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.increase_timelock_delay(100), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_price(managed_biguint!(5));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "change is timelocked, use proposeAction");
+
+    // pricing setters without a proposal flow stay closed
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_price_tiers(ManagedVarArgs::new());
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "change is timelocked, use proposeAction");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.disable_dutch_auction();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "change is timelocked, use proposeAction");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.propose_action(TimelockAction::Price, ManagedBuffer::new_from_bytes(&[5u8])), 1);
+        assert_eq!(sc.get_pending_actions().len(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.set_block_timestamp(50);
+    let result = em_setup.blockchain_wrapper.execute_tx(&em_setup.user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.execute_action(1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "action still timelocked");
+
+    em_setup.blockchain_wrapper.set_block_timestamp(100);
+    let result = em_setup.blockchain_wrapper.execute_tx(&em_setup.user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.execute_action(1), SCResult::Ok(()));
+        assert_eq!(sc.nft_token_price().get(), managed_biguint!(5));
+        assert_eq!(sc.get_pending_actions().len(), 0);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn pause_guard_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.pause_minting(), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE * 2), |sc| {
+        sc.mint_multiple(2, TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE * 2), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "minting paused");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let mut receivers = ManagedVarArgs::new();
+        receivers.push(managed_address!(&user_address));
+        sc.airdrop(receivers);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "minting paused");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.start_minting(), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+}
+
+#[test]
+fn migrate_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.migrate();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "contract already migrated");
+
+    // storage as left by a deployment that predates version tracking
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.contract_version().clear();
+        sc.max_royalties().clear();
+        sc.highest_mint_id().clear();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let result = sc.init(
+            TokenIdentifier::from(PAYMENT_TOKEN_ID),
+            managed_biguint!(1),
+            0,
+            ManagedBuffer::new(),
+            ManagedBuffer::new(),
+            0,
+            0,
+        );
+        assert_eq!(result, SCResult::Ok(()));
+        assert_eq!(sc.migrate(), CONTRACT_VERSION);
+
+        assert_eq!(sc.contract_version().get(), CONTRACT_VERSION);
+        assert_eq!(sc.nft_token_price().get(), managed_biguint!(NFT_TOKEN_PRICE));
+        assert_eq!(sc.max_royalties().get(), 300);
+        assert_eq!(sc.highest_mint_id().get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn metadata_attributes_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    em_setup.blockchain_wrapper.set_block_timestamp(1_650_000_000);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.build_attributes(1), ManagedBuffer::from(b"creatime:1650000000"));
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_metadata_cid(ManagedBuffer::from(b"QmCid")), SCResult::Ok(()));
+        assert_eq!(sc.set_tags(ManagedBuffer::from(b"art,pfp")), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(
+            sc.build_attributes(1),
+            ManagedBuffer::from(b"metadata:QmCid/1.json;tags:art,pfp;creatime:1650000000")
+        );
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn creation_time_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    em_setup.blockchain_wrapper.set_block_timestamp(1_650_000_000);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.get_creation_time(1), 1_650_000_000);
+        assert_eq!(sc.decode_creation_time(sc.build_attributes(1)), 1_650_000_000);
+
+        let mut legacy_attributes = ManagedBuffer::from(b"creatime:");
+        legacy_attributes.append_bytes(&1_650_000_000u64.to_le_bytes());
+        legacy_attributes.append_bytes(b";message:gm");
+        assert_eq!(sc.decode_creation_time(legacy_attributes), 1_650_000_000);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn referral_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let referred_price = NFT_TOKEN_PRICE / 100 * 95;
+    let commission = referred_price / 10;
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_referral_rates(1_000, 500), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(referred_price), |sc| {
+        sc.mint_with_referrer(managed_address!(&owner_address), managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(referred_price), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let stats = sc.referrer_stats(&managed_address!(&owner_address)).get();
+        assert_eq!(stats.referrals, 1);
+        assert_eq!(stats.volume, managed_biguint!(referred_price));
+        assert_eq!(sc.claim_referral_rewards(managed_token_id!(PAYMENT_TOKEN_ID)), managed_biguint!(commission));
+        assert_eq!(sc.referral_balance(&managed_address!(&owner_address), &managed_token_id!(PAYMENT_TOKEN_ID)).get(), managed_biguint!(0));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(em_setup.em_wrapper.address_ref(), PAYMENT_TOKEN_ID, &rust_biguint!(referred_price - commission));
+}
+
+#[test]
+fn coupon_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let code_hash = sc.crypto().sha256(&ManagedBuffer::from(b"SPRING"));
+        assert_eq!(sc.add_coupon(code_hash, 5_000, 1), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE / 2), |sc| {
+        sc.mint_with_coupon(ManagedBuffer::from(b"SPRING"), managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE / 2), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE / 2), |sc| {
+        sc.mint_with_coupon(ManagedBuffer::from(b"SPRING"), managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE / 2), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "invalid coupon");
+}
+
+#[test]
+fn mint_pass_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&user_address, b"PASS-123456", &rust_biguint!(2));
+    em_setup.blockchain_wrapper.set_esdt_local_roles(&sc_address, b"PASS-123456", &[EsdtLocalRole::Burn]);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_mint_pass(managed_token_id!(b"PASS-123456"), 0, managed_biguint!(0)), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, b"PASS-123456", 0, &rust_biguint!(2), |sc| {
+        sc.mint_with_pass();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "one mint pass per mint");
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, b"PASS-123456", 0, &rust_biguint!(1), |sc| {
+        assert_eq!(sc.mint_with_pass(), 1);
+        assert_eq!(sc.mint_passes_redeemed().get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, b"PASS-123456", &rust_biguint!(1));
+    em_setup.blockchain_wrapper.check_esdt_balance(&sc_address, b"PASS-123456", &rust_biguint!(0));
+}
+
+#[test]
+fn team_reserve_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_max_supply(3), SCResult::Ok(()));
+        assert_eq!(sc.set_team_reserve(2), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    assert_eq!(mint_one(&mut em_setup), 4);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.mint_team_reserve(2, managed_address!(&user_address)).into_vec(), vec![2, 3]);
+        assert_eq!(sc.team_reserve().get(), 0);
+        assert_eq!(sc.team_reserve_minted().get(), 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, NFT_TOKEN_ID, 3, &rust_biguint!(1), &());
+}
+
+#[test]
+fn staking_position_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let user_address = em_setup.user_address.clone();
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.stake(managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let position: StakingPosition<DebugApi> = sc.get_staking_position(managed_address!(&user_address));
+        assert_eq!(position.staked_nonces.len(), 1);
+        assert_eq!(position.staked_nonces.get(0), 1);
+        assert!(position.unbonding_nonces.is_empty());
+        assert!(position.stake_weight > 0);
+        assert!(!position.auto_compound);
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn pause_feature_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.pause_feature(Feature::Marketplace), SCResult::Ok(()));
+        assert!(sc.get_paused_features().into_vec() == [Feature::Marketplace]);
+        assert!(sc.is_feature_paused(Feature::Marketplace));
+        assert!(!sc.is_feature_paused(Feature::Mint));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // only the paused subsystem stops
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.list_nft(managed_biguint!(1_000), managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "feature is paused");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.unpause_feature(Feature::Marketplace), SCResult::Ok(()));
+        assert!(sc.get_paused_features().into_vec().is_empty());
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.list_nft(managed_biguint!(1_000), managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn marketplace_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(1_000));
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let user_balance = rust_biguint!(NFT_TOKEN_PRICE * 9);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        assert!(sc.get_listing_state(1) == ListingState::None);
+        sc.list_nft(managed_biguint!(1_000), managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+        assert_eq!(sc.get_listing_count(), 1);
+        assert!(sc.get_listing_state(1) == ListingState::Active);
+        assert!(sc.get_auction_state(1) == AuctionState::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(999), |sc| {
+        sc.buy_nft(1, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(999), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "payment does not match the price");
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(1_000), |sc| {
+        sc.buy_nft(1, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(1_000), OptionalArg::None);
+        assert_eq!(sc.get_listing_count(), 0);
+        assert_eq!(sc.known_owner(1).get(), managed_address!(&owner_address));
+        assert!(sc.get_listing_state(1) == ListingState::Sold);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // 3% royalties stay with the collection
+    em_setup.blockchain_wrapper.check_nft_balance(&owner_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &(user_balance + rust_biguint!(970)));
+    em_setup.blockchain_wrapper.check_esdt_balance(&sc_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE + 30));
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.list_nft(managed_biguint!(2_000), managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+        assert!(sc.get_listing_state(1) == ListingState::Active);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.delist_nft(1);
+        assert!(sc.get_listing_state(1) == ListingState::Cancelled);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&owner_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+}
+
+#[test]
+fn guest_artist_royalties_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let artist_address = em_setup.blockchain_wrapper.create_user_account(&rust_biguint!(0));
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(1_000));
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.register_guest_artist(1, 5, managed_address!(&artist_address), 5_000);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "range includes minted ids");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.register_guest_artist(2, 5, managed_address!(&artist_address), 5_000), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert!(matches!(sc.get_creator_of(1), OptionalArg::None));
+        match sc.get_creator_of(2) {
+            OptionalArg::Some(creator) => assert_eq!(creator, managed_address!(&artist_address)),
+            OptionalArg::None => panic!("creator not found"),
+        }
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 2, &rust_biguint!(1), |sc| {
+        sc.list_nft(managed_biguint!(1_000), managed_token_id!(NFT_TOKEN_ID), 2, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // the artist takes half of the 3% royalties
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(1_000), |sc| {
+        sc.buy_nft(2, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(1_000), OptionalArg::None);
+        let payment_token = TokenIdentifier::from(PAYMENT_TOKEN_ID);
+        assert_eq!(sc.creator_royalties(&managed_address!(&artist_address), &payment_token).get(), managed_biguint!(15));
+        assert_eq!(sc.get_revenue_breakdown(payment_token).royalties, managed_biguint!(15));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&artist_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.claim_creator_royalties(TokenIdentifier::from(PAYMENT_TOKEN_ID));
+        assert_eq!(sc.creator_royalties(&managed_address!(&artist_address), &TokenIdentifier::from(PAYMENT_TOKEN_ID)).get(), managed_biguint!(0));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&artist_address, PAYMENT_TOKEN_ID, &rust_biguint!(15));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&artist_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.claim_creator_royalties(TokenIdentifier::from(PAYMENT_TOKEN_ID));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "nothing to claim");
+}
+
+#[test]
+fn auction_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    let bidder_address = em_setup.blockchain_wrapper.create_user_account(&rust_biguint!(0));
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(100));
+    em_setup.blockchain_wrapper.set_esdt_balance(&bidder_address, PAYMENT_TOKEN_ID, &rust_biguint!(200));
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let user_balance = rust_biguint!(NFT_TOKEN_PRICE * 9);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.start_auction(managed_biguint!(100), 10, managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+        assert!(sc.get_auction_state(1) == AuctionState::Running);
+        assert!(sc.get_listing_state(1) == ListingState::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(100), |sc| {
+        sc.bid(1, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(100), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&bidder_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(200), |sc| {
+        sc.bid(1, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(200), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(100));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.end_auction(1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "auction still running");
+
+    em_setup.blockchain_wrapper.set_block_timestamp(10);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert!(sc.get_auction_state(1) == AuctionState::Ended);
+        sc.end_auction(1);
+        assert_eq!(sc.get_listing_count(), 0);
+        assert!(sc.get_auction_state(1) == AuctionState::Settled);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    em_setup.blockchain_wrapper.check_nft_balance(&bidder_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &(user_balance + rust_biguint!(194)));
+}
+
+#[test]
+fn marketplace_fee_cap_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    // max royalties are 1_000 basis points
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_marketplace_fee(9_001);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "fee and royalties cannot exceed 100%");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.set_marketplace_fee(9_000), SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn offer_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(200));
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let user_balance = rust_biguint!(NFT_TOKEN_PRICE * 9);
+
+    for _ in 0..2 {
+        let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(100), |sc| {
+            sc.make_offer(1, 10, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(100));
+
+            StateChange::Commit
+        });
+        assert_eq!(result.result_status, 0);
+    }
+    em_setup.blockchain_wrapper.check_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(0));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.withdraw_offer(2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "offer still open");
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.accept_offer(1, managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&owner_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &(user_balance + rust_biguint!(97)));
+
+    em_setup.blockchain_wrapper.set_block_timestamp(10);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.withdraw_offer(2);
+        assert_eq!(sc.get_offers_for_nonce(1).len(), 0);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(100));
+}
+
+#[test]
+fn holder_count_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(NFT_TOKEN_PRICE));
+
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.holder_count().get(), 0);
+    });
+    assert_eq!(result.result_status, 0);
+
+    // a second mint by the same holder does not count them twice
+    for expected_tokens in [1u32, 2] {
+        assert_eq!(mint_one(&mut em_setup), 0);
+        let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+            assert_eq!(sc.holder_count().get(), 1);
+            assert_eq!(sc.attributed_token_count(&managed_address!(&user_address)).get(), expected_tokens);
+        });
+        assert_eq!(result.result_status, 0);
+    }
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.mint(TokenIdentifier::from(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE), OptionalArg::None);
+        assert_eq!(sc.holder_count().get(), 2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn raffle_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(100));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert!(sc.get_raffle_state() == RaffleState::None);
+        let result = sc.set_raffle(0, 10, managed_biguint!(100));
+        assert_eq!(result, SCResult::Ok(()));
+        assert!(sc.get_raffle_state() == RaffleState::Registration);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    for address in [&owner_address, &user_address] {
+        let result = em_setup.blockchain_wrapper.execute_esdt_transfer(address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(100), |sc| {
+            sc.enter_raffle(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(100));
+
+            StateChange::Commit
+        });
+        assert_eq!(result.result_status, 0);
+    }
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.draw_winners(1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "raffle still open");
+
+    em_setup.blockchain_wrapper.set_block_timestamp(10);
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.paused().set(true);
+        sc.draw_winners(1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "minting paused");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert!(sc.get_raffle_state() == RaffleState::RegistrationClosed);
+        assert_eq!(sc.draw_winners(1).len(), 1);
+        assert_eq!(sc.raffle_winner_count().get(), 1);
+        assert!(sc.get_raffle_state() == RaffleState::Drawn);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let mut refunded = 0;
+    for address in [&owner_address, &user_address] {
+        let result = em_setup.blockchain_wrapper.execute_tx(address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+            sc.claim_refund();
+
+            StateChange::Commit
+        });
+        if result.result_status == 0 {
+            refunded += 1;
+        } else {
+            assert_eq!(result.result_message, "nothing to refund");
+        }
+    }
+    assert_eq!(refunded, 1);
+}
+
+#[test]
+fn raffle_payment_token_migration_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(100));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let result = sc.set_raffle(0, 10, managed_biguint!(100));
+        assert_eq!(result, SCResult::Ok(()));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    for address in [&owner_address, &user_address] {
+        let result = em_setup.blockchain_wrapper.execute_esdt_transfer(address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(100), |sc| {
+            sc.enter_raffle(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(100));
+
+            StateChange::Commit
+        });
+        assert_eq!(result.result_status, 0);
+    }
+
+    // the escrowed entries stay in the old token
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.pause_feature(Feature::Mint);
+        sc.migrate_payment_token(TokenIdentifier::from(b"USDC-123456"), managed_biguint!(2), managed_biguint!(1));
+        sc.unpause_feature(Feature::Mint);
+        assert_eq!(sc.raffle_price().get(), managed_biguint!(200));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(0));
+
+    em_setup.blockchain_wrapper.set_block_timestamp(10);
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.draw_winners(1).len(), 1);
+        let breakdown = sc.get_revenue_breakdown(TokenIdentifier::from(PAYMENT_TOKEN_ID));
+        assert_eq!(breakdown.primary_mint, managed_biguint!(100));
+        let breakdown = sc.get_revenue_breakdown(TokenIdentifier::from(b"USDC-123456"));
+        assert_eq!(breakdown.primary_mint, managed_biguint!(0));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // the loser gets the entry back in the token it was paid in
+    let mut refunded = 0;
+    for (address, balance_before) in [(&owner_address, 0), (&user_address, NFT_TOKEN_PRICE * 10 - 100)] {
+        let result = em_setup.blockchain_wrapper.execute_tx(address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+            assert_eq!(sc.claim_refund(), managed_biguint!(100));
+
+            StateChange::Commit
+        });
+        if result.result_status == 0 {
+            refunded += 1;
+            em_setup.blockchain_wrapper.check_esdt_balance(address, PAYMENT_TOKEN_ID, &rust_biguint!(balance_before + 100));
+        } else {
+            assert_eq!(result.result_message, "nothing to refund");
+        }
+    }
+    assert_eq!(refunded, 1);
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.protected_amount(&TokenIdentifier::from(PAYMENT_TOKEN_ID), 0).get(), managed_biguint!(0));
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn rental_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+    em_setup.blockchain_wrapper.set_esdt_balance(&owner_address, PAYMENT_TOKEN_ID, &rust_biguint!(20));
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let user_balance = rust_biguint!(NFT_TOKEN_PRICE * 9);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, NFT_TOKEN_ID, 1, &rust_biguint!(1), |sc| {
+        sc.list_for_rent(managed_biguint!(10), 3, managed_token_id!(NFT_TOKEN_ID), 1, managed_biguint!(1));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&owner_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(20), |sc| {
+        sc.rent(1, 2, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(20));
+        assert!(sc.is_rented_by(1, managed_address!(&owner_address)));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_esdt_balance(&user_address, PAYMENT_TOKEN_ID, &(user_balance + rust_biguint!(20)));
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.reclaim(1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "token still rented");
+
+    em_setup.blockchain_wrapper.set_block_timestamp(2 * 86_400);
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert!(!sc.is_rented_by(1, managed_address!(&owner_address)));
+        sc.reclaim(1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    em_setup.blockchain_wrapper.check_nft_balance(&user_address, NFT_TOKEN_ID, 1, &rust_biguint!(1), &());
+}
+
+#[test]
+fn soulbound_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_transfer_role(managed_address!(&owner_address));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "collection not soulbound");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.soulbound().set(true);
+        sc.local_roles_set().set(true);
+        sc.transfer_role_addresses().insert(managed_address!(&sc_address));
+        let _ = sc.unset_transfer_role(managed_address!(&sc_address));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "cannot remove the last transfer role");
+}
+
+#[test]
+fn compliance_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.freeze_nft(managed_address!(&user_address), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "collection not freezable");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.wipe_nft(managed_address!(&user_address), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "collection not wipeable");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.unfreeze_nft(managed_address!(&user_address), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "Endpoint can only be called by owner");
+}
+
+#[test]
+fn royalties_override_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let result = sc.set_royalties_override(2, 2, 1_000);
+        assert_eq!(result, SCResult::Ok(()));
+        assert_eq!(sc.get_royalties_for_next(), 300);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_royalties_override(1, 5, 500);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "range overlaps another override");
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        assert_eq!(sc.get_royalties_for_next(), 1_000);
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_royalties_override(5, 5, 500);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "royalties can only decrease after sale start");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.timelock_delay().set(10);
+        let _ = sc.remove_royalties_override(2);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "change is timelocked, use proposeAction");
+}
+
+#[test]
+fn collection_transfer_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&user_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.transfer_collection_ownership(managed_address!(&user_address));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "Endpoint can only be called by owner");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.collection_new_owner().set(&managed_address!(&user_address));
+        let _ = sc.transfer_nft_create_role(managed_address!(&user_address));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "collection already transferred");
+}
+
+#[test]
+fn special_roles_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.set_local_roles(OptionalArg::Some(managed_address!(&user_address)), ManagedVarArgs::new());
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "no roles given");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.local_roles_set().set(true);
+        let mut roles = ManagedVarArgs::new();
+        roles.push(NftRole::NftBurn);
+        let _ = sc.set_local_roles(OptionalArg::Some(managed_address!(&user_address)), roles);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn stop_creation_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    let companion_address = em_setup.blockchain_wrapper.create_user_account(&rust_biguint!(0));
+    let sc_address = em_setup.em_wrapper.address_ref().clone();
+
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    // the companion's create role goes first, the flag waits for this contract's
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.special_roles_callback(managed_address!(&companion_address), true, ManagedAsyncCallResult::Ok(()));
+        assert_eq!(sc.get_create_role_addresses().len(), 1);
+
+        let _ = sc.stop_creation();
+        assert!(!sc.creation_stopped().get());
+        sc.stop_creation_callback(managed_address!(&companion_address), ManagedAsyncCallResult::Ok(()));
+        assert_eq!(sc.get_create_role_addresses().len(), 0);
+        assert!(!sc.creation_stopped().get());
+
+        sc.stop_creation_callback(managed_address!(&sc_address), ManagedAsyncCallResult::Ok(()));
+        assert!(sc.creation_stopped().get());
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        let _ = sc.stop_creation();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "creation already stopped");
+
+    assert_eq!(mint_one(&mut em_setup), 4);
+}
+
+#[test]
+fn setup_retry_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert!(sc.get_issue_status() == SetupStatus::Done);
+        let _ = sc.retry_set_local_roles();
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "no failed roles request to retry");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.roles_status().set(SetupStatus::Failed);
+        let _ = sc.retry_set_local_roles();
+        assert!(sc.get_roles_status() == SetupStatus::Pending);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+}
+
+#[test]
+fn paused_settle_waitlist_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.max_supply().set(1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+    assert_eq!(mint_one(&mut em_setup), 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(NFT_TOKEN_PRICE), |sc| {
+        sc.join_waitlist(managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(NFT_TOKEN_PRICE));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.max_supply().set(2);
+        sc.paused().set(true);
+        sc.settle_waitlist(1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "minting paused");
+}
+
+#[test]
+fn paused_team_reserve_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.max_supply().set(10);
+        sc.team_reserve().set(2);
+        sc.paused().set(true);
+        sc.mint_team_reserve(1, managed_address!(&owner_address));
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "minting paused");
+}
+
+#[test]
+fn buy_piece_test() {
+    let mut em_setup = setup_elrond_nftmanager(elrond_nftmanager::contract_obj);
+    let owner_address = em_setup.owner_address.clone();
+    let user_address = em_setup.user_address.clone();
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        sc.register_piece(ManagedBuffer::from(b"Special"), ManagedBuffer::from(b"https://example.com/special.png"), managed_biguint!(100), 2_000);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "royalties cannot exceed the committed maximum");
+
+    let result = em_setup.blockchain_wrapper.execute_tx(&owner_address, &em_setup.em_wrapper, &rust_biguint!(0), |sc| {
+        assert_eq!(sc.register_piece(ManagedBuffer::from(b"Special"), ManagedBuffer::from(b"https://example.com/special.png"), managed_biguint!(100), 500), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(150), |sc| {
+        sc.buy_piece(1, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(150), OptionalArg::None);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_message, "payment does not match the price");
+
+    let result = em_setup.blockchain_wrapper.execute_esdt_transfer(&user_address, &em_setup.em_wrapper, PAYMENT_TOKEN_ID, 0, &rust_biguint!(100), |sc| {
+        let nonce = sc.buy_piece(1, managed_token_id!(PAYMENT_TOKEN_ID), managed_biguint!(100), OptionalArg::None);
+        assert_eq!(sc.mint_count().get(), 1);
+        assert_eq!(sc.nonce_mint_id(nonce).get(), 1);
+
+        StateChange::Commit
+    });
+    assert_eq!(result.result_status, 0);
+
+    // piece sales count like any other paid mint
+    let result = em_setup.blockchain_wrapper.execute_query(&em_setup.em_wrapper, |sc| {
+        let stats = sc.get_daily_mint_stats(0, TokenIdentifier::from(PAYMENT_TOKEN_ID));
+        assert_eq!(stats.mint_count, 1);
+        assert_eq!(stats.revenue, managed_biguint!(100));
+        assert_eq!(sc.get_revenue_breakdown(TokenIdentifier::from(PAYMENT_TOKEN_ID)).primary_mint, managed_biguint!(100));
+    });
+    assert_eq!(result.result_status, 0);
+}
@@ -8,20 +8,434 @@ elrond_wasm_node::wasm_endpoints! {
     elrond_nftmanager
     (
         callBack
+        acceptOffer
+        acceptTreasury
+        addAdmin
+        addCollection
+        addCoupon
+        addDiscountTier
+        addDropBaseUris
+        addMediaBaseUri
+        addPayoutSchedule
+        addRarityBoost
+        addSalePhase
+        addTrait
+        addUri
+        airdrop
+        applyAttributeUpdate
+        areCollectionRolesSet
+        areContractCallersAllowed
+        areEditionRolesSet
+        areLocalRolesSet
+        areReceiptRolesSet
+        areReceiptsEnabled
+        bid
+        burn
+        burnToRecycle
+        buyNft
+        buyPiece
+        canFreeze
+        canWipe
+        cancelAction
+        cancelPayoutSchedule
+        cancelTreasuryChange
+        cancelUpgradeProposal
+        claimCreatorRoyalties
+        claimPartnerMint
+        claimPartnerMintWithVoucher
+        claimReferralRewards
+        claimRefund
+        claimRelayerReimbursement
+        claimReward
+        claimShare
+        claimStakingRewards
+        clearDiscountTiers
+        clearMediaBaseUris
+        clearMintPass
+        clearRarityBoosts
+        clearSalePhases
+        clearTokenUriOverride
+        clearTraits
+        createEdition
+        createOpenEdition
+        createRewardDistribution
+        decodeCreationTime
+        delistNft
+        depositRewards
+        disableDutchAuction
+        doRedemptionsBurn
+        drawWinners
+        endAuction
+        enterRaffle
+        executeAction
+        executeUpgradeProposal
+        exportConfig
+        exportHolders
+        exportMintHistory
+        finalizePoll
+        freezeNft
+        fundRelayerPool
+        fundStakingRewards
+        fuse
+        getActivePayoutSchedules
+        getActivePhaseId
+        getAdmins
+        getAllowlistMints
+        getAttributedTokenCount
+        getAttributes
+        getAuctionState
+        getAvailableMintIds
+        getAvailablePieceCount
+        getAvailablePieces
+        getBurnRefund
+        getBurnedCount
+        getChainId
+        getClaimableAmount
+        getClaimableStakingRewards
+        getCollection
+        getCollectionCount
+        getCollectionMintCount
+        getCollectionNewOwner
+        getCollectionTokenId
+        getCompoundedRewards
+        getCoupon
+        getCreateRoleAddresses
+        getCreationTime
+        getCreatorOf
+        getCreatorRoyalties
+        getCurrentDay
+        getCurrentPhase
+        getCurrentPrice
+        getDailyMintStats
+        getDailyMintStatsRange
+        getDiscountTiers
+        getDistributionCount
+        getDropBaseUris
+        getDropReceiptSupply
+        getEarlyUnstakeFee
+        getEarlyUnstakeRewardPenalty
+        getEdition
+        getEditionCount
+        getEditionTokenId
+        getFullConfig
+        getFusionCount
+        getFusionInputCount
+        getFusionLevel
+        getGuestArtists
+        getHighestMintId
+        getHolderCount
         getImageBaseUri
+        getIssueStatus
+        getKnownOwner
+        getListing
+        getListingCount
+        getListingState
+        getListings
+        getLockTokenId
+        getLongestStakeDuration
+        getMarketplaceFee
+        getMaxMintsPerAddress
+        getMaxMintsPerBlock
+        getMaxMintsPerTx
+        getMaxRoyalties
+        getMaxSupply
+        getMediaBaseUris
         getMetadataBaseUri
+        getMetadataCid
+        getMetadataHash
         getMintCount
+        getMintDiscount
+        getMintHistory
+        getMintHistoryLength
+        getMintIdForNonce
+        getMintIdOfNonce
+        getMintMessage
+        getMintPassNonce
+        getMintPassPrice
+        getMintPassTokenId
+        getMintPassesRedeemed
+        getMintPriceFor
+        getMintRecords
+        getMintedNonces
+        getMinterOf
+        getMintsForAddress
+        getMintsInBlock
         getNftTokenId
         getNftTokenName
         getNftTokenPrice
+        getNoncesMintedBy
+        getOffer
+        getOffersForNonce
+        getPartner
+        getPartnerCount
+        getPartnerRemainingQuota
+        getPausedFeatures
         getPaymentTokenId
+        getPayoutSchedule
+        getPayoutScheduleCount
+        getPendingActions
+        getPendingAttributes
+        getPendingRedemptions
+        getPendingTreasuryAddress
+        getPendingTreasuryTimestamp
+        getPendingUnbonds
+        getPendingUris
+        getPhaseConfig
+        getPhaseCount
+        getPhaseMintStats
+        getPhaseMints
+        getPickPremium
+        getPiece
+        getPieceCount
+        getPlaceholderImageUri
+        getPlaceholderMetadataUri
+        getPoll
+        getPollCount
+        getPollResults
+        getPresaleMints
+        getPresalePrice
+        getPriceForNextMint
+        getPriceTiers
+        getProtectedAmount
+        getProvenanceHash
+        getRaffleEnd
+        getRaffleEntrantCount
+        getRaffleEntrants
+        getRaffleEntry
+        getRafflePrice
+        getRaffleStart
+        getRaffleState
+        getRaffleWinnerCount
+        getRandomPoolSize
+        getRarityBoost
+        getRarityBoosts
+        getReceiptNonce
+        getReceiptSupply
+        getReceiptTokenId
+        getRedemption
+        getRedemptionCount
+        getRedemptionState
+        getReferralBalance
+        getReferralCommission
+        getReferralDiscount
+        getReferrerStats
+        getReferrers
+        getRelayerBalance
+        getRelayerClaimed
+        getRelayerFee
+        getRelayerMintCount
+        getRelayerPool
+        getRemainingSupply
+        getRental
+        getRentals
+        getRescuableAmount
+        getReservation
+        getReservationDeposit
+        getReservationDurationMinutes
+        getReservedSupply
+        getRevealedUri
+        getRevenueBreakdown
+        getRewardDistribution
+        getRewardPool
+        getRoleMembers
+        getRolesStatus
         getRoyalties
+        getRoyaltiesForNext
+        getRoyaltiesOverrides
+        getRoyaltiesPercent
+        getSaleState
+        getSaleStatus
+        getSaleWindow
+        getSecondsUntilSaleEnd
+        getSecondsUntilSaleStart
+        getShareBalance
+        getShareholders
+        getSignatureNonce
+        getSigner
+        getStakeTimestamp
+        getStakeWeight
+        getStakedNonces
+        getStakingPosition
+        getStakingRewardRate
+        getStakingRewardReserve
+        getStakingRewardTokenId
+        getTags
+        getTeamReserve
+        getTeamReserveMinted
+        getTierSupplies
+        getTierSupply
+        getTimelockDelay
+        getTokenLock
+        getTokenMintHistory
+        getTokenMintHistoryLength
+        getTokenUriList
+        getTokenUris
+        getTotalDistributed
+        getTotalStakeWeight
+        getTotalStaked
+        getTraitDistribution
+        getTraitNames
+        getTraitTable
+        getTraitValueCount
+        getTransferRoleAddresses
+        getTreasuryAddress
+        getUnbondingPeriod
+        getUpgradeProposal
+        getUpgradeProposalCount
+        getVersion
+        getWaitlist
+        getWaitlistLength
+        getWaitlistPosition
+        getWhitelistRoot
+        grantRole
+        hasClaimedForPartner
+        hasRole
+        hasVetoedUpgrade
+        hasVoted
+        increaseTimelockDelay
+        isAutoCompounding
+        isCreationStopped
+        isDutchAuctionEnabled
+        isFeaturePaused
+        isMintIdAvailable
+        isPartnerTokenUsed
         isPaused
+        isPickModeEnabled
+        isPresaleOpen
+        isRaffleDrawn
+        isRandomModeEnabled
+        isRecycleModeEnabled
+        isRedemptionEnabled
+        isRentedBy
+        isReservationDepositForfeited
+        isRevealed
+        isRewardClaimed
+        isSoldOut
+        isSoulbound
+        issueCollectionToken
+        issueEditionToken
         issueNft
+        issueReceiptToken
+        joinWaitlist
+        leaveWaitlist
+        listForRent
+        listNft
+        lockTokens
+        makeOffer
+        markRedemptionFulfilled
+        migrate
+        migratePaymentToken
         mint
+        mintEdition
+        mintFor
+        mintFromCollection
+        mintMultiple
+        mintPicked
+        mintSponsored
+        mintTeamReserve
+        mintWhitelist
+        mintWithCoupon
+        mintWithMessage
+        mintWithPass
+        mintWithReferrer
+        mintWithSignature
+        openPoll
+        pauseFeature
         pauseMinting
+        proposeAction
+        proposeTreasury
+        proposeUpgrade
+        reclaim
+        redeemPhysical
+        refreshKnownOwner
+        registerGuestArtist
+        registerPartner
+        registerPiece
+        releaseExpiredReservation
+        removeAdmin
+        removeCoupon
+        removeRoyaltiesOverride
+        rent
+        rescueTokens
+        reserve
+        retryIssueNft
+        retrySetLocalRoles
+        reveal
+        revokeRole
+        setAutoCompound
+        setBurnRefund
+        setChainId
+        setCollectionLocalRoles
+        setCollectionPrice
+        setContractCallersAllowed
+        setDutchAuction
+        setEarlyUnstakePenalty
+        setEditionLocalRoles
+        setFusionInputCount
+        setImageBaseUri
         setLocalRoles
+        setMarketplaceFee
+        setMaxMintsPerAddress
+        setMaxMintsPerBlock
+        setMaxMintsPerTx
+        setMaxSupply
+        setMetadataBaseUri
+        setMetadataCid
+        setMetadataHashes
+        setMintPass
+        setNftStakeWeight
+        setPartnerQuota
+        setPickMode
+        setPlaceholderUris
+        setPresaleOpen
+        setPrice
+        setPriceTiers
+        setProvenanceHash
+        setRaffle
+        setRandomMode
+        setReceiptLocalRoles
+        setReceiptsEnabled
+        setRecycleMode
+        setRedemptionConfig
+        setReferralRates
+        setRelayerFee
+        setReservationConfig
+        setRoyalties
+        setRoyaltiesOverride
+        setSaleWindow
+        setShareholders
+        setSigner
+        setStakingRewardRate
+        setStakingRewardToken
+        setTags
+        setTeamReserve
+        setTokenUriOverride
+        setTransferRole
+        setUnbondingPeriod
+        setWhitelist
+        settleWaitlist
+        stake
+        startAuction
         startMinting
+        stopCreation
+        sweepUnclaimedRewards
+        transferCollectionOwnership
+        transferFromVault
+        transferNftCreateRole
+        triggerPayouts
+        unfreezeNft
+        unlockTokens
+        unpauseFeature
+        unsetTransferRole
+        unstake
+        unstakeEarly
+        updateAttributes
+        verifyMetadata
+        vetoUpgrade
+        vote
+        wipeNft
         withdraw
+        withdrawOffer
+        withdrawUnstaked
     )
 }